@@ -0,0 +1,124 @@
+use clap::{ArgGroup, Parser};
+use elden_mod_loader_gui::{
+    check_game_closed, new_io_error, toggle_files,
+    utils::{
+        display::{DisplayName, DisplayState},
+        ini::common::{Cfg, Config},
+    },
+    PathResult,
+};
+use slint::SharedString;
+use std::io::ErrorKind;
+use tracing::{error, info, info_span};
+
+use crate::{get_ini_dir, get_or_update_game_dir, ERROR_VAL, OK_VAL};
+
+/// Elden Ring mod manager by: WardLordRuby  
+/// run without arguments to start the app
+#[derive(Parser, Debug)]
+#[command(version, about, long_about = None)]
+#[command(group(ArgGroup::new("state").args(["on", "off"]).requires("toggle")))]
+pub struct Cli {
+    /// name of a registered mod to toggle, requires `--on` or `--off`
+    #[arg(long, value_name = "NAME", requires = "state", conflicts_with = "list")]
+    toggle: Option<String>,
+
+    /// enable the mod supplied to `--toggle`
+    #[arg(long)]
+    on: bool,
+
+    /// disable the mod supplied to `--toggle`
+    #[arg(long)]
+    off: bool,
+
+    /// list all registered mods and their state
+    #[arg(long)]
+    list: bool,
+
+    /// round-trips a synthetic config in a temp directory and prints PASS/FAIL for each stage  
+    /// used to diagnose environments that break parsing, the real config is never read
+    #[arg(long, hide = true, exclusive = true)]
+    self_test: bool,
+}
+
+impl Cli {
+    #[inline]
+    fn has_operation(&self) -> bool {
+        self.toggle.is_some() || self.list || self.self_test
+    }
+}
+
+/// parses command line arguments, returns `Some(exit_code)` if an operation was performed  
+/// returns `None` if no operation was supplied and the ui should be started  
+/// results are printed to stdout, release builds do not attach a console so output must be redirected
+pub fn run() -> Option<i32> {
+    let cli = Cli::parse();
+    if !cli.has_operation() {
+        return None;
+    }
+    let span = info_span!("cli");
+    let _guard = span.enter();
+
+    if cli.self_test {
+        return Some(if crate::self_test::run() { OK_VAL } else { ERROR_VAL });
+    }
+    match run_operation(cli) {
+        Ok(()) => Some(OK_VAL),
+        Err(err) => {
+            error!("{err}");
+            eprintln!("{err}");
+            Some(ERROR_VAL)
+        }
+    }
+}
+
+fn run_operation(cli: Cli) -> std::io::Result<()> {
+    let mut ini = Cfg::read(get_ini_dir())?;
+    ini.migrate()?;
+    let PathResult::Full(game_dir) = ini.attempt_locate_game()? else {
+        return new_io_error!(
+            ErrorKind::NotFound,
+            "Could not locate Elden Ring, start the app without arguments to set the game directory"
+        );
+    };
+    let game_dir = get_or_update_game_dir(Some(game_dir));
+
+    if cli.list {
+        // validation can rewrite the ini and toggle files, listing only reads what is saved
+        ini.collect_mods(&*game_dir, None, true)
+            .mods
+            .iter()
+            .for_each(|reg_mod| {
+                if reg_mod.is_always_active() {
+                    println!("{}: always active", DisplayName(&reg_mod.name));
+                } else {
+                    println!("{}: {}", DisplayName(&reg_mod.name), DisplayState(reg_mod.state));
+                }
+            });
+        return Ok(());
+    }
+
+    let name = cli.toggle.expect("`has_operation` and `list` is false");
+    let new_state = match (cli.on, cli.off) {
+        (true, false) => true,
+        (false, true) => false,
+        _ => unreachable!("clap requires exactly one of `--on` or `--off` with `--toggle`"),
+    };
+    let mut reg_mod = ini.get_mod(&SharedString::from(name), &game_dir, None)?;
+    if reg_mod.is_always_active() {
+        println!(
+            "Can not toggle: {}, mod has no .dll files and is always active",
+            DisplayName(&reg_mod.name)
+        );
+        return Ok(());
+    }
+    if reg_mod.state == new_state {
+        println!("{} is already {}", DisplayName(&reg_mod.name), DisplayState(new_state));
+        return Ok(());
+    }
+    check_game_closed()?;
+    toggle_files(&game_dir, new_state, &mut reg_mod, Some(ini.path()))?;
+    info!("{} {}", DisplayName(&reg_mod.name), DisplayState(new_state));
+    println!("{}: {}", DisplayName(&reg_mod.name), DisplayState(new_state));
+    Ok(())
+}
@@ -1,7 +1,11 @@
 pub mod utils {
     pub mod display;
+    pub mod error;
     pub mod installer;
+    pub mod lock;
+    pub mod mock;
     pub mod subscriber;
+    pub mod watcher;
     pub mod ini {
         pub mod common;
         pub mod mod_loader;
@@ -11,20 +15,28 @@ pub mod utils {
 }
 
 use ini::Ini;
-use tracing::{info, instrument, trace, warn};
+use sha2::{Digest, Sha256};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System};
+use tracing::{error, info, instrument, trace, warn};
+use unicode_normalization::UnicodeNormalization;
 use utils::{
-    display::{DisplayName, DisplayState, DisplayVec, IntoIoError},
+    display::{DisplayName, DisplayState, DisplayVec, IntoIoError, ModError, GAME_PASS_MSG},
     ini::{
         common::{Cfg, Config},
         parser::{IniProperty, RegMod, Setup},
         writer::{new_cfg, save_path},
     },
+    installer::{check_files_unlocked, normalize_path},
+    subscriber::OpTimer,
+    watcher::mark_self_write,
 };
 
 use std::{
     collections::{HashMap, HashSet},
     io::ErrorKind,
     path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
 };
 
 // changing the order of any of the following consts would not be good
@@ -43,6 +55,12 @@ pub const REQUIRED_GAME_FILES: [&str; 3] = [
     "oo2core_6_win64.dll",
     "eossdk-win64-shipping.dll",
 ];
+/// default location of a Game Pass / Windows Store install, searched after `DEFAULT_GAME_DIR`
+pub const GAME_PASS_DIR: [&str; 3] = ["XboxGames", "ELDEN RING", "Content"];
+/// directories Game Pass / Windows Store games are installed within
+pub const GAME_PASS_ROOTS: [&str; 2] = ["XboxGames", "WindowsApps"];
+/// files only found in the directory of a Game Pass / Windows Store install
+pub const GAME_PASS_FILES: [&str; 2] = ["MicrosoftGame.config", "gamelaunchhelper.exe"];
 
 pub const OFF_STATE: &str = ".disabled";
 
@@ -54,8 +72,71 @@ pub const INI_SECTIONS: [Option<&str>; 4] = [
     Some("registered-mods"),
     Some("mod-files"),
 ];
-pub const INI_KEYS: [&str; 3] = ["dark_mode", "save_log", "game_dir"];
+pub const INI_KEYS: [&str; 14] = [
+    "dark_mode",
+    "save_log",
+    "game_dir",
+    "editor",
+    "suppress_eac_warning",
+    "install_preview_limit",
+    "watch_files",
+    "log_level",
+    "backup_configs",
+    "hash_files",
+    "move_files",
+    "schema_version",
+    "launch_method",
+    "confirm_level",
+];
 pub const DEFAULT_INI_VALUES: [bool; 2] = [true, true];
+/// layout of the sections and keys of `INI_NAME`, saved with key "schema_version"  
+/// bump this and add a step to `Cfg::migrate` for any change that would break a saved config
+pub const SCHEMA_VERSION: u32 = 1;
+pub const DEFAULT_EDITOR: &str = "notepad";
+pub const DEFAULT_SUPPRESS_EAC_WARNING: bool = false;
+pub const DEFAULT_INSTALL_PREVIEW_LIMIT: usize = 9;
+pub const DEFAULT_WATCH_FILES: bool = false;
+/// release builds are compiled with `release_max_level_info` so "trace" and "debug" have no effect
+pub const LOG_LEVELS: [&str; 5] = ["trace", "debug", "info", "warn", "error"];
+pub const DEFAULT_LOG_LEVEL: tracing::Level = tracing::Level::INFO;
+pub const DEFAULT_BACKUP_CONFIGS: bool = true;
+pub const DEFAULT_HASH_FILES: bool = false;
+/// installs copy files by default so the user's originals are left untouched
+pub const DEFAULT_MOVE_FILES: bool = false;
+pub const LAUNCH_METHODS: [&str; 2] = ["steam", "direct"];
+pub const DEFAULT_LAUNCH_METHOD: LaunchMethod = LaunchMethod::Steam;
+pub const STEAM_APP_ID: u32 = 1245620;
+pub const STEAM_EXE: &str = "steam.exe";
+pub const CONFIRM_LEVELS: [&str; 3] = ["all", "destructive_only", "minimal"];
+pub const DEFAULT_CONFIRM_LEVEL: ConfirmLevel = ConfirmLevel::All;
+/// files larger than this many bytes are not hashed, large asset mods would take too long to verify
+pub const HASH_SIZE_LIMIT: u64 = 64 * 1024 * 1024;
+pub const INSTALL_PREVIEW_RANGE: std::ops::RangeInclusive<usize> = 1..=100;
+/// optional section, not part of `INI_SECTIONS` so inis saved before notes existed are still valid
+pub const NOTES_SECTION: Option<&str> = Some("descriptions");
+/// optional section, holds the keys of mods pinned to the top of the mod list
+pub const PINNED_SECTION: Option<&str> = Some("pinned-mods");
+/// optional section, holds the keys of the mods shown in the favorites bar in the order they were added
+pub const FAVORITES_SECTION: Option<&str> = Some("favorites");
+pub const MAX_FAVORITES: usize = 8;
+/// optional section, holds the category each mod is grouped under in the mod list
+pub const CATEGORIES_SECTION: Option<&str> = Some("categories");
+/// mods without a saved category are grouped under this name
+pub const DEFAULT_CATEGORY: &str = "Uncategorized";
+/// optional section, holds previously validated "game_dir"s most recent first
+pub const RECENT_DIRS_SECTION: Option<&str> = Some("recent-game-dirs");
+pub const MAX_RECENT_DIRS: usize = 5;
+/// optional section, holds the sha-256 of registered files keyed by short path, see `hash_key`
+pub const HASHES_SECTION: Option<&str> = Some("file-hashes");
+/// optional section, holds the directory a mod's asset files are read from, see `FileSet::Dir`
+pub const TRACKED_DIRS_SECTION: Option<&str> = Some("tracked-dirs");
+/// optional section, holds the keys of mods whose `.ini` files toggle with their `.dll` files
+pub const TOGGLE_CONFIGS_SECTION: Option<&str> = Some("toggle-configs");
+/// mods registered with more files than this within their install directory are offered to be  
+/// tracked by directory, see `RegMod::track_by_dir`
+pub const TRACK_BY_DIR_THRESHOLD: usize = 500;
+/// patterns that match more files than this are rejected, see `expand_file_pattern`
+pub const MAX_PATTERN_MATCHES: usize = 100;
 pub const ARRAY_KEY: &str = "array[]";
 pub const ARRAY_VALUE: &str = "array";
 
@@ -67,11 +148,31 @@ pub const LOADER_FILES: [&str; 4] = [
 ];
 pub const LOADER_EXAMPLE: &str = "Example.dll";
 pub const LOADER_SECTIONS: [Option<&str>; 2] = [Some("modloader"), Some("loadorder")];
+/// section of "mod_loader_config.ini" that holds the values of section "loadorder" while suspended
+pub const SUSPENDED_ORDER_SECTION: Option<&str> = Some("suspended-loadorder");
+/// every option the mod loader reads from section "modloader", a new option only needs to be added  
+/// here along with its default in `DEFAULT_LOADER_VALUES`, see `ModLoaderCfg::get_bool_key`
 pub const LOADER_KEYS: [&str; 2] = ["load_delay", "show_terminal"];
 pub const DEFAULT_LOADER_VALUES: [&str; 2] = ["5000", "0"];
+const _: () = assert!(LOADER_KEYS.len() == DEFAULT_LOADER_VALUES.len());
+/// milliseconds, longer delays stall the loader before any mod is loaded
+pub const LOAD_DELAY_RANGE: std::ops::RangeInclusive<u32> = 0..=60000;
 
 pub const ANTI_CHEAT_EXE: &str = "toggle_anti_cheat.exe";
 
+/// windows error code for `ERROR_SHARING_VIOLATION`
+pub const SHARING_VIOLATION: i32 = 32;
+/// windows error code for `ERROR_LOCK_VIOLATION`
+pub const LOCK_VIOLATION: i32 = 33;
+/// time to wait before each retry of a rename that failed because the file was locked
+const RENAME_RETRY_DELAYS: [Duration; 3] = [
+    Duration::from_millis(100),
+    Duration::from_millis(200),
+    Duration::from_millis(400),
+];
+
+pub const ASSET_EXTENSIONS: [&str; 4] = [".dds", ".tpf", ".bnd", ".dcx"];
+
 pub type OrderMap = HashMap<String, usize>;
 pub type DllSet<'a> = HashSet<&'a str>;
 
@@ -82,6 +183,19 @@ macro_rules! new_io_error {
     };
 }
 
+/// path of the mod loader's config `LOADER_FILES[3]` within `game_dir`
+#[inline]
+pub fn loader_cfg_path(game_dir: &Path) -> PathBuf {
+    game_dir.join(LOADER_FILES[3])
+}
+
+/// path of the app's config `INI_NAME` within `base_dir`
+#[inline]
+pub fn ini_cfg_path(base_dir: &Path) -> PathBuf {
+    base_dir.join(INI_NAME)
+}
+
+#[derive(Debug)]
 pub struct PathErrors<'a> {
     pub ok_paths_short: Vec<&'a Path>,
     pub err_paths_long: Vec<&'a Path>,
@@ -104,13 +218,33 @@ impl PathErrors<'_> {
 pub fn shorten_paths<'a, P: AsRef<Path>>(
     paths: &'a [P],
     remove: &P,
+) -> Result<Vec<&'a Path>, PathErrors<'a>> {
+    shorten_paths_with(paths, remove.as_ref(), |path, remove| {
+        path.strip_prefix(remove).ok()
+    })
+}
+
+/// same as `shorten_paths` except the prefix test ignores case, the returned paths keep their original case  
+/// use this for paths that came from user input, eg. `c:\` and `C:\` are treated as the same prefix  
+#[instrument(level = "trace", skip_all)]
+pub fn shorten_paths_ci<'a, P: AsRef<Path>>(
+    paths: &'a [P],
+    remove: &P,
+) -> Result<Vec<&'a Path>, PathErrors<'a>> {
+    shorten_paths_with(paths, remove.as_ref(), strip_prefix_ci)
+}
+
+fn shorten_paths_with<'a, P: AsRef<Path>>(
+    paths: &'a [P],
+    remove: &Path,
+    strip_prefix: impl Fn(&'a Path, &Path) -> Option<&'a Path>,
 ) -> Result<Vec<&'a Path>, PathErrors<'a>> {
     let mut results = PathErrors::with_capacity(paths.len());
     paths
         .iter()
-        .for_each(|path| match path.as_ref().strip_prefix(remove) {
-            Ok(shortened_path) => results.ok_paths_short.push(shortened_path),
-            Err(_) => results.err_paths_long.push(path.as_ref()),
+        .for_each(|path| match strip_prefix(path.as_ref(), remove) {
+            Some(shortened_path) => results.ok_paths_short.push(shortened_path),
+            None => results.err_paths_long.push(path.as_ref()),
         });
     if !results.err_paths_long.is_empty() {
         trace!(
@@ -124,6 +258,20 @@ pub fn shorten_paths<'a, P: AsRef<Path>>(
     Ok(results.ok_paths_short)
 }
 
+/// case-insensitive `Path::strip_prefix`, components are compared in lowercase
+fn strip_prefix_ci<'a>(path: &'a Path, prefix: &Path) -> Option<&'a Path> {
+    let mut components = path.components();
+    for prefix_component in prefix.components() {
+        let component = components.next()?;
+        if component.as_os_str().to_string_lossy().to_lowercase()
+            != prefix_component.as_os_str().to_string_lossy().to_lowercase()
+        {
+            return None;
+        }
+    }
+    Some(components.as_path())
+}
+
 /// finds the current state of the input Path and returns an owned Pathbuf in the opposite state
 pub fn toggle_path_state(path: &Path) -> PathBuf {
     let mut path_str = path.to_string_lossy().to_string();
@@ -138,6 +286,14 @@ pub fn toggle_path_state(path: &Path) -> PathBuf {
     }
 }
 
+/// replaces all `'/'` in `path` with `'\\'`, short paths are always saved with `'\\'` as the separator
+pub fn normalize_separators(path: PathBuf) -> PathBuf {
+    match path.to_str() {
+        Some(path_str) if path_str.contains('/') => PathBuf::from(path_str.replace('/', "\\")),
+        _ => path,
+    }
+}
+
 /// takes in an array of PathBufs, finds file_name name and outputs the new_state version
 pub fn toggle_paths_state(file_paths: &[PathBuf], new_state: bool) -> Vec<PathBuf> {
     file_paths
@@ -168,7 +324,204 @@ pub fn toggle_paths_state(file_paths: &[PathBuf], new_state: bool) -> Vec<PathBu
         .collect()
 }
 
-/// toggle the state of the files saved in `reg_mod.files.dll`  
+/// retries a rename that failed because the file was locked, returns the last error once all  
+/// `RENAME_RETRY_DELAYS` are used up  
+/// **Note:** the current thread sleeps between attempts, do not call from the ui thread
+pub fn rename_with_retry(path: &Path, new_path: &Path) -> std::io::Result<()> {
+    let mut delays = RENAME_RETRY_DELAYS.iter();
+    loop {
+        match std::fs::rename(path, new_path) {
+            Ok(()) => return Ok(()),
+            Err(err)
+                if err.kind() == ErrorKind::PermissionDenied
+                    || err.raw_os_error() == Some(SHARING_VIOLATION) =>
+            {
+                let Some(delay) = delays.next() else {
+                    return Err(err);
+                };
+                warn!(
+                    file = ?path.file_name().unwrap(),
+                    "{err}, retrying in: {}ms",
+                    delay.as_millis()
+                );
+                std::thread::sleep(*delay);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// returns `true` if a process named `REQUIRED_GAME_FILES[0]` is running
+pub fn is_game_running() -> bool {
+    let mut system = System::new();
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::new());
+    let running = system
+        .processes()
+        .values()
+        .any(|process| process.name().eq_ignore_ascii_case(REQUIRED_GAME_FILES[0]));
+    trace!(running, "checked for game process");
+    running
+}
+
+/// returns an error if Elden Ring is running, the game holds `.dll`s it has loaded open so  
+/// renaming them would fail part way through, call this before toggling the loader or any mod
+pub fn check_game_closed() -> std::io::Result<()> {
+    if is_game_running() {
+        return new_io_error!(
+            ErrorKind::PermissionDenied,
+            "Elden Ring is currently running, close Elden Ring first and try again"
+        );
+    }
+    Ok(())
+}
+
+/// how `launch_game` starts Elden Ring, saved with key "launch_method" as one of `LAUNCH_METHODS`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaunchMethod {
+    /// opens `steam://run/{STEAM_APP_ID}` so Steam starts the game
+    Steam,
+    /// runs `REQUIRED_GAME_FILES[0]` from "game_dir"
+    Direct,
+}
+
+impl LaunchMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LaunchMethod::Steam => LAUNCH_METHODS[0],
+            LaunchMethod::Direct => LAUNCH_METHODS[1],
+        }
+    }
+
+    fn spawn(&self, game_dir: &Path) -> std::io::Result<()> {
+        match self {
+            LaunchMethod::Steam => {
+                // explorer exits with the same status whether or not the url could be opened
+                if !steam_installed(game_dir) {
+                    return new_io_error!(ErrorKind::NotFound, "Could not find a Steam install");
+                }
+                Command::new("explorer")
+                    .arg(format!("steam://run/{STEAM_APP_ID}"))
+                    .spawn()?;
+            }
+            LaunchMethod::Direct => {
+                let exe = game_dir.join(REQUIRED_GAME_FILES[0]);
+                if !matches!(exe.try_exists(), Ok(true)) {
+                    return new_io_error!(
+                        ErrorKind::NotFound,
+                        format!("Could not find: '{}'", exe.display())
+                    );
+                }
+                Command::new(exe).current_dir(game_dir).spawn()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// returns `true` if `STEAM_EXE` is found in a parent of `game_dir` or in the default Steam  
+/// install directory
+fn steam_installed(game_dir: &Path) -> bool {
+    let default_dir =
+        std::env::var_os("ProgramFiles(x86)").map(|dir| Path::new(&dir).join("Steam"));
+    game_dir
+        .ancestors()
+        .map(Path::to_path_buf)
+        .chain(default_dir)
+        .any(|dir| dir.join(STEAM_EXE).is_file())
+}
+
+impl std::str::FromStr for LaunchMethod {
+    type Err = std::io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase() {
+            method if method == LAUNCH_METHODS[0] => Ok(LaunchMethod::Steam),
+            method if method == LAUNCH_METHODS[1] => Ok(LaunchMethod::Direct),
+            method => new_io_error!(
+                ErrorKind::InvalidInput,
+                format!("'{method}', is not one of: {}", DisplayVec(&LAUNCH_METHODS))
+            ),
+        }
+    }
+}
+
+/// which confirmation prompts are shown, saved with key "confirm_level" as one of `CONFIRM_LEVELS`  
+/// prompts before a destructive action are always shown no matter the level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfirmLevel {
+    All,
+    /// skips prompts that only ask to continue a non-destructive action
+    DestructiveOnly,
+    /// also skips the summary shown before mods are installed or registered
+    Minimal,
+}
+
+impl ConfirmLevel {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfirmLevel::All => CONFIRM_LEVELS[0],
+            ConfirmLevel::DestructiveOnly => CONFIRM_LEVELS[1],
+            ConfirmLevel::Minimal => CONFIRM_LEVELS[2],
+        }
+    }
+
+    /// returns `false` if a prompt that asks to continue a non-destructive action is assumed yes
+    #[inline]
+    pub fn confirm_routine(&self) -> bool {
+        *self == ConfirmLevel::All
+    }
+
+    /// returns `false` if the summary of a non-destructive action is skipped
+    #[inline]
+    pub fn confirm_summary(&self) -> bool {
+        *self != ConfirmLevel::Minimal
+    }
+}
+
+impl std::str::FromStr for ConfirmLevel {
+    type Err = std::io::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase() {
+            level if level == CONFIRM_LEVELS[0] => Ok(ConfirmLevel::All),
+            level if level == CONFIRM_LEVELS[1] => Ok(ConfirmLevel::DestructiveOnly),
+            level if level == CONFIRM_LEVELS[2] => Ok(ConfirmLevel::Minimal),
+            level => new_io_error!(
+                ErrorKind::InvalidInput,
+                format!("'{level}', is not one of: {}", DisplayVec(&CONFIRM_LEVELS))
+            ),
+        }
+    }
+}
+
+/// starts Elden Ring with `method`, if the game can not be started that way the other method is tried  
+/// returns the method that started the game, errors only if neither method could start the game
+#[instrument(level = "trace", skip(game_dir))]
+pub fn launch_game(method: LaunchMethod, game_dir: &Path) -> std::io::Result<LaunchMethod> {
+    let err = match method.spawn(game_dir) {
+        Ok(()) => return Ok(method),
+        Err(err) => err,
+    };
+    let fallback = match method {
+        LaunchMethod::Steam => LaunchMethod::Direct,
+        LaunchMethod::Direct => LaunchMethod::Steam,
+    };
+    warn!("Failed to launch with: {}, {err}", method.as_str());
+    match fallback.spawn(game_dir) {
+        Ok(()) => Ok(fallback),
+        Err(fallback_err) => new_io_error!(
+            ErrorKind::NotFound,
+            format!(
+                "Could not launch Elden Ring\n\n{}: {err}\n{}: {fallback_err}",
+                method.as_str(),
+                fallback.as_str()
+            )
+        ),
+    }
+}
+
+/// toggle the state of the files saved in `reg_mod.files.dll`, and `reg_mod.files.config` if  
+/// `reg_mod.toggle_configs` is set, see `RegMod::toggled_files`  
 /// this function updates the reg_mod's modified files and state  
 #[instrument(level = "trace", skip(game_dir, reg_mod, save_file), fields(name = reg_mod.name, prev_state = reg_mod.state))]
 pub fn toggle_files(
@@ -192,20 +545,32 @@ pub fn toggle_files(
             );
         }
 
-        paths.iter().zip(new_paths.iter()).try_for_each(|(path, new_path)| {
-            std::fs::rename(path, new_path)?;
+        mark_self_write();
+        for (i, (path, new_path)) in paths.iter().zip(new_paths.iter()).enumerate() {
+            if let Err(err) = rename_with_retry(path, new_path) {
+                // keep files on disk consistent with `reg_mod.state`
+                paths[..i]
+                    .iter()
+                    .zip(new_paths[..i].iter())
+                    .rev()
+                    .for_each(|(path, new_path)| {
+                        if let Err(err) = std::fs::rename(new_path, path) {
+                            error!("Failed to undo rename of: '{}', {err}", new_path.display());
+                        }
+                    });
+                return Err(err);
+            }
             trace!(
                 old = ?path.file_name().unwrap(),
                 new = ?new_path.file_name().unwrap(), "Rename success"
             );
-            Ok(())
-        })
+        }
+        Ok(())
     }
 
+    let toggled_files = reg_mod.toggled_files();
     if reg_mod.state == new_state
-        && reg_mod
-            .files
-            .dll
+        && toggled_files
             .iter()
             .all(|f| FileData::state_data(&f.to_string_lossy()).0 == new_state)
     {
@@ -213,16 +578,20 @@ pub fn toggle_files(
         return Ok(());
     }
 
-    let num_rename_files = reg_mod.files.dll.len();
+    let num_rename_files = toggled_files.len();
     let was_array = reg_mod.is_array();
 
-    let short_path_new = toggle_paths_state(&reg_mod.files.dll, new_state);
+    let mut short_path_new = toggle_paths_state(&toggled_files, new_state);
     let full_path_new = join_paths(game_dir, &short_path_new);
-    let full_path_original = join_paths(game_dir, &reg_mod.files.dll);
+    let full_path_original = join_paths(game_dir, &toggled_files);
 
     rename_files(&num_rename_files, &full_path_original, &full_path_new)?;
 
+    let config_path_new = short_path_new.split_off(reg_mod.files.dll.len());
     reg_mod.files.dll = short_path_new;
+    if reg_mod.toggle_configs {
+        reg_mod.files.config = config_path_new;
+    }
     reg_mod.state = new_state;
     if !reg_mod.files.dll.is_empty()
         && (reg_mod.files.dll[0].ends_with(LOADER_FILES[1])
@@ -242,6 +611,92 @@ pub fn toggle_files(
     Ok(())
 }
 
+/// toggles only `file`, one of the paths saved in `reg_mod.files.dll`, to `new_state`  
+/// used for mods that ship multiple dlls that work independently of each other  
+/// `reg_mod.state` is `true` while any dll is enabled, so a mod in a mixed state is shown as enabled  
+/// this function updates the reg_mod's modified file and state
+#[instrument(level = "trace", skip(game_dir, reg_mod, save_file), fields(name = reg_mod.name))]
+pub fn toggle_single_file(
+    game_dir: &Path,
+    file: &Path,
+    new_state: bool,
+    reg_mod: &mut RegMod,
+    save_file: Option<&Path>,
+) -> std::io::Result<()> {
+    let Some(index) = reg_mod
+        .files
+        .dll
+        .iter()
+        .position(|dll| dll == file || toggle_path_state(dll) == file)
+    else {
+        return new_io_error!(
+            ErrorKind::InvalidInput,
+            format!(
+                "'{}', is not a .dll registered to: {}",
+                file.display(),
+                DisplayName(&reg_mod.name)
+            )
+        );
+    };
+    if FileData::is_enabled(&reg_mod.files.dll[index]) == new_state {
+        trace!("File is already in the desired state");
+        return Ok(());
+    }
+
+    let short_path_new = toggle_path_state(&reg_mod.files.dll[index]);
+    mark_self_write();
+    rename_with_retry(
+        &game_dir.join(&reg_mod.files.dll[index]),
+        &game_dir.join(&short_path_new),
+    )?;
+    info!(
+        "{} {}",
+        omit_off_state(file_name_from_str(&short_path_new.to_string_lossy())),
+        DisplayState(new_state)
+    );
+
+    reg_mod.files.dll[index] = short_path_new;
+    reg_mod.state = reg_mod.files.dll.iter().any(FileData::is_enabled);
+    if let Some(file) = save_file {
+        reg_mod.write_to_file(file, reg_mod.is_array())?
+    }
+    Ok(())
+}
+
+/// sets the state of every mod in `reg_mods` to `new_state`, the mod loader itself is not changed  
+/// mods that are always active or are already in `new_state` are skipped  
+/// the files of each mod that will be toggled are checked to be unlocked before any are renamed  
+/// if a mod fails to toggle all mods toggled before it are returned to their previous state  
+/// returns the number of mods toggled
+#[instrument(level = "trace", skip_all, fields(new_state))]
+pub fn toggle_all_mods(
+    game_dir: &Path,
+    new_state: bool,
+    reg_mods: &mut [RegMod],
+    save_file: Option<&Path>,
+) -> std::io::Result<usize> {
+    let mut to_toggle = reg_mods
+        .iter_mut()
+        .filter(|reg_mod| !reg_mod.is_always_active() && reg_mod.state != new_state)
+        .collect::<Vec<_>>();
+    for reg_mod in to_toggle.iter() {
+        check_files_unlocked(game_dir, reg_mod)?;
+    }
+    for i in 0..to_toggle.len() {
+        if let Err(mut err) = toggle_files(game_dir, new_state, to_toggle[i], save_file) {
+            to_toggle[..i].iter_mut().rev().for_each(|reg_mod| {
+                if let Err(err) = toggle_files(game_dir, !new_state, reg_mod, save_file) {
+                    error!("Failed to undo toggle of: {}, {err}", DisplayName(&reg_mod.name));
+                }
+            });
+            err.add_msg(&format!("no mods were {}", DisplayState(new_state)), false);
+            return Err(err);
+        }
+    }
+    info!("{} mod(s) {}", to_toggle.len(), DisplayState(new_state));
+    Ok(to_toggle.len())
+}
+
 /// if cfg file does not exist or is not set up with provided sections this function will  
 /// create a new ".ini" file in the given path  
 #[instrument(level = "trace", skip_all, fields(cfg_dir = %from_path.display()))]
@@ -338,6 +793,26 @@ where
     }
 }
 
+/// returns the sha-256 of the file at `path` as a lowercase hex string  
+/// returns `None` if the file is larger than `HASH_SIZE_LIMIT`
+#[instrument(level = "trace", skip_all, fields(path = %path.display()))]
+pub fn file_sha256(path: &Path) -> std::io::Result<Option<String>> {
+    let mut file = std::fs::File::open(path)?;
+    if file.metadata()?.len() > HASH_SIZE_LIMIT {
+        trace!("file is larger than the hash size limit, skipped");
+        return Ok(None);
+    }
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(Some(
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect(),
+    ))
+}
+
 pub struct FileData<'a> {
     pub name: &'a str,
     pub extension: &'a str,
@@ -408,6 +883,32 @@ impl FileData<'_> {
     }
 }
 
+/// category of a registered file, used by the front end to pick an icon  
+/// discriminants are the `int` values the front end expects
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileCategory {
+    Dll = 0,
+    Config = 1,
+    Text = 2,
+    Asset = 3,
+    Other = 4,
+}
+
+impl FileCategory {
+    /// classifies a file by its extension, works with files in either the enabled or disabled state  
+    pub fn from_path<T: AsRef<Path>>(path: &T) -> Self {
+        let path_str = path.as_ref().to_string_lossy();
+        let extension = FileData::from(&path_str).extension.to_lowercase();
+        match extension.as_str() {
+            ".dll" => FileCategory::Dll,
+            ".ini" => FileCategory::Config,
+            ".txt" => FileCategory::Text,
+            ext if ASSET_EXTENSIONS.contains(&ext) => FileCategory::Asset,
+            _ => FileCategory::Other,
+        }
+    }
+}
+
 /// removes the off_state if the file name is in the off_state  
 /// to get an accurate `FileData.name` function input needs `file_name()` called before hand  
 #[instrument(level = "trace", skip_all)]
@@ -420,6 +921,200 @@ pub fn omit_off_state(name: &str) -> &str {
     }
 }
 
+/// formats a user supplied mod name into the key it is saved under  
+/// surrounding whitespace is trimmed, each run of inner whitespace or `'_'` becomes a single `'_'`  
+/// and the result is NFC normalized so visually identical names produce the same key
+pub fn format_key(name: &str) -> String {
+    name.split(|c: char| c.is_whitespace() || c == '_')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("_")
+        .nfc()
+        .collect()
+}
+
+/// `format_key` with case folded, use this any time mod names or keys are compared  
+/// re-normalized after lowercasing since some characters lowercase into a decomposed form
+pub fn canonical_key(name: &str) -> String {
+    format_key(name).to_lowercase().nfc().collect()
+}
+
+/// tiebreak used to order mods that share a load order value or have no set load order  
+/// names are compared alphabetically ignoring case, `'_'` and `' '` are considered equal so ini keys  
+/// and display names sort the same
+pub fn cmp_mod_names(a: &str, b: &str) -> std::cmp::Ordering {
+    let normalize = |c: char| if c == '_' { ' ' } else { c };
+    a.chars()
+        .flat_map(char::to_lowercase)
+        .map(normalize)
+        .cmp(b.chars().flat_map(char::to_lowercase).map(normalize))
+}
+
+/// orders the categories of the mod list alphabetically ignoring case, `DEFAULT_CATEGORY` is always  
+/// sorted last, categories that only differ by case or `'_'` are kept apart so each forms one group
+pub fn cmp_categories(a: &str, b: &str) -> std::cmp::Ordering {
+    (a == DEFAULT_CATEGORY)
+        .cmp(&(b == DEFAULT_CATEGORY))
+        .then_with(|| cmp_mod_names(a, b))
+        .then_with(|| a.cmp(b))
+}
+
+/// returns `true` if the file name of `path` with the off_state removed matches a file in `LOADER_FILES`  
+/// or `REQUIRED_GAME_FILES`, the directory `path` is located in is not considered
+pub fn is_restricted_file(path: &Path) -> bool {
+    let Some(file_name) = path.file_name() else {
+        return false;
+    };
+    let file_name = file_name.to_string_lossy().to_lowercase();
+    let file_name = omit_off_state(&file_name);
+    LOADER_FILES
+        .iter()
+        .chain(REQUIRED_GAME_FILES.iter())
+        .any(|restricted| omit_off_state(restricted) == file_name)
+}
+
+/// returns `true` if the file name of `path` with the off_state removed is one of the names the  
+/// mod loader's dll hook is saved as, `LOADER_FILES[0..3]`, the directory `path` is located in is not considered
+pub fn is_loader_hook(path: &Path) -> bool {
+    let Some(file_name) = path.file_name() else {
+        return false;
+    };
+    let file_name = file_name.to_string_lossy().to_lowercase();
+    let file_name = omit_off_state(&file_name);
+    LOADER_FILES[..3]
+        .iter()
+        .any(|hook| omit_off_state(hook) == file_name)
+}
+
+/// labels the contents of the config at `path` with its file name and full path for viewing in-app  
+/// a file that does not exist yet or can not be read is noted in place of its contents
+pub fn labeled_config_contents(path: &Path) -> String {
+    let name = path.file_name().unwrap_or(path.as_os_str()).to_string_lossy();
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => String::from("File does not exist yet"),
+        Err(err) => format!("Could not read file, {err}"),
+    };
+    format!(
+        "===== {name} =====\nPath: '{}'\n\n{}",
+        path.display(),
+        contents.trim_end()
+    )
+}
+
+/// where the app is running from relative to "game_dir", see `check_app_location`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AppLocationWarning {
+    /// the app is running from "game_dir"
+    InGameDir,
+    /// the app is running from a directory within "game_dir"
+    WithinGameDir,
+}
+
+impl std::fmt::Display for AppLocationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let location = match self {
+            AppLocationWarning::InGameDir => "the game directory",
+            AppLocationWarning::WithinGameDir => "a folder within the game directory",
+        };
+        write!(
+            f,
+            "The app is running from {location}, this is unsupported and can cause app files to be \
+            managed as mod files\n\nPlease move the app to a folder outside of the game directory"
+        )
+    }
+}
+
+/// compares the directory `ini_dir` is saved in against `game_dir`, case is ignored  
+/// returns `Some` if the app is running from inside of `game_dir`
+pub fn check_app_location(ini_dir: &Path, game_dir: &Path) -> Option<AppLocationWarning> {
+    let app_dir = normalize_path(ini_dir.parent()?);
+    let game_dir = normalize_path(game_dir);
+    if game_dir.as_os_str().is_empty() {
+        return None;
+    }
+    let within = strip_prefix_ci(&app_dir, &game_dir)?;
+    if within.as_os_str().is_empty() {
+        return Some(AppLocationWarning::InGameDir);
+    }
+    Some(AppLocationWarning::WithinGameDir)
+}
+
+/// reads the file version of "eldenring.exe" located in `game_dir` as "major.minor.build.revision"  
+/// errors with `NotFound` if the exe is missing and `InvalidData` if it contains no version info
+#[instrument(level = "trace", skip_all)]
+pub fn read_game_version(game_dir: &Path) -> std::io::Result<String> {
+    let exe = game_dir.join(REQUIRED_GAME_FILES[0]);
+    if !matches!(exe.try_exists(), Ok(true)) {
+        return new_io_error!(
+            ErrorKind::NotFound,
+            format!("Could not find: '{}'", exe.display())
+        );
+    }
+    let (high, low) = file_version_parts(&exe)?;
+    let version = format!("{}.{}.{}.{}", high >> 16, high & 0xFFFF, low >> 16, low & 0xFFFF);
+    trace!(version);
+    Ok(version)
+}
+
+/// returns the most and least significant halves of the file version stored in `path`'s version info
+#[cfg(target_os = "windows")]
+fn file_version_parts(path: &Path) -> std::io::Result<(u32, u32)> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetFileVersionInfoSizeW, GetFileVersionInfoW, VerQueryValueW, VS_FIXEDFILEINFO,
+    };
+
+    const FIXED_INFO_SIGNATURE: u32 = 0xFEEF04BD;
+    let no_version_info = || {
+        new_io_error!(
+            ErrorKind::InvalidData,
+            format!("'{}' does not contain version info", path.display())
+        )
+    };
+    let wide_path = path
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect::<Vec<u16>>();
+    let root_block = [u16::from(b'\\'), 0];
+
+    // SAFETY: both strings are null terminated, `data` is allocated to the size requested by
+    // `GetFileVersionInfoSizeW` and `fixed_info` is only read after `VerQueryValueW` reports a
+    // block at least the size of `VS_FIXEDFILEINFO` that lives within `data`
+    unsafe {
+        let size = GetFileVersionInfoSizeW(wide_path.as_ptr(), std::ptr::null_mut());
+        if size == 0 {
+            return no_version_info();
+        }
+        let mut data = vec![0_u8; size as usize];
+        if GetFileVersionInfoW(wide_path.as_ptr(), 0, size, data.as_mut_ptr().cast()) == 0 {
+            return no_version_info();
+        }
+        let mut fixed_info = std::ptr::null_mut();
+        let mut len = 0_u32;
+        if VerQueryValueW(data.as_ptr().cast(), root_block.as_ptr(), &mut fixed_info, &mut len) == 0
+            || fixed_info.is_null()
+            || (len as usize) < std::mem::size_of::<VS_FIXEDFILEINFO>()
+        {
+            return no_version_info();
+        }
+        let fixed_info = &*fixed_info.cast::<VS_FIXEDFILEINFO>();
+        if fixed_info.dwSignature != FIXED_INFO_SIGNATURE {
+            return no_version_info();
+        }
+        Ok((fixed_info.dwFileVersionMS, fixed_info.dwFileVersionLS))
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn file_version_parts(path: &Path) -> std::io::Result<(u32, u32)> {
+    new_io_error!(
+        ErrorKind::Unsupported,
+        format!("Can not read the version info of '{}' on this platform", path.display())
+    )
+}
+
 /// convience function to map Option None to an io Error
 #[inline]
 pub fn parent_or_err(path: &Path) -> std::io::Result<&Path> {
@@ -433,12 +1128,12 @@ pub fn file_name_or_err(path: &Path) -> std::io::Result<&std::ffi::OsStr> {
         .ok_or_else(|| std::io::Error::new(ErrorKind::InvalidData, "Could not get file_name"))
 }
 
-/// returns whats right of the right most "\\" or does nothing
+/// returns whats right of the right most "\\" or "/" or does nothing
 #[instrument(level = "trace")]
 pub fn file_name_from_str(str: &str) -> &str {
-    let split = str.rfind('\\').unwrap_or(0);
+    let split = str.rfind(['\\', '/']).unwrap_or(0);
     if split == 0 {
-        trace!("'\\' not found");
+        trace!("'\\' or '/' not found");
         return str;
     }
     let output = str.split_at(split + 1).1;
@@ -446,19 +1141,76 @@ pub fn file_name_from_str(str: &str) -> &str {
     output
 }
 
+/// root of a path found by `get_drive`
+#[derive(Debug, PartialEq, Eq)]
+pub enum DriveRoot {
+    /// drive letter root eg. `C:\`
+    Disk(String),
+    /// network share root eg. `\\server\share\`, the game directory is not searched for on shares
+    Network(String),
+}
+
+impl std::fmt::Display for DriveRoot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DriveRoot::Disk(root) | DriveRoot::Network(root) => write!(f, "{root}"),
+        }
+    }
+}
+
 pub enum PathResult {
     Full(PathBuf),
+    /// a Game Pass / Windows Store install was found, it can not be used as "game_dir"
+    GamePass(PathBuf),
     Partial(PathBuf),
     None(PathBuf),
 }
 
+/// the kind of Elden Ring install found in a directory, see `detect_install_type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstallType {
+    /// contains all `REQUIRED_GAME_FILES`
+    Steam,
+    /// located within one of `GAME_PASS_ROOTS` or contains any of `GAME_PASS_FILES`
+    GamePass,
+    Unknown,
+}
+
+/// classifies the install of Elden Ring in `dir`, a Steam install is always checked for first  
+/// Game Pass installs are only recognized so the user can be told why they are not supported
+pub fn detect_install_type(dir: &Path) -> InstallType {
+    if files_not_found(dir, &REQUIRED_GAME_FILES).is_ok_and(|not_found| not_found.is_empty()) {
+        return InstallType::Steam;
+    }
+    let in_game_pass_root = dir
+        .to_string_lossy()
+        .split(['\\', '/'])
+        .any(|dir| GAME_PASS_ROOTS.iter().any(|root| root.eq_ignore_ascii_case(dir)));
+    if in_game_pass_root
+        || matches!(
+            does_dir_contain(dir, Operation::Any, &GAME_PASS_FILES),
+            Ok(OperationResult::Bool(true))
+        )
+    {
+        return InstallType::GamePass;
+    }
+    InstallType::Unknown
+}
+
 impl Cfg {
     /// returns various levels of a Path: "game_dir"  
     /// first tries to validate the path saved in the .ini if that fails then tries to located the "game_dir" on disk  
+    /// a Game Pass install found at `GAME_PASS_DIR` returns `PathResult::GamePass` as it can not be modded  
     /// if that fails will return a `PathResult::Partial` that is known to exist if not returns `PathResult::None` that contains just the found drive
     #[instrument(level = "trace", skip_all)]
     pub fn attempt_locate_game(&mut self) -> std::io::Result<PathResult> {
-        match IniProperty::<PathBuf>::read(self.data(), INI_SECTIONS[1], INI_KEYS[2], None, false) {
+        let _timer = OpTimer::start("attempt_locate_game");
+        match IniProperty::<PathBuf>::read_full_path(
+            self.data(),
+            INI_SECTIONS[1],
+            INI_KEYS[2],
+            false,
+        ) {
             Ok(path) => {
                 info!("Game directory in: {INI_NAME}, is valid");
                 return Ok(PathResult::Full(path.value));
@@ -473,14 +1225,19 @@ impl Cfg {
             info!(
                 "Located valid game directory on drive: {}",
                 get_drive(&try_locate)
-                    .unwrap_or_else(|_| std::ffi::OsString::from(""))
-                    .to_str()
-                    .unwrap_or("")
+                    .map(|root| root.to_string())
+                    .unwrap_or_default()
             );
             save_path(self.path(), INI_SECTIONS[1], INI_KEYS[2], &try_locate)?;
             self.set(INI_SECTIONS[1], INI_KEYS[2], &try_locate.to_string_lossy());
             return Ok(PathResult::Full(try_locate));
         }
+        if let Ok(game_pass_dir) = attempt_locate_dir(&GAME_PASS_DIR) {
+            if detect_install_type(&game_pass_dir) == InstallType::GamePass {
+                info!("Found {} install in: '{}'", InstallType::GamePass, game_pass_dir.display());
+                return Ok(PathResult::GamePass(game_pass_dir));
+            }
+        }
         if try_locate.components().count() > 1 {
             info!("Partial game directory found");
             return Ok(PathResult::Partial(try_locate));
@@ -488,11 +1245,44 @@ impl Cfg {
         info!("Could not locate game directory");
         Ok(PathResult::None(try_locate))
     }
+
+    /// validates that `new_dir` contains all `REQUIRED_GAME_FILES` then saves it as: "game_dir"  
+    /// registered files are stored relative to "game_dir" so they are kept as is  
+    /// returns (`mod_name`, `short_paths`) for each mod with files that could not be found in `new_dir`
+    #[instrument(level = "trace", skip(self))]
+    pub fn relocate_game_dir(
+        &mut self,
+        new_dir: &Path,
+    ) -> std::io::Result<Vec<(String, Vec<PathBuf>)>> {
+        let not_found = files_not_found(new_dir, &REQUIRED_GAME_FILES)?;
+        if !not_found.is_empty() && detect_install_type(new_dir) == InstallType::GamePass {
+            return new_io_error!(ErrorKind::Unsupported, GAME_PASS_MSG);
+        }
+        if !not_found.is_empty() {
+            return new_io_error!(
+                ErrorKind::NotFound,
+                format!(
+                    "Could not find Elden Ring in: '{}', missing: {}",
+                    new_dir.display(),
+                    DisplayVec(&not_found)
+                )
+            );
+        }
+        save_path(self.path(), INI_SECTIONS[1], INI_KEYS[2], new_dir)?;
+        self.set(INI_SECTIONS[1], INI_KEYS[2], &new_dir.to_string_lossy());
+        Ok(self.diagnose(new_dir, &HashSet::new()).missing_files)
+    }
 }
 
 #[instrument(level = "trace", skip_all)]
 fn attempt_locate_dir(target_path: &[&str]) -> std::io::Result<PathBuf> {
-    let curr_drive = get_drive(&std::env::current_dir()?)?;
+    let curr_drive = match get_drive(&std::env::current_dir()?)? {
+        DriveRoot::Disk(drive) => drive,
+        DriveRoot::Network(share) => {
+            info!("Running from network share: {share}, network installs are not searched");
+            String::from("C:\\")
+        }
+    };
 
     trace!(?curr_drive, "Drive Found");
 
@@ -526,13 +1316,50 @@ fn test_path_buf(mut path: PathBuf, target_path: &[&str]) -> std::io::Result<Pat
     Ok(path)
 }
 
-fn get_drive(path: &Path) -> std::io::Result<std::ffi::OsString> {
-    path.components()
-        .next()
-        .map(|root| {
-            let mut drive = root.as_os_str().to_ascii_uppercase();
-            drive.push("\\");
-            drive
-        })
-        .ok_or_else(|| std::io::Error::new(ErrorKind::InvalidData, "Could not get root component"))
+/// returns the root of `path` as a `DriveRoot`, `'/'` is accepted as a separator  
+/// extended-length paths `\\?\C:\` are returned as their plain drive `C:\`, `\\?\UNC\` as a share  
+/// errors if `path` does not start with a drive letter or a network share
+pub fn get_drive(path: &Path) -> std::io::Result<DriveRoot> {
+    fn root_err(path: &str) -> std::io::Error {
+        std::io::Error::new(
+            ErrorKind::InvalidData,
+            format!("Could not get root component of: '{path}'"),
+        )
+    }
+    fn share_root(unc: &str) -> Option<String> {
+        let mut parts = unc.split('\\');
+        let (server, share) = (parts.next()?, parts.next()?);
+        if server.is_empty() || share.is_empty() {
+            return None;
+        }
+        Some(format!("\\\\{server}\\{share}\\"))
+    }
+
+    let path_str = path.to_string_lossy().replace('/', "\\");
+    let (verbatim, rest) = match path_str
+        .strip_prefix(r"\\?\")
+        .or_else(|| path_str.strip_prefix(r"\\.\"))
+    {
+        Some(rest) => (true, rest),
+        None => (false, path_str.as_str()),
+    };
+    let unc = if verbatim {
+        rest.get(..4)
+            .filter(|unc| unc.eq_ignore_ascii_case("UNC\\"))
+            .map(|_| &rest[4..])
+    } else {
+        rest.strip_prefix(r"\\")
+    };
+    if let Some(unc) = unc {
+        return share_root(unc)
+            .map(DriveRoot::Network)
+            .ok_or_else(|| root_err(&path_str));
+    }
+    match rest.split('\\').next().map(str::as_bytes) {
+        Some([letter, b':']) if letter.is_ascii_alphabetic() => Ok(DriveRoot::Disk(format!(
+            "{}:\\",
+            letter.to_ascii_uppercase() as char
+        ))),
+        _ => Err(root_err(&path_str)),
+    }
 }
@@ -2,31 +2,43 @@
 // Setting windows_subsystem will hide console | cant read logs if console is hidden
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod cli;
+mod self_test;
+
 use elden_mod_loader_gui::{
     utils::{
         display::*,
+        error::EmlError,
         ini::{
             common::*,
-            mod_loader::{ModLoader, OrdMetaData, RegModsExt},
-            parser::{CollectedMods, RegMod, Setup, SplitFiles},
+            mod_loader::{
+                loader_backend, order_line_key, order_placement, DinputLoader, LoaderBackend,
+                OrdMetaData, OrderPlacement, OrderStore, RegModsExt,
+            },
+            parser::{CollectedMods, ConfigDiff, RegMod, Setup, SplitFiles},
             writer::*,
         },
-        installer::{remove_mod_files, scan_for_mods, InstallData},
-        subscriber::init_subscriber,
+        installer::{
+            clean_orphaned_files, commit_scanned_mods, copy_with_progress, discover_mods,
+            ensure_mods_dir, expand_file_pattern, remove_mod_files, CopyProgress, InstallData,
+            ScanMode,
+        },
+        lock::UiRwLock,
+        subscriber::{init_subscriber, set_log_level, set_save_log},
+        watcher::{mark_self_write, watch_files},
     },
     *,
 };
 use i_slint_backend_winit::WinitWindowAccessor;
 use slint::{ComponentHandle, Model, ModelRc, SharedString, StandardListViewItem, VecModel};
 use std::{
-    collections::{HashMap, HashSet, VecDeque},
-    ffi::OsStr,
+    collections::{HashMap, HashSet},
     io::ErrorKind,
     path::{Path, PathBuf},
     rc::Rc,
     sync::{
-        atomic::{AtomicU32, Ordering},
-        LazyLock, OnceLock,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Mutex, OnceLock, RwLockReadGuard, RwLockWriteGuard,
     },
 };
 use tokio::sync::{
@@ -38,10 +50,18 @@ use tracing::{error, info, info_span, instrument, trace, warn};
 slint::include_modules!();
 
 static GLOBAL_NUM_KEY: AtomicU32 = AtomicU32::new(0);
-static UNKNOWN_ORDER_KEYS: OnceLock<RwLock<HashSet<String>>> = OnceLock::new();
+static CANCEL_INSTALL: AtomicBool = AtomicBool::new(false);
+static ADDING_MOD: AtomicBool = AtomicBool::new(false);
+static TOGGLING: AtomicBool = AtomicBool::new(false);
+// lock order: GAME_DIR is acquired before UNKNOWN_ORDER_KEYS, guards are never held across an `.await`
+// and GAME_DIR is only updated while no other guard of it is alive, see `UiRwLock`
+static UNKNOWN_ORDER_KEYS: OnceLock<UiRwLock<HashSet<String>>> = OnceLock::new();
 static RECEIVER: OnceLock<RwLock<UnboundedReceiver<MessageData>>> = OnceLock::new();
-static RESTRICTED_FILES: LazyLock<HashSet<&OsStr>> = LazyLock::new(populate_restricted_files);
+static DROPPED_PATHS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+static FILE_WATCHER: Mutex<Option<notify::RecommendedWatcher>> = Mutex::new(None);
 
+/// minimum time between install progress updates sent to the ui
+const PROGRESS_UPDATE_INTERVAL: std::time::Duration = std::time::Duration::from_millis(50);
 const ERROR_VAL: i32 = 42069;
 const OK_VAL: i32 = 0;
 
@@ -58,6 +78,14 @@ fn main() {
         None
     });
 
+    if let Some(exit_code) = cli::run() {
+        dsp_msgs.iter().for_each(|msg| eprintln!("{msg}"));
+        // `exit` skips destructors, release builds flush the log file once the guard is dropped
+        #[allow(dropping_copy_types)]
+        drop(_guard);
+        std::process::exit(exit_code);
+    }
+
     slint::platform::set_platform(Box::new(
         i_slint_backend_winit::Backend::new().expect("This app is being run on windows"),
     ))
@@ -69,6 +97,28 @@ fn main() {
             winit::window::WindowButtons::CLOSE | winit::window::WindowButtons::MINIMIZE,
         );
     });
+    ui.window().on_winit_window_event({
+        let ui_handle = ui.as_weak();
+        move |_, event| {
+            match event {
+                // a new drag replaces any paths still waiting for a mod name
+                winit::event::WindowEvent::HoveredFile(_) => DROPPED_PATHS.lock().unwrap().clear(),
+                winit::event::WindowEvent::DroppedFile(path) => {
+                    let mut dropped = DROPPED_PATHS.lock().unwrap();
+                    // winit sends one event per path, wait for all of them before handling the drop
+                    if dropped.is_empty() {
+                        let ui_handle = ui_handle.clone();
+                        slint::Timer::single_shot(std::time::Duration::from_millis(50), move || {
+                            ui_handle.unwrap().handle_dropped_paths()
+                        });
+                    }
+                    dropped.push(path.clone());
+                }
+                _ => (),
+            }
+            i_slint_backend_winit::WinitWindowEventResult::Propagate
+        }
+    });
     let (message_sender, message_receiver) = unbounded_channel::<MessageData>();
     RECEIVER.set(RwLock::new(message_receiver)).unwrap();
     {
@@ -98,6 +148,10 @@ fn main() {
         };
         let mut ini = if let Some(ini_data) = ini {
             let mut ini: Cfg = Config::from(ini_data, current_ini);
+            if let Err(err) = ini.migrate() {
+                error!("{err}");
+                dsp_msgs.push(err.to_string());
+            }
             if let Err(messages) = ini.validate_entries() {
                 dsp_msgs.extend(messages);
                 ini.write_to_file()
@@ -115,117 +169,98 @@ fn main() {
                 })
         };
 
+        set_backup_configs(ini.get_backup_configs());
+
         let game_verified: bool;
-        let mod_loader: ModLoader;
-        let mut mod_loader_cfg: ModLoaderCfg;
-        let mut reg_mods = None;
-        let mut order_data = None;
-        let mut ord_meta_data = None;
+        let mut found_game = None;
+        let mut game_dir_moved = false;
+        let mut game_pass_found = false;
         let game_dir = match ini.attempt_locate_game() {
             Ok(PathResult::Full(path)) => {
-                mod_loader = ModLoader::properties(&path).unwrap_or_else(|err| {
-                    error!(err_code = 3, "{err}");
-                    dsp_msgs.push(err.to_string());
-                    ModLoader::default()
-                });
-                if mod_loader.installed() {
-                    info!(dll_hook = %DisplayState(!mod_loader.disabled()), "elden_mod_loader files found");
-                    mod_loader_cfg = ModLoaderCfg::read(mod_loader.path()).unwrap_or_else(|err| {
-                        error!(err_code = 4, "{err}");
-                        dsp_msgs.push(err.to_string());
-                        ModLoaderCfg::default(mod_loader.path())
-                    });
-                    let (dlls, order_count, update_loader) =
-                        ini.dll_set_order_count(mod_loader_cfg.mut_section());
-                    if update_loader {
-                        mod_loader_cfg.write_to_file().unwrap_or_else(|err| {
-                            error!(err_code = 5, "{err}");
-                            dsp_msgs.push(err.to_string());
-                        });
-                    }
-                    if let Err(key_err) = mod_loader_cfg.verify_keys(&dlls, order_count) {
-                        match key_err.err.kind() {
-                            ErrorKind::Unsupported => {
-                                ini.update().unwrap_or_else(|err| {
-                                    error!(err_code = 6, "{err}");
-                                });
-                                ord_meta_data = key_err.update_ord_data;
-                                warn!("{}", key_err.err);
-                            }
-                            ErrorKind::Other => info!("{}", key_err.err),
-                            _ => error!(err_code = 7, "{}", key_err.err),
-                        }
-                        if let Some(unknown_keys) = key_err.unknown_keys {
-                            UNKNOWN_ORDER_KEYS
-                                .set(RwLock::new(unknown_keys))
-                                .expect("only initial set");
-                        }
-                        dsp_msgs.push(key_err.err.to_string());
-                    }
-                    order_data = mod_loader_cfg
-                        .parse_section(&get_unknown_orders())
-                        .map(Some)
-                        .unwrap_or_else(|err| {
-                            error!(err_code = 8, "{err}");
-                            dsp_msgs.push(err.to_string());
-                            None
-                        });
-                } else {
-                    mod_loader_cfg = ModLoaderCfg::default(mod_loader.path());
-                }
-                info!(
-                    "{}",
-                    DisplayAntiCheatFound(mod_loader.anti_cheat_toggle_installed())
-                );
-                reg_mods = {
-                    let mut collection = ini.collect_mods(&path, order_data.as_ref(), false);
-                    if collection.mods.len() != ini.mods_registered() {
-                        ini.update().unwrap_or_else(|err| {
-                            error!(err_code = 9, "{err}");
-                        });
-                    }
-                    if let Some(warning) = collection.warnings.take() {
-                        dsp_msgs.push(warning.to_string());
-                    }
-                    info!(
-                        "Found {} mod(s) registered in: {}",
-                        collection.mods.len(),
-                        INI_NAME
-                    );
-                    if ord_meta_data.is_none() {
-                        ord_meta_data = Some(OrdMetaData::with_ord(collection.mods.max_order()));
-                    }
-                    Some(collection)
-                };
+                found_game = Some(setup_found_game(&mut ini, &path, &mut dsp_msgs));
                 game_verified = true;
                 Some(path)
             }
+            Ok(PathResult::GamePass(path)) => {
+                game_verified = false;
+                game_pass_found = true;
+                Some(path)
+            }
             Ok(PathResult::Partial(path) | PathResult::None(path)) => {
-                mod_loader_cfg = ModLoaderCfg::empty();
-                mod_loader = ModLoader::default();
                 game_verified = false;
+                // a saved "game_dir" that is no longer valid means the install was moved or removed
+                game_dir_moved = ini
+                    .data()
+                    .get_from(INI_SECTIONS[1], INI_KEYS[2])
+                    .is_some_and(|saved| !saved.is_empty());
                 Some(path)
             }
             Err(err) => {
                 // io::Write error
                 error!(err_code = 10, "{err}");
                 dsp_msgs.push(err.to_string());
-                mod_loader_cfg = ModLoaderCfg::empty();
-                mod_loader = ModLoader::default();
                 game_verified = false;
                 None
             }
         };
 
+        if let Some(warning) = game_dir
+            .as_deref()
+            .filter(|_| game_verified)
+            .and_then(|path| check_app_location(current_ini, path))
+        {
+            warn!("{warning}");
+            dsp_msgs.push(warning.to_string());
+        }
+
+        ui.global::<SettingsLogic>().set_dark_mode(ini.get_dark_mode());
+        ui.global::<SettingsLogic>().set_save_log(ini.get_save_log());
+
+        let log_level = ini.get_log_level().unwrap_or_else(|err| {
+            // parse error ErrorKind::InvalidData
+            error!("{err}");
+            dsp_msgs.push(err.to_string());
+            DEFAULT_LOG_LEVEL
+        });
         ui.global::<SettingsLogic>()
-            .set_dark_mode(ini.get_dark_mode().unwrap_or_else(|err| {
-                // parse error ErrorKind::InvalidData
-                error!(err_code = 11, "{err}");
-                dsp_msgs.push(err.to_string());
-                DEFAULT_INI_VALUES[0]
-            }));
+            .set_log_level(log_level.as_str().to_lowercase().into());
+
+        ui.global::<SettingsLogic>()
+            .set_editor(SharedString::from(ini.get_editor()));
+
+        let launch_method = ini.get_launch_method().unwrap_or_else(|err| {
+            // parse error ErrorKind::InvalidData
+            error!("{err}");
+            dsp_msgs.push(err.to_string());
+            DEFAULT_LAUNCH_METHOD
+        });
+        ui.global::<SettingsLogic>()
+            .set_launch_method(launch_method.as_str().into());
+        ui.global::<SettingsLogic>().set_launch_methods(ModelRc::from(Rc::new(VecModel::from(
+            LAUNCH_METHODS.map(SharedString::from).to_vec(),
+        ))));
+
+        let confirm_level = ini.get_confirm_level().unwrap_or_else(|err| {
+            // parse error ErrorKind::InvalidData
+            error!("{err}");
+            dsp_msgs.push(err.to_string());
+            DEFAULT_CONFIRM_LEVEL
+        });
+        ui.global::<SettingsLogic>()
+            .set_confirm_level(confirm_level.as_str().into());
+        ui.global::<SettingsLogic>().set_confirm_levels(ModelRc::from(Rc::new(VecModel::from(
+            CONFIRM_LEVELS.map(SharedString::from).to_vec(),
+        ))));
+
+        let suppress_eac_warning = ini.get_suppress_eac_warning();
+        ui.global::<SettingsLogic>()
+            .set_suppress_eac_warning(suppress_eac_warning);
+        ui.global::<SettingsLogic>().set_watch_files(ini.get_watch_files());
+        ui.global::<SettingsLogic>().set_move_files(ini.get_move_files());
 
         ui.global::<MainLogic>().set_game_path_valid(game_verified);
+        ui.global::<SettingsLogic>()
+            .set_game_version(game_version(game_dir.as_deref().filter(|_| game_verified)));
         ui.global::<SettingsLogic>().set_game_path(
             game_dir
                 .as_ref()
@@ -234,61 +269,28 @@ fn main() {
                 .to_string()
                 .into(),
         );
-        if let Some(meta_data) = ord_meta_data {
-            ui.global::<MainLogic>()
-                .set_max_order(MaxOrder::from(meta_data.max_order));
-            if let Some(ref vals) = meta_data.missing_vals {
-                let msg = DisplayMissingOrd(vals).to_string();
-                info!("{msg}");
-                dsp_msgs.push(msg);
-            }
-        }
-        let _ = get_or_update_game_dir(Some(
+        ini.prune_recent_game_dirs().unwrap_or_else(|err| {
+            error!("{err}");
+        });
+        ui.global::<SettingsLogic>()
+            .set_recent_game_dirs(recent_game_dirs_model(&ini));
+        drop(get_or_update_game_dir(Some(
             game_dir.as_ref().unwrap_or(&PathBuf::new()).to_owned(),
-        ));
-
-        if !game_verified {
-            ui.global::<MainLogic>().set_current_subpage(1);
-        } else {
-            deserialize_collected_mods(
-                &if let Some(mod_data) = reg_mods {
-                    mod_data
-                } else {
-                    ini.collect_mods(
-                        game_dir.as_ref().expect("game verified"),
-                        order_data.as_ref(),
-                        !mod_loader.installed(),
-                    )
-                },
-                ui.as_weak(),
-            );
-            ui.global::<SettingsLogic>()
-                .set_loader_disabled(mod_loader.disabled());
-
-            if mod_loader.installed() {
-                ui.global::<SettingsLogic>().set_loader_installed(true);
-                let delay = mod_loader_cfg.get_load_delay().unwrap_or_else(|err| {
-                    // parse error ErrorKind::InvalidData
-                    error!(err_code = 12, "{err}");
-                    dsp_msgs.push(err.to_string());
-                    DEFAULT_LOADER_VALUES[0].parse().unwrap()
-                });
-                let show_terminal = mod_loader_cfg.get_show_terminal().unwrap_or_else(|err| {
-                    // parse error ErrorKind::InvalidData
-                    error!(err_code = 13, "{err}");
-                    dsp_msgs.push(err.to_string());
-                    false
-                });
-
-                ui.global::<SettingsLogic>()
-                    .set_load_delay(SharedString::from(format!("{delay}ms")));
-                ui.global::<SettingsLogic>().set_show_terminal(show_terminal);
+        )));
+        if game_verified {
+            start_file_watcher(&ini, game_dir.as_ref().expect("game verified"), ui.as_weak());
+        }
 
-                if mod_loader.anti_cheat_enabled() {
-                    dsp_msgs.push(DisplayAntiCheatMsg.to_string());
-                }
+        match found_game {
+            Some(ref found) => {
+                apply_found_game(&ui, &ini, found, suppress_eac_warning, &mut dsp_msgs)
             }
+            None => ui.global::<MainLogic>().set_current_subpage(1),
         }
+        let (mut mod_loader, order_data) = match found_game {
+            Some(found) => (found.mod_loader, found.order_data),
+            None => (Box::new(DinputLoader::default()) as Box<dyn LoaderBackend>, None),
+        };
         // we need to wait for slint event loop to start `ui.run()` before making calls to `ui.display_msg()`
         // otherwise calculations for the positon of display_msg_popup are not correct
         let ui_handle = ui.as_weak();
@@ -320,7 +322,31 @@ fn main() {
                         ui.display_msg(&std::mem::take(&mut disp_msg));
                         let _ = receive_msg().await;
                     }
-                    if !game_verified {
+                    if let (true, Some((found, canonical))) = (game_verified, mod_loader.renamed_hook()) {
+                        ui.display_confirm(&format!(
+                            "Found mod loader dll hook saved as: '{found}'\n\n\
+                            Would you like to rename it to: '{canonical}'?"
+                        ), Buttons::YesNo);
+                        if receive_msg().await == Message::Confirm {
+                            let game_dir = game_dir.as_ref().expect("game_verified");
+                            match mod_loader.restore_renamed_hook(game_dir) {
+                                Ok(()) => {
+                                    ui.global::<SettingsLogic>().set_loader_installed(true);
+                                    ui.global::<SettingsLogic>().set_loader_disabled(mod_loader.disabled());
+                                    reset_app_state(&mut ini, game_dir, Some(mod_loader.config_path()), None, ui.as_weak());
+                                }
+                                Err(err) => {
+                                    ui.display_and_log_err(err);
+                                    let _ = receive_msg().await;
+                                }
+                            }
+                        }
+                    }
+                    if game_dir_moved {
+                        disp_msg = String::from("Elden Ring is no longer at the saved game directory\nIf the install was moved use \"Set Path\" to select its new location, all registered mods will be kept")
+                    } else if game_pass_found {
+                        disp_msg = String::from(GAME_PASS_MSG)
+                    } else if !game_verified {
                         disp_msg = String::from("Could not locate Elden Ring\nPlease Select the install directory for Elden Ring")
                     } else if !mod_loader.installed() {
                         disp_msg = format!(
@@ -328,7 +354,7 @@ fn main() {
                             Please install files to: '{}', and relaunch Elden Mod Loader GUI", game_dir.as_ref().expect("game_verified").display()
                         )
                     }
-                    if game_verified && !mod_loader.anti_cheat_toggle_installed() {
+                    if game_verified && !mod_loader.anti_cheat_toggle_installed() && !suppress_eac_warning {
                         let anti_cheat_msg = format!(
                             "'{ANTI_CHEAT_EXE}' not found, do not forget to disable Easy-AntiCheat before running Elden Ring with mods installed"
                         );
@@ -369,8 +395,15 @@ fn main() {
             let _guard = span.enter();
 
             let ui = ui_handle.unwrap();
+            let Some(add_mod_guard) = AddModGuard::acquire() else {
+                trace!("add mod is already in progress");
+                return;
+            };
+            if !ui.ensure_game_dir_available() {
+                return;
+            }
             let ini_dir = get_ini_dir();
-            let game_dir = get_or_update_game_dir(None);
+            let game_dir = get_or_update_game_dir(None).clone();
             let mut ini = match Cfg::read(ini_dir) {
                 Ok(ini_data) => ini_data,
                 Err(err) => {
@@ -378,8 +411,8 @@ fn main() {
                     return;
                 }
             };
-            let format_key = mod_name.trim().replace(' ', "_");
-            if ini.keys().contains(&format_key.to_lowercase()) {
+            let format_key = format_key(&mod_name);
+            if ini.keys().contains(&canonical_key(&format_key)) {
                 ui.display_msg(&format!(
                     "There is already a registered mod with the name\n\"{mod_name}\""
                 ));
@@ -387,33 +420,48 @@ fn main() {
                     .set_line_edit_text(SharedString::new());
                 return;
             }
+            let preview_limit = ini.get_install_preview_limit();
             let span_clone = span.clone();
             slint::spawn_local(async move {
                 let _guard = span_clone.enter();
-                let mut file_paths = match get_user_files(&game_dir, ui.window()) {
-                    Ok(files) => files,
-                    Err(err) => {
-                        if err.kind() != ErrorKind::InvalidInput {
-                            error!("{err}");
+                let _add_mod_guard = add_mod_guard;
+                let dropped_paths = take_dropped_paths();
+                let (mut file_paths, dropped_dir) = if dropped_paths.is_empty() {
+                    match get_user_files(&game_dir, ui.window()) {
+                        Ok(files) => (files, None),
+                        Err(err) => {
+                            if err.kind() != ErrorKind::InvalidInput {
+                                error!("{err}");
+                            }
+                            ui.display_msg(&err.to_string());
+                            return;
                         }
-                        ui.display_msg(&err.to_string());
-                        return;
                     }
+                } else {
+                    let (dirs, files): (Vec<_>, Vec<_>) = dropped_paths.into_iter().partition(|path| path.is_dir());
+                    (files, dirs.into_iter().next())
                 };
-                let files = match shorten_paths(&file_paths, &game_dir) {
-                    Ok(files) => files,
-                    Err(err) => {
-                        if file_paths.len() != err.err_paths_long.len() {
-                            error!("Encountered {} StripPrefixError on input files", err.err_paths_long.len());
-                            ui.display_msg(
-                                &format!(
-                                    "Some selected files are already installed\n\nSelected Files Installed: {}\nSelected Files not installed: {}",
-                                    err.ok_paths_short.len(),
-                                    err.err_paths_long.len()
-                                ));
-                            return;
+                // a dropped folder is always installed, same as selecting a directory in `add_dir_to_install_data`
+                let shortened = match dropped_dir {
+                    Some(_) => None,
+                    None => Some(shorten_paths_ci(&file_paths, &game_dir)),
+                };
+                let files = match shortened {
+                    Some(Ok(files)) => files,
+                    shortened => {
+                        if let Some(Err(err)) = shortened {
+                            if file_paths.len() != err.err_paths_long.len() {
+                                error!("Encountered {} StripPrefixError on input files", err.err_paths_long.len());
+                                ui.display_msg(
+                                    &format!(
+                                        "Some selected files are already installed\n\nSelected Files Installed: {}\nSelected Files not installed: {}",
+                                        err.ok_paths_short.len(),
+                                        err.err_paths_long.len()
+                                    ));
+                                return;
+                            }
                         }
-                        match install_new_mod(&mod_name, file_paths, &game_dir, ui.as_weak()).await {
+                        match install_new_mod(&mod_name, file_paths, dropped_dir, &game_dir, preview_limit, ui.as_weak()).await {
                             Ok(installed_files) => {
                                 file_paths = installed_files;
                                 match shorten_paths(&file_paths, &game_dir) {
@@ -437,41 +485,41 @@ fn main() {
                         }
                     }
                 };
-                let registered_files = ini.files();
-                if files.iter().any(|f| registered_files.contains(f.to_str().unwrap_or_default())) {
-                    let err_str = "A selected file is already registered to a mod";
-                    error!("{err_str}");
-                    ui.display_msg(err_str);
-                    return;
+                // only the prompt is handled here, the mod is registered by `Cfg::register_existing`
+                let short_paths = files.iter().map(PathBuf::from).collect::<Vec<_>>();
+                let preview = RegMod::new(&format_key, true, short_paths.clone());
+                let mut track_dir = None;
+                if let Some(dir) = preview.install_dir(&game_dir).ok().and_then(|dir| Some(dir.strip_prefix(&game_dir).ok()?.to_path_buf())) {
+                    let in_dir = preview.files.other.iter().filter(|f| f.starts_with(&dir)).count();
+                    if in_dir > TRACK_BY_DIR_THRESHOLD {
+                        ui.display_confirm(&format!(
+                            "{mod_name} has {in_dir} files in: '{}'\n\n\
+                            Would you like to track them by directory instead of saving each file?",
+                            dir.display()
+                        ), Buttons::YesNo);
+                        if receive_msg().await == Message::Confirm {
+                            track_dir = Some(dir);
+                        }
+                    }
+                }
+                let loader_dir = loader_cfg_path(&game_dir);
+                let new_mod = match ini.register_existing(&format_key, short_paths, &game_dir, &loader_dir, track_dir) {
+                    Ok(reg_mod) => reg_mod,
+                    Err(err) => {
+                        ui.display_and_log_err(err);
+                        return;
+                    }
                 };
-                let loader_dir = get_loader_ini_dir();
-                let mut loader_cfg = ModLoaderCfg::read(loader_dir).unwrap_or_else(|err| {
+                let mut loader_cfg = ModLoaderCfg::read(&loader_dir).unwrap_or_else(|err| {
                     ui.display_and_log_err(err);
-                    ModLoaderCfg::default(loader_dir)
+                    ModLoaderCfg::default(&loader_dir)
                 });
-                let mut unknown_orders = get_mut_unknown_orders();
-                let order_data = loader_cfg.parse_section(&unknown_orders).unwrap_or_else(|err| {
+                let order_data = loader_cfg.parse_section(&get_unknown_orders()).unwrap_or_else(|err| {
                     ui.display_and_log_err(err);
                     HashMap::new()
                 });
-                let mut new_mod = RegMod::with_load_order(&format_key, true, files.iter().map(PathBuf::from).collect(), &order_data);
-                if !new_mod.files.dll.is_empty() {
-                    if new_mod.files.dll.iter().all(FileData::is_disabled) {
-                        new_mod.state = false;
-                    }
-                    if let Err(err) = new_mod.verify_state(&game_dir, ini.path()) {
-                        // Toggle files returned an error lets try it again
-                        if new_mod.verify_state(&game_dir, ini.path()).is_err() {
-                            ui.display_msg(&err.to_string());
-                            return;
-                        };
-                    };
-                }
-                if let Err(err) = new_mod.write_to_file(ini.path(), false) {
-                    let _ = new_mod.remove_from_file(ini.path());
-                    ui.display_and_log_err(err);
-                    return;
-                };
+                // taken after the track by directory prompt so the guard is never held across an `.await`
+                let mut unknown_orders = get_mut_unknown_orders();
                 for f in new_mod.files.dll.iter() {
                     let Some(f_name) = f.file_name().and_then(|o| o.to_str()).map(omit_off_state) else {
                         error!("Failed to get file name for: {}", f.display());
@@ -480,10 +528,6 @@ fn main() {
                     unknown_orders.remove(f_name);
                 }
                 ui.global::<MainLogic>().set_line_edit_text(SharedString::new());
-                ini.update().unwrap_or_else(|err| {
-                    ui.display_and_log_err(err);
-                    ini = Cfg::default(ini_dir);
-                });
 
                 let model = ui.global::<MainLogic>().get_current_mods();
                 let mut_model = model.as_any().downcast_ref::<VecModel<DisplayMod>>().expect("we set this type earlier");
@@ -493,15 +537,63 @@ fn main() {
                     ui.global::<MainLogic>().set_max_order(MaxOrder::from(ord_meta_data.max_order));
                     model.update_order(None, &order_data, &unknown_orders, ui.as_weak());
                 }
-                info!(
-                    files = new_mod.files.len(),
-                    state = %DisplayState(new_mod.state),
-                    order = %new_mod.order,
-                    "{mod_name} added with"
-                );
             }).unwrap();
         }
     });
+    ui.global::<MainLogic>().on_register_pattern({
+        let ui_handle = ui.as_weak();
+        move |mod_name, pattern| {
+            let span = info_span!("register_pattern");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            if !ui.ensure_game_dir_available() {
+                return;
+            }
+            let game_dir = get_or_update_game_dir(None).clone();
+            let mut ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            let short_paths = match expand_file_pattern(&game_dir, &pattern) {
+                Ok(files) => files,
+                Err(err) => {
+                    info!("{err}");
+                    ui.display_msg(&err.to_string());
+                    return;
+                }
+            };
+            let preview_limit = ini.get_install_preview_limit();
+            let span_clone = span.clone();
+            slint::spawn_local(async move {
+                let _guard = span_clone.enter();
+                ui.display_confirm(
+                    &format!(
+                        "Register {} file(s) matching: '{pattern}', as: {mod_name}?\n\n{}",
+                        short_paths.len(),
+                        display_capped_paths(&short_paths, &game_dir, preview_limit)
+                    ),
+                    Buttons::YesNo,
+                );
+                if receive_msg().await != Message::Confirm {
+                    return;
+                }
+                let loader_dir = loader_cfg_path(&game_dir);
+                let result = ini.register_existing(&mod_name, short_paths, &game_dir, &loader_dir, None);
+                if let Err(err) = result {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+                ui.global::<MainLogic>()
+                    .set_line_edit_text(SharedString::new());
+                reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+            })
+            .unwrap();
+        }
+    });
     ui.global::<SettingsLogic>().on_select_game_dir({
         let ui_handle = ui.as_weak();
         move || {
@@ -538,70 +630,24 @@ fn main() {
                 }
                 _ => unreachable!(),
             };
-            let not_found = match files_not_found(&try_path, &REQUIRED_GAME_FILES) {
-                Ok(files) => files,
+            apply_game_dir(ini, try_path, ui.as_weak());
+        }
+    });
+    ui.global::<SettingsLogic>().on_select_recent_game_dir({
+        let ui_handle = ui.as_weak();
+        move |dir| {
+            let span = info_span!("select_recent_game_dir");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
                 Err(err) => {
-                    match err.kind() {
-                        ErrorKind::NotFound => warn!("{err}"),
-                        _ => error!("{err}"),
-                    }
-                    ui.display_msg(&err.to_string());
+                    ui.display_and_log_err(err);
                     return;
                 }
             };
-            if !not_found.is_empty() {
-                error!(
-                    "Required game files not found in: '{}', files missing: {}",
-                    try_path.display(),
-                    DisplayVec(&not_found)
-                );
-                ui.display_msg(&format!(
-                    "Could not find Elden Ring in:\n\"{}\"",
-                    try_path.display()
-                ));
-                return;
-            }
-            if let Err(err) = save_path(ini.path(), INI_SECTIONS[1], INI_KEYS[2], &try_path) {
-                error!("Failed to save directory. {err}");
-                ui.display_msg(&err.to_string());
-                return;
-            };
-
-            let span_clone = span.clone();
-            slint::spawn_local(async move {
-                let _guard = span_clone.enter();
-                let mod_loader = ModLoader::properties(&try_path).unwrap_or_default();
-                ui.global::<SettingsLogic>()
-                    .set_game_path(try_path.to_string_lossy().to_string().into());
-                ui.global::<MainLogic>().set_game_path_valid(true);
-                ui.global::<MainLogic>().set_current_subpage(0);
-                ui.global::<SettingsLogic>()
-                    .set_loader_installed(mod_loader.installed());
-                ui.global::<SettingsLogic>()
-                    .set_loader_disabled(mod_loader.disabled());
-                if mod_loader.installed() {
-                    ui.display_msg(&format!(
-                        "Game Files Found!\n\
-                        {TUTORIAL_MSG}"
-                    ));
-                    let _ = receive_msg().await;
-                    if ini.mods_is_empty() {
-                        if let Err(err) =
-                            confirm_scan_mods(ui.as_weak(), &try_path, Some(&ini), None).await
-                        {
-                            error!("{err}");
-                            ui.display_msg(&err.to_string());
-                        };
-                    }
-                } else {
-                    ui.display_msg(&format!(
-                        "Game Files Found!\n\n\
-                        {TECHIE_W_MSG}"
-                    ))
-                }
-                let _ = get_or_update_game_dir(Some(try_path));
-            })
-            .unwrap();
+            apply_game_dir(ini, PathBuf::from(dir.as_str()), ui.as_weak());
         }
     });
     ui.global::<MainLogic>().on_toggle_mod({
@@ -611,6 +657,13 @@ fn main() {
             let _guard = span.enter();
 
             let ui = ui_handle.unwrap();
+            if !ui.ensure_game_dir_available() || !ui.ensure_game_closed() {
+                return !state;
+            }
+            let Some(toggle_guard) = ToggleGuard::acquire() else {
+                trace!("a toggle is already in progress");
+                return !state;
+            };
             let ini_dir = get_ini_dir();
             let mut ini = match Cfg::read(ini_dir) {
                 Ok(ini_data) => ini_data,
@@ -619,51 +672,197 @@ fn main() {
                     return !state;
                 }
             };
-            let game_dir = get_or_update_game_dir(None);
-            match ini.get_mod(&key, &game_dir, None) {
-                Ok(ref mut reg_mod) => {
-                    if reg_mod.files.dll.is_empty() {
-                        info!(
-                            "Can not toggle: {}, mod has no .dll files",
-                            DisplayName(&reg_mod.name)
-                        );
-                        ui.display_msg(&format!(
-                            "To toggle: {}, please add a .dll file",
-                            DisplayName(&reg_mod.name)
-                        ));
-                        return !state;
-                    }
-                    if let Err(err) = toggle_files(&game_dir, state, reg_mod, Some(ini.path())) {
-                        error!("{err}");
-                        ui.display_msg(&err.to_string());
-                    } else {
-                        return state;
-                    };
-                }
+            let game_dir = get_or_update_game_dir(None).clone();
+            let mut reg_mod = match ini.get_mod(&key, &game_dir, None) {
+                Ok(reg_mod) => reg_mod,
                 Err(err) => {
                     ui.display_and_log_err(err);
+                    reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+                    return !state;
                 }
+            };
+            if reg_mod.is_always_active() {
+                info!(
+                    "Can not toggle: {}, mod has no .dll files and is always active",
+                    DisplayName(&reg_mod.name)
+                );
+                return true;
             }
-            reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
-            !state
+            let ui_handle = ui.as_weak();
+            let span_clone = span.clone();
+            slint::spawn_local(async move {
+                let _guard = span_clone.enter();
+                let (job_dir, ini_path) = (game_dir.clone(), ini.path().to_path_buf());
+                let result = off_ui_thread(move || {
+                    toggle_files(&job_dir, state, &mut reg_mod, Some(ini_path.as_path()))
+                        .map(|()| reg_mod)
+                })
+                .await;
+                drop(toggle_guard);
+                match result {
+                    Ok(reg_mod) if reg_mod.files.dll.len() > 1 => {
+                        update_displayed_state(&reg_mod, ui_handle)
+                    }
+                    Ok(_) => (),
+                    Err(err) => {
+                        let ui = ui_handle.unwrap();
+                        ui.display_and_log_err(err);
+                        // the toggle was shown as done, reload so the ui matches the files on disk
+                        reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+                    }
+                }
+            })
+            .unwrap();
+            state
         }
     });
-    ui.global::<MainLogic>().on_force_app_focus({
+    ui.global::<MainLogic>().on_toggle_dll({
         let ui_handle = ui.as_weak();
-        move || {
+        move |key, file_name, state| -> bool {
+            let span = info_span!("toggle_dll");
+            let _guard = span.enter();
+
             let ui = ui_handle.unwrap();
-            ui.invoke_focus_app()
-        }
-    });
-    ui.global::<MainLogic>().on_add_to_mod({
-        let ui_handle = ui.as_weak();
+            if !ui.ensure_game_dir_available() || !ui.ensure_game_closed() {
+                return !state;
+            }
+            let Some(toggle_guard) = ToggleGuard::acquire() else {
+                trace!("a toggle is already in progress");
+                return !state;
+            };
+            let mut ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return !state;
+                }
+            };
+            let game_dir = get_or_update_game_dir(None).clone();
+            let mut reg_mod = match ini.get_mod(&key, &game_dir, None) {
+                Ok(reg_mod) => reg_mod,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+                    return !state;
+                }
+            };
+            let Some(file) = reg_mod
+                .files
+                .dll
+                .iter()
+                .find(|dll| {
+                    let dll_str = dll.to_string_lossy();
+                    omit_off_state(file_name_from_str(&dll_str)) == file_name.as_str()
+                })
+                .cloned()
+            else {
+                error!("{file_name}, is not registered to: {}", DisplayName(&key));
+                reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+                return !state;
+            };
+            let ui_handle = ui.as_weak();
+            let span_clone = span.clone();
+            slint::spawn_local(async move {
+                let _guard = span_clone.enter();
+                let (job_dir, ini_path) = (game_dir.clone(), ini.path().to_path_buf());
+                let result = off_ui_thread(move || {
+                    toggle_single_file(
+                        &job_dir,
+                        &file,
+                        state,
+                        &mut reg_mod,
+                        Some(ini_path.as_path()),
+                    )
+                    .map(|()| reg_mod)
+                })
+                .await;
+                drop(toggle_guard);
+                match result {
+                    Ok(reg_mod) => update_displayed_state(&reg_mod, ui_handle),
+                    Err(err) => {
+                        let ui = ui_handle.unwrap();
+                        ui.display_and_log_err(err);
+                        // the toggle was shown as done, reload so the ui matches the files on disk
+                        reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+                    }
+                }
+            })
+            .unwrap();
+            state
+        }
+    });
+    ui.global::<MainLogic>().on_set_toggle_configs({
+        let ui_handle = ui.as_weak();
+        move |key, state| -> bool {
+            let span = info_span!("set_toggle_configs");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            if !ui.ensure_game_dir_available() || !ui.ensure_game_closed() {
+                return !state;
+            }
+            let mut ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return !state;
+                }
+            };
+            let game_dir = get_or_update_game_dir(None);
+            let mut reg_mod = match ini.get_mod(&key, &game_dir, None) {
+                Ok(reg_mod) => reg_mod,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+                    return !state;
+                }
+            };
+            if let Err(err) = reg_mod.set_toggle_configs(state, &game_dir, ini.path()) {
+                ui.display_and_log_err(err);
+                update_displayed_state(&reg_mod, ui.as_weak());
+                return !state;
+            }
+            update_displayed_state(&reg_mod, ui.as_weak());
+            state
+        }
+    });
+    ui.global::<MainLogic>().on_select_mod({
+        let ui_handle = ui.as_weak();
+        move |row, step| -> i32 {
+            let ui = ui_handle.unwrap();
+            next_visible_row(&ui.global::<MainLogic>().get_current_mods(), row, step)
+        }
+    });
+    ui.global::<MainLogic>().on_force_app_focus({
+        let ui_handle = ui.as_weak();
+        move || {
+            let ui = ui_handle.unwrap();
+            ui.invoke_focus_app()
+        }
+    });
+    ui.global::<MainLogic>().on_view_configs(move || -> SharedString {
+        let span = info_span!("view_configs");
+        let _guard = span.enter();
+
+        [get_ini_dir(), get_loader_ini_dir()]
+            .into_iter()
+            .map(|path| labeled_config_contents(path))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+            .into()
+    });
+    ui.global::<MainLogic>().on_add_to_mod({
+        let ui_handle = ui.as_weak();
         move |row| {
             let span = info_span!("add_to_mod");
             let _guard = span.enter();
 
             let ui = ui_handle.unwrap();
+            if !ui.ensure_game_dir_available() {
+                return;
+            }
             let ini_dir = get_ini_dir();
-            let game_dir = get_or_update_game_dir(None);
+            let game_dir = get_or_update_game_dir(None).clone();
             let mut ini = match Cfg::read(ini_dir) {
                 Ok(ini_data) => ini_data,
                 Err(err) => {
@@ -684,13 +883,13 @@ fn main() {
                         return;
                     }
                 };
-                let mut loader_cfg = ModLoaderCfg::read(get_loader_ini_dir()).unwrap_or_else(|err| {
+                let loader_dir = loader_cfg_path(&game_dir);
+                let mut loader_cfg = ModLoaderCfg::read(&loader_dir).unwrap_or_else(|err| {
                     warn!("{err}");
                     ui.display_msg(&err.to_string());
                     ModLoaderCfg::empty()
                 });
-                let mut unknown_orders = get_mut_unknown_orders();
-                let order_map = loader_cfg.parse_section(&unknown_orders).unwrap_or_else(|err| {
+                let order_map = loader_cfg.parse_section(&get_unknown_orders()).unwrap_or_else(|err| {
                         error!("{err}");
                         ui.display_msg(&err.to_string());
                         loader_cfg.parse_into_map()
@@ -702,11 +901,11 @@ fn main() {
                     Err(err) => {
                         error!("{err}");
                         ui.display_msg(&err.to_string());
-                        reset_app_state(&mut ini, &game_dir, None, Some(&unknown_orders), ui.as_weak());
+                        reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
                         return;
                     }
                 };
-                let files = match shorten_paths(&file_paths, &game_dir) {
+                let files = match shorten_paths_ci(&file_paths, &game_dir) {
                     Ok(files) => files,
                     Err(err) => {
                         if file_paths.len() != err.err_paths_long.len() {
@@ -750,6 +949,8 @@ fn main() {
                     ui.display_msg(err_str);
                     return;
                 };
+                // acquired after installing so the guard is not held across an `.await`
+                let mut unknown_orders = get_mut_unknown_orders();
                 let num_files = files.len();
                 let was_array = found_mod.is_array();
                 files.iter().for_each(|path| found_mod.files.add(path));
@@ -766,6 +967,7 @@ fn main() {
                     reset_app_state(&mut ini, &game_dir, None, Some(&unknown_orders), ui.as_weak());
                     return;
                 };
+                record_hashes_if_enabled(&ini, &mut found_mod, &game_dir);
                 let new_dlls_with_set_order = files.iter().filter_map(|f| {
                     let f_str = f.to_string_lossy();
                     let f_data = FileData::from(file_name_from_str(&f_str));
@@ -780,10 +982,12 @@ fn main() {
                 }).collect::<Vec<_>>();
                 let dll_added_with_set_order = !new_dlls_with_set_order.is_empty();
                 let mut update_order = false;
-                let (files, dll_files, config_files) = deserialize_split_files(&found_mod.files);
+                let (files, file_categories, dll_files, config_files) = deserialize_split_files(&found_mod.files);
                 display_mod.files = files;
+                display_mod.file_categories = file_categories;
                 display_mod.dll_files = dll_files;
                 display_mod.config_files = config_files;
+                display_mod.dll_states = deserialize_dll_states(&found_mod.files);
                 if !found_mod.order.set {
                     if dll_added_with_set_order {
                         let Some(index) = found_mod.files.dll.iter().position(|f| f == new_dlls_with_set_order[0].1) else {
@@ -836,6 +1040,9 @@ fn main() {
                 let span = info_span!("remove_mod");
                 let _guard = span.enter();
                 let ui = handle_clone.unwrap();
+                if !ui.ensure_game_dir_available() {
+                    return;
+                }
                 ui.display_confirm(&format!("Are you sure you want to de-register: {key}?"), Buttons::OkCancel);
                 if receive_msg().await != Message::Confirm {
                     return
@@ -849,10 +1056,10 @@ fn main() {
                         return;
                     }
                 };
-                let loader_dir = get_loader_ini_dir();
                 let mut messages = Vec::with_capacity(5);
-                let mut unknown_orders = get_mut_unknown_orders();
-                let mut loader = match ModLoaderCfg::read(loader_dir) {
+                let game_dir = get_or_update_game_dir(None).clone();
+                let loader_dir = loader_cfg_path(&game_dir);
+                let mut loader = match ModLoaderCfg::read(&loader_dir) {
                     Ok(data) => data,
                     Err(err) => {
                         error!("{err}");
@@ -860,15 +1067,14 @@ fn main() {
                         return;
                     }
                 };
-                let mut order_map = loader.parse_section(&unknown_orders).unwrap_or_else(|err| {
+                let mut order_map = loader.parse_section(&get_unknown_orders()).unwrap_or_else(|err| {
                     error!("{err}");
                     messages.push(err.to_string());
                     loader.parse_into_map()
                 });
-                let game_dir = get_or_update_game_dir(None);
                 let reset_app_state_hook = |err: std::io::Error, mut ini: Cfg| {
                     ui.display_and_log_err(err);
-                    reset_app_state(&mut ini, &game_dir, Some(loader_dir), Some(&unknown_orders), ui.as_weak());
+                    reset_app_state(&mut ini, &game_dir, Some(&loader_dir), None, ui.as_weak());
                 };
                 let mut found_mod = match ini.get_mod(&key, &game_dir, Some(&order_map)) {
                     Ok(found_data) => found_data,
@@ -893,14 +1099,14 @@ fn main() {
                         ui.global::<MainLogic>().set_current_subpage(0);
                     },
                     Err(err) => {
-                        match err.kind() {
-                            ErrorKind::ConnectionAborted => info!("{err}"),
-                            ErrorKind::Interrupted => {
+                        match err {
+                            EmlError::FilesLeftInstalled { .. } => info!("{err}"),
+                            EmlError::UserCanceled(_) => {
                                 info!("{err}");
                                 return;
                             },
                             _ => {
-                                reset_app_state_hook(err, ini);
+                                reset_app_state_hook(err.into(), ini);
                                 return;
                             }
                         }
@@ -921,45 +1127,54 @@ fn main() {
                         return;
                     }
                 }
-                let (dlls, order_count, _) = ini.dll_set_order_count(loader.mut_section());
-                let model = ui.global::<MainLogic>().get_current_mods();
-                let mut_model = model.as_any().downcast_ref::<VecModel<DisplayMod>>().expect("we set this type earlier");
-                mut_model.remove(row as usize);
-                if found_mod.order.set {
-                    let mut ord_meta_data = None;
-                    loader.verify_keys(&dlls, order_count).unwrap_or_else(|key_err| {
-                        if let Some(unknown_keys) = key_err.unknown_keys {
-                            *unknown_orders = unknown_keys;
-                        }
-                        match key_err.err.kind() {
-                            ErrorKind::Other => info!("{}", key_err.err),
-                            ErrorKind::Unsupported => {
-                                warn!("{}", key_err.err);
-                                ord_meta_data = key_err.update_ord_data;
-                            },
-                            _ => error!("{}", key_err.err),
+                // acquired after the removal is confirmed and released before the next `.await`
+                let write_result = {
+                    let mut unknown_orders = get_mut_unknown_orders();
+                    let (dlls, order_count, _) = ini.dll_set_order_count(loader.mut_section());
+                    let model = ui.global::<MainLogic>().get_current_mods();
+                    let mut_model = model.as_any().downcast_ref::<VecModel<DisplayMod>>().expect("we set this type earlier");
+                    mut_model.remove(row as usize);
+                    let mut write_result = Ok(());
+                    if found_mod.order.set {
+                        let mut ord_meta_data = None;
+                        loader.verify_keys(&dlls, order_count).unwrap_or_else(|key_err| {
+                            if let Some(unknown_keys) = key_err.unknown_keys {
+                                *unknown_orders = unknown_keys;
+                            }
+                            match key_err.err {
+                                EmlError::UnknownOrderKeys { order_changed: false, .. } => info!("{}", key_err.err),
+                                EmlError::UnknownOrderKeys { order_changed: true, .. } => {
+                                    warn!("{}", key_err.err);
+                                    ord_meta_data = key_err.update_ord_data;
+                                },
+                                _ => error!("{}", key_err.err),
+                            }
+                            messages.push(key_err.err.to_string());
+                        });
+                        if ord_meta_data.is_none() {
+                            ord_meta_data = Some(loader.update_order_entries(None, &unknown_orders));
+                            write_result = loader.write_to_file();
                         }
-                        messages.push(key_err.err.to_string());
-                    });
-                    if ord_meta_data.is_none() {
-                        ord_meta_data = Some(loader.update_order_entries(None, &unknown_orders));
-                        if let Err(err) = loader.write_to_file() {
-                            error!("{err}");
-                            ui.display_msg(&err.to_string());
-                            let _ = receive_msg().await;
-                            reset_app_state(&mut ini, &game_dir, Some(loader_dir), Some(&unknown_orders), ui.as_weak());
-                            return;
+                        if write_result.is_ok() {
+                            order_map = loader.parse_into_map();
+                            let ord_meta_data = ord_meta_data.expect("is_some");
+                            ui.global::<MainLogic>().set_max_order(MaxOrder::from(ord_meta_data.max_order));
+                            model.update_order(None, &order_map, &unknown_orders, ui.as_weak());
+                            if let Some(ref vals) = ord_meta_data.missing_vals {
+                                let msg = DisplayMissingOrd(vals).to_string();
+                                info!("{msg}");
+                                messages.push(msg);
+                            }
                         }
                     }
-                    order_map = loader.parse_into_map();
-                    let ord_meta_data = ord_meta_data.expect("is_some");
-                    ui.global::<MainLogic>().set_max_order(MaxOrder::from(ord_meta_data.max_order));
-                    model.update_order(None, &order_map, &unknown_orders, ui.as_weak());
-                    if let Some(ref vals) = ord_meta_data.missing_vals {
-                        let msg = DisplayMissingOrd(vals).to_string();
-                        info!("{msg}");
-                        messages.push(msg);
-                    }
+                    write_result
+                };
+                if let Err(err) = write_result {
+                    error!("{err}");
+                    ui.display_msg(&err.to_string());
+                    let _ = receive_msg().await;
+                    reset_app_state(&mut ini, &game_dir, Some(&loader_dir), None, ui.as_weak());
+                    return;
                 }
                 for message in messages {
                     ui.display_msg(&message);
@@ -968,133 +1183,724 @@ fn main() {
             }).unwrap();
         }
     });
-    ui.global::<SettingsLogic>().on_toggle_theme({
+    ui.global::<MainLogic>().on_rename_mod({
         let ui_handle = ui.as_weak();
-        move |state| {
-            let span = info_span!("toggle_theme");
+        move |key, new_name, row| {
+            let span = info_span!("rename_mod");
             let _guard = span.enter();
+
             let ui = ui_handle.unwrap();
-            let current_ini = get_ini_dir();
-            if let Err(err) = save_bool(current_ini, INI_SECTIONS[0], INI_KEYS[0], state) {
-                let err_str = format!("Failed to save theme preference\n\n{err}");
-                error!("{err_str}");
-                ui.display_msg(&err_str);
-            } else {
-                info!("Theme set to: {}", DisplayTheme(state));
+            let ini_dir = get_ini_dir();
+            let game_dir = get_or_update_game_dir(None);
+            let mut ini = match Cfg::read(ini_dir) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
             };
+            let old_key = format_key(&key);
+            if let Err(err) = ini.rename_mod(&old_key, &new_name, &loader_cfg_path(&game_dir)) {
+                match err.kind() {
+                    ErrorKind::AlreadyExists | ErrorKind::InvalidInput => info!("{err}"),
+                    _ => error!("{err}"),
+                }
+                ui.display_msg(&err.to_string());
+                return;
+            }
+            let order_map = order_data_or_default(ui.as_weak(), None, None);
+            let new_key = SharedString::from(format_key(&new_name));
+            match ini.get_mod(&new_key, &game_dir, Some(&order_map)) {
+                Ok(reg_mod) => {
+                    let display_mod = deserialize_saved_mod(&reg_mod, &ini);
+                    let model = ui.global::<MainLogic>().get_current_mods();
+                    model.set_row_data(row as usize, display_mod);
+                }
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+                }
+            }
         }
     });
-    ui.global::<MainLogic>().on_edit_config_item({
+    ui.global::<MainLogic>().on_duplicate_mod({
         let ui_handle = ui.as_weak();
-        move |config_item| {
-            let span = info_span!("edit_config");
+        move |key, new_name| {
+            let span = info_span!("duplicate_mod");
             let _guard = span.enter();
 
             let ui = ui_handle.unwrap();
-            let game_dir = get_or_update_game_dir(None);
-            let item = config_item.text.to_string();
-            if !matches!(FileData::from(&item).extension, ".txt" | ".ini") {
+            if !ui.ensure_game_dir_available() {
                 return;
+            }
+            let game_dir = get_or_update_game_dir(None).clone();
+            let mut ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
             };
-            let os_file = vec![game_dir.join(item)];
-            open_text_files(ui.as_weak(), os_file);
+            match ini.duplicate_mod(&format_key(&key), &new_name, &game_dir) {
+                Ok(reg_mod) => ui.display_msg(&format!(
+                    "Duplicated {} as {}, the copy starts without a load order",
+                    DisplayName(&key),
+                    DisplayName(&reg_mod.name)
+                )),
+                Err(err) => {
+                    match err.kind() {
+                        ErrorKind::AlreadyExists | ErrorKind::InvalidInput => info!("{err}"),
+                        _ => error!("{err}"),
+                    }
+                    ui.display_msg(&err.to_string());
+                    return;
+                }
+            }
+            reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
         }
     });
-    ui.global::<MainLogic>().on_edit_config({
+    ui.global::<MainLogic>().on_request_mod_size({
         let ui_handle = ui.as_weak();
-        move |config_file| {
-            let span = info_span!("edit_config");
+        move |key| {
+            let span = info_span!("request_mod_size");
             let _guard = span.enter();
 
             let ui = ui_handle.unwrap();
+            let ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    error!("{err}");
+                    return;
+                }
+            };
             let game_dir = get_or_update_game_dir(None);
-            let downcast_config_file = config_file
-                .as_any()
-                .downcast_ref::<VecModel<SharedString>>()
-                .expect("We know we set a VecModel earlier");
-            let os_files = downcast_config_file
-                .iter()
-                .map(|path| game_dir.join(path.to_string()))
-                .collect::<Vec<_>>();
-            open_text_files(ui.as_weak(), os_files);
+            let size = match ini
+                .get_mod(&key, &game_dir, None)
+                .and_then(|reg_mod| reg_mod.total_size_on_disk(&game_dir))
+            {
+                Ok(bytes) => DisplaySize(bytes).to_string(),
+                Err(err) => {
+                    error!("{err}");
+                    return;
+                }
+            };
+            let model = ui.global::<MainLogic>().get_current_mods();
+            if let Some((row, mut display_mod)) =
+                model.iter().enumerate().find(|(_, m)| m.name == key)
+            {
+                display_mod.size = SharedString::from(size);
+                model.set_row_data(row, display_mod);
+            }
         }
     });
-    ui.global::<SettingsLogic>().on_toggle_terminal({
+    ui.global::<MainLogic>().on_request_mod_note({
         let ui_handle = ui.as_weak();
-        move |state| -> bool {
-            let span = info_span!("toggle_terminal");
+        move |key| {
+            let span = info_span!("request_mod_note");
             let _guard = span.enter();
 
             let ui = ui_handle.unwrap();
-            let value = if state { "1" } else { "0" };
-            let ext_ini = get_loader_ini_dir();
-            if let Err(err) = save_value_ext(ext_ini, LOADER_SECTIONS[0], LOADER_KEYS[1], value) {
-                error!("{err}");
-                ui.display_msg(&err.to_string());
-                return !state;
+            let ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    error!("{err}");
+                    return;
+                }
+            };
+            let note = ini.get_mod_note(&key).unwrap_or_default();
+            let model = ui.global::<MainLogic>().get_current_mods();
+            if let Some((row, mut display_mod)) =
+                model.iter().enumerate().find(|(_, m)| m.name == key)
+            {
+                display_mod.note = SharedString::from(note);
+                model.set_row_data(row, display_mod);
             }
-            info!("Show terminal set to: {}", state);
-            state
         }
     });
-    ui.global::<SettingsLogic>().on_set_load_delay({
+    ui.global::<MainLogic>().on_set_mod_note({
         let ui_handle = ui.as_weak();
-        move |time| {
-            let span = info_span!("set_load_delay");
+        move |key, note| {
+            let span = info_span!("set_mod_note");
             let _guard = span.enter();
 
             let ui = ui_handle.unwrap();
-            ui.global::<MainLogic>().invoke_force_app_focus();
-            if let Err(err) = save_value_ext(
-                get_loader_ini_dir(),
-                LOADER_SECTIONS[0],
-                LOADER_KEYS[0],
-                &time,
-            ) {
-                error!("{err}");
-                ui.display_msg(&format!("Failed to set load delay\n\n{err}"));
+            let mut ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            ini.set_mod_note(&key, &note);
+            if let Err(err) = ini.write_to_file() {
+                ui.display_and_log_err(err);
                 return;
             }
-            info!("Load delay set to: {}", DisplayTime(&time));
-            ui.global::<SettingsLogic>()
-                .set_load_delay(SharedString::from(DisplayTime(time).to_string()));
-            ui.global::<SettingsLogic>().set_delay_input(SharedString::new());
+            info!("Saved note for: {}", DisplayName(&key));
+            let model = ui.global::<MainLogic>().get_current_mods();
+            if let Some((row, mut display_mod)) =
+                model.iter().enumerate().find(|(_, m)| m.name == key)
+            {
+                display_mod.note = SharedString::from(ini.get_mod_note(&key).unwrap_or_default());
+                model.set_row_data(row, display_mod);
+            }
         }
     });
-    ui.global::<SettingsLogic>().on_toggle_all({
+    ui.global::<MainLogic>().on_toggle_pin({
         let ui_handle = ui.as_weak();
-        move |state| -> bool {
-            let span = info_span!("toggle_all");
+        move |key, state| {
+            let span = info_span!("toggle_pin");
             let _guard = span.enter();
 
             let ui = ui_handle.unwrap();
-            let game_dir = get_or_update_game_dir(None);
-            let loader = ModLoader::properties(&game_dir).unwrap_or_else(|err| {
-                ui.display_msg(&err.to_string());
-                error!("{err}");
-                ModLoader::new(!state)
-            });
-            if loader.anti_cheat_enabled() {
-                ui.display_msg(&DisplayAntiCheatMsg.to_string());
-                ui.global::<SettingsLogic>().set_loader_disabled(true);
-                return !state;
-            }
-            let files = if loader.disabled() {
-                vec![PathBuf::from(LOADER_FILES[0])]
-            } else {
-                vec![PathBuf::from(LOADER_FILES[1])]
+            let mut ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
             };
-            let mut main_dll = RegMod::new(LOADER_FILES[1], !loader.disabled(), files);
-            toggle_files(&game_dir, !state, &mut main_dll, None)
-                .map(|_| state)
-                .unwrap_or_else(|err| {
-                    error!("{err}");
-                    ui.display_msg(&format!("{err}"));
-                    !state
-                })
+            ini.set_pinned(&key, state);
+            if let Err(err) = ini.write_to_file() {
+                ui.display_and_log_err(err);
+                return;
+            }
+            info!("{}: {}", if state { "Pinned" } else { "Unpinned" }, DisplayName(&key));
+            let model = ui.global::<MainLogic>().get_current_mods();
+            if let Some((row, mut display_mod)) =
+                model.iter().enumerate().find(|(_, m)| m.name == key)
+            {
+                display_mod.pinned = state;
+                model.set_row_data(row, display_mod);
+            }
+            let (rows_moved, key_row) = sort_display_rows(&model, Some(key.as_str()));
+            if let Some(row) = key_row {
+                ui.invoke_update_mod_index(row as i32, 0);
+            }
+            if rows_moved {
+                ui.invoke_redraw_checkboxes();
+            }
         }
     });
-    ui.global::<SettingsLogic>().on_open_game_dir({
+    ui.global::<MainLogic>().on_toggle_favorite({
+        let ui_handle = ui.as_weak();
+        move |key| {
+            let span = info_span!("toggle_favorite");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let mut ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            let favorite = match ini.toggle_favorite(&key) {
+                Ok(favorite) => favorite,
+                Err(err) => {
+                    ui.display_msg(&err.to_string());
+                    return;
+                }
+            };
+            if let Err(err) = ini.write_to_file() {
+                ui.display_and_log_err(err);
+                return;
+            }
+            info!(
+                "{}: {}",
+                if favorite { "Added to favorites" } else { "Removed from favorites" },
+                DisplayName(&key)
+            );
+            let model = ui.global::<MainLogic>().get_current_mods();
+            if let Some((row, mut display_mod)) =
+                model.iter().enumerate().find(|(_, m)| m.name == key)
+            {
+                display_mod.favorite = favorite;
+                model.set_row_data(row, display_mod);
+            }
+        }
+    });
+    ui.global::<MainLogic>().on_set_category({
+        let ui_handle = ui.as_weak();
+        move |key, category| {
+            let span = info_span!("set_category");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let mut ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            ini.set_category(&key, &category);
+            if let Err(err) = ini.write_to_file() {
+                ui.display_and_log_err(err);
+                return;
+            }
+            let category = SharedString::from(ini.get_category(&key).unwrap_or(DEFAULT_CATEGORY));
+            info!("Moved: {}, to category: {category}", DisplayName(&key));
+            let model = ui.global::<MainLogic>().get_current_mods();
+            // expand the new category so the edited mod stays visible
+            set_category_collapsed(&model, &category, false);
+            if let Some((row, mut display_mod)) =
+                model.iter().enumerate().find(|(_, m)| m.name == key)
+            {
+                display_mod.category = category;
+                display_mod.collapsed = false;
+                model.set_row_data(row, display_mod);
+            }
+            let (rows_moved, key_row) = sort_display_rows(&model, Some(key.as_str()));
+            if let Some(row) = key_row {
+                ui.invoke_update_mod_index(row as i32, 0);
+            }
+            if rows_moved {
+                ui.invoke_redraw_checkboxes();
+            }
+        }
+    });
+    ui.global::<MainLogic>().on_toggle_category({
+        let ui_handle = ui.as_weak();
+        move |category, collapsed| {
+            let ui = ui_handle.unwrap();
+            let model = ui.global::<MainLogic>().get_current_mods();
+            set_category_collapsed(&model, &category, collapsed);
+        }
+    });
+    ui.global::<SettingsLogic>().on_toggle_theme({
+        let ui_handle = ui.as_weak();
+        move |state| {
+            let span = info_span!("toggle_theme");
+            let _guard = span.enter();
+            let ui = ui_handle.unwrap();
+            let current_ini = get_ini_dir();
+            if let Err(err) = save_bool(current_ini, INI_SECTIONS[0], INI_KEYS[0], state) {
+                let err_str = format!("Failed to save theme preference\n\n{err}");
+                error!("{err_str}");
+                ui.display_msg(&err_str);
+            } else {
+                info!("Theme set to: {}", DisplayTheme(state));
+            };
+        }
+    });
+    ui.global::<SettingsLogic>().on_toggle_save_log({
+        let ui_handle = ui.as_weak();
+        move |state| -> bool {
+            let span = info_span!("toggle_save_log");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            if let Err(err) = save_bool(get_ini_dir(), INI_SECTIONS[0], INI_KEYS[1], state) {
+                let err_str = format!("Failed to save log preference\n\n{err}");
+                error!("{err_str}");
+                ui.display_msg(&err_str);
+                return !state;
+            }
+            info!("Save log set to: {state}");
+            if let Err(err) = set_save_log(state) {
+                let err_str = format!("Log preference saved, restart the app to apply\n\n{err}");
+                error!("{err_str}");
+                ui.display_msg(&err_str);
+            }
+            state
+        }
+    });
+    ui.global::<SettingsLogic>().on_change_log_level({
+        let ui_handle = ui.as_weak();
+        move |level| -> SharedString {
+            let span = info_span!("change_log_level");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let current = ui.global::<SettingsLogic>().get_log_level();
+            let new_level = match level.parse::<tracing::Level>() {
+                Ok(new_level) => new_level,
+                Err(err) => {
+                    error!("{err}: '{level}'");
+                    ui.display_msg(&format!("Invalid log level: '{level}'"));
+                    return current;
+                }
+            };
+            if let Err(err) = save_value(get_ini_dir(), INI_SECTIONS[0], INI_KEYS[7], &level) {
+                let err_str = format!("Failed to save log level\n\n{err}");
+                error!("{err_str}");
+                ui.display_msg(&err_str);
+                return current;
+            }
+            // logged before applying so the change is still recorded when raising the level
+            info!("Log level set to: {level}");
+            if let Err(err) = set_log_level(new_level) {
+                let err_str = format!("Log level saved, restart the app to apply\n\n{err}");
+                error!("{err_str}");
+                ui.display_msg(&err_str);
+            }
+            level
+        }
+    });
+    ui.global::<SettingsLogic>().on_change_launch_method({
+        let ui_handle = ui.as_weak();
+        move |method| -> SharedString {
+            let span = info_span!("change_launch_method");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let current = ui.global::<SettingsLogic>().get_launch_method();
+            if let Err(err) = method.parse::<LaunchMethod>() {
+                error!("{err}");
+                ui.display_msg(&format!("Invalid launch method: '{method}'"));
+                return current;
+            }
+            if let Err(err) = save_value(get_ini_dir(), INI_SECTIONS[0], INI_KEYS[12], &method) {
+                let err_str = format!("Failed to save launch method\n\n{err}");
+                error!("{err_str}");
+                ui.display_msg(&err_str);
+                return current;
+            }
+            info!("Launch method set to: {method}");
+            method
+        }
+    });
+    ui.global::<SettingsLogic>().on_change_confirm_level({
+        let ui_handle = ui.as_weak();
+        move |level| -> SharedString {
+            let span = info_span!("change_confirm_level");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let current = ui.global::<SettingsLogic>().get_confirm_level();
+            if let Err(err) = level.parse::<ConfirmLevel>() {
+                error!("{err}");
+                ui.display_msg(&format!("Invalid confirm level: '{level}'"));
+                return current;
+            }
+            if let Err(err) = save_value(get_ini_dir(), INI_SECTIONS[0], INI_KEYS[13], &level) {
+                let err_str = format!("Failed to save confirm level\n\n{err}");
+                error!("{err_str}");
+                ui.display_msg(&err_str);
+                return current;
+            }
+            info!("Confirm level set to: {level}");
+            level
+        }
+    });
+    ui.global::<SettingsLogic>().on_toggle_eac_warning({
+        let ui_handle = ui.as_weak();
+        move |state| -> bool {
+            let span = info_span!("toggle_eac_warning");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            if let Err(err) = save_bool(get_ini_dir(), INI_SECTIONS[0], INI_KEYS[4], state) {
+                let err_str = format!("Failed to save anti-cheat reminder preference\n\n{err}");
+                error!("{err_str}");
+                ui.display_msg(&err_str);
+                return !state;
+            }
+            info!("Suppress anti-cheat reminder set to: {state}");
+            state
+        }
+    });
+    ui.global::<SettingsLogic>().on_toggle_watch_files({
+        let ui_handle = ui.as_weak();
+        move |state| -> bool {
+            let span = info_span!("toggle_watch_files");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            if let Err(err) = save_bool(get_ini_dir(), INI_SECTIONS[0], INI_KEYS[6], state) {
+                let err_str = format!("Failed to save watch files preference\n\n{err}");
+                error!("{err_str}");
+                ui.display_msg(&err_str);
+                return !state;
+            }
+            info!("Watch files set to: {state}");
+            if ui.global::<MainLogic>().get_game_path_valid() {
+                match Cfg::read(get_ini_dir()) {
+                    Ok(ini) => {
+                        let game_dir = get_or_update_game_dir(None).clone();
+                        start_file_watcher(&ini, &game_dir, ui.as_weak());
+                    }
+                    Err(err) => ui.display_and_log_err(err),
+                }
+            }
+            state
+        }
+    });
+    ui.global::<SettingsLogic>().on_toggle_move_files({
+        let ui_handle = ui.as_weak();
+        move |state| -> bool {
+            let span = info_span!("toggle_move_files");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            if let Err(err) = save_bool(get_ini_dir(), INI_SECTIONS[0], INI_KEYS[10], state) {
+                let err_str = format!("Failed to save move files preference\n\n{err}");
+                error!("{err_str}");
+                ui.display_msg(&err_str);
+                return !state;
+            }
+            info!("Move files set to: {state}");
+            state
+        }
+    });
+    ui.global::<SettingsLogic>().on_set_editor({
+        let ui_handle = ui.as_weak();
+        move |editor| {
+            let span = info_span!("set_editor");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let editor = match editor.trim() {
+                "" => DEFAULT_EDITOR,
+                editor => editor,
+            };
+            let editor_path = Path::new(editor);
+            if let Err(err) = save_path(get_ini_dir(), INI_SECTIONS[0], INI_KEYS[3], editor_path) {
+                let err_str = format!("Failed to save editor\n\n{err}");
+                error!("{err_str}");
+                ui.display_msg(&err_str);
+                return;
+            }
+            info!("Editor set to: {editor}");
+            ui.global::<SettingsLogic>().set_editor(SharedString::from(editor));
+        }
+    });
+    ui.global::<MainLogic>().on_edit_config_item({
+        let ui_handle = ui.as_weak();
+        move |config_item| {
+            let span = info_span!("edit_config");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let game_dir = get_or_update_game_dir(None);
+            let item = config_item.text.to_string();
+            if !matches!(FileData::from(&item).extension, ".txt" | ".ini") {
+                return;
+            };
+            let os_file = vec![game_dir.join(item)];
+            open_text_files(ui.as_weak(), os_file);
+        }
+    });
+    ui.global::<MainLogic>().on_edit_config({
+        let ui_handle = ui.as_weak();
+        move |config_file| {
+            let span = info_span!("edit_config");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let game_dir = get_or_update_game_dir(None);
+            let downcast_config_file = config_file
+                .as_any()
+                .downcast_ref::<VecModel<SharedString>>()
+                .expect("We know we set a VecModel earlier");
+            let os_files = downcast_config_file
+                .iter()
+                .map(|path| game_dir.join(path.to_string()))
+                .collect::<Vec<_>>();
+            open_text_files(ui.as_weak(), os_files);
+        }
+    });
+    ui.global::<SettingsLogic>().on_toggle_terminal({
+        let ui_handle = ui.as_weak();
+        move |state| -> bool {
+            let span = info_span!("toggle_terminal");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let mut loader_cfg = match ModLoaderCfg::read(get_loader_ini_dir()) {
+                Ok(data) => data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return !state;
+                }
+            };
+            if let Err(err) = loader_cfg.set_bool_key(LOADER_KEYS[1], state) {
+                error!("{err}");
+                ui.display_msg(&err.to_string());
+                return !state;
+            }
+            info!("Show terminal set to: {}", state);
+            state
+        }
+    });
+    ui.global::<SettingsLogic>().on_toggle_order_suspended({
+        let ui_handle = ui.as_weak();
+        move |state| -> bool {
+            let span = info_span!("toggle_order_suspended");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let mut loader_cfg = match ModLoaderCfg::read(get_loader_ini_dir()) {
+                Ok(data) => data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return !state;
+                }
+            };
+            let result = if state {
+                loader_cfg.suspend_order()
+            } else {
+                loader_cfg.resume_order()
+            };
+            if let Err(err) = result {
+                error!("{err}");
+                ui.display_msg(&err.to_string());
+                return loader_cfg.order_suspended();
+            }
+            ui.global::<MainLogic>()
+                .set_config_contents(ui.global::<MainLogic>().invoke_view_configs());
+            if ui.global::<MainLogic>().get_game_path_valid() {
+                let game_dir = get_or_update_game_dir(None).clone();
+                let mut ini = Cfg::default(get_ini_dir());
+                reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+                // stay on the config view the toggle was made from
+                ui.global::<MainLogic>().set_current_subpage(3);
+            }
+            state
+        }
+    });
+    ui.global::<SettingsLogic>().on_set_load_delay({
+        let ui_handle = ui.as_weak();
+        move |time| {
+            let span = info_span!("set_load_delay");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            ui.global::<MainLogic>().invoke_force_app_focus();
+            let delay = match parse_load_delay(&time) {
+                Ok(delay) => delay,
+                Err(err) => {
+                    warn!("{err}");
+                    ui.display_msg(&err.to_string());
+                    return;
+                }
+            };
+            if let Err(err) = save_value_ext(
+                get_loader_ini_dir(),
+                LOADER_SECTIONS[0],
+                LOADER_KEYS[0],
+                &delay.to_string(),
+            ) {
+                error!("{err}");
+                ui.display_msg(&format!("Failed to set load delay\n\n{err}"));
+                return;
+            }
+            info!("Load delay set to: {}", DisplayTime(delay));
+            ui.global::<SettingsLogic>()
+                .set_load_delay(SharedString::from(DisplayTime(delay).to_string()));
+            ui.global::<SettingsLogic>().set_delay_input(SharedString::new());
+        }
+    });
+    ui.global::<SettingsLogic>().on_restore_backup({
+        let ui_handle = ui.as_weak();
+        move || {
+            let span = info_span!("restore_backup");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let backups = [get_ini_dir(), get_loader_ini_dir()]
+                .into_iter()
+                .filter(|path| matches!(backup_path(path).try_exists(), Ok(true)))
+                .collect::<Vec<_>>();
+            if backups.is_empty() {
+                ui.display_msg("No config backups were found");
+                return;
+            }
+            let span_clone = span.clone();
+            slint::spawn_local(async move {
+                let _guard = span_clone.enter();
+                let changes = backup_diff()
+                    .map(|diff| diff.to_string())
+                    .unwrap_or_else(|err| {
+                        error!("{err}");
+                        format!("Could not preview changes, {err}")
+                    });
+                ui.display_confirm(
+                    &format!(
+                        "Replace the current config with the last backup?\n\n\
+                        Changes:\n{changes}\n\n\
+                        The current config is kept as the new backup"
+                    ),
+                    Buttons::YesNo,
+                );
+                if receive_msg().await != Message::Confirm {
+                    return;
+                }
+                for path in backups {
+                    if let Err(err) = restore_backup(path) {
+                        ui.display_and_log_err(err);
+                        return;
+                    }
+                }
+                let mut ini = match Cfg::read(get_ini_dir()) {
+                    Ok(ini_data) => ini_data,
+                    Err(err) => {
+                        ui.display_and_log_err(err);
+                        return;
+                    }
+                };
+                set_backup_configs(ini.get_backup_configs());
+                if ui.global::<MainLogic>().get_game_path_valid() {
+                    let game_dir = get_or_update_game_dir(None).clone();
+                    reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+                }
+                ui.display_msg("Config backup restored");
+            })
+            .unwrap();
+        }
+    });
+    ui.global::<SettingsLogic>().on_toggle_all({
+        let ui_handle = ui.as_weak();
+        move |state| -> bool {
+            let span = info_span!("toggle_all");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            if !ui.ensure_game_closed() {
+                return !state;
+            }
+            let game_dir = get_or_update_game_dir(None);
+            let mut loader = loader_backend(&game_dir).unwrap_or_else(|err| {
+                ui.display_msg(&err.to_string());
+                error!("{err}");
+                Box::new(DinputLoader::new(!state))
+            });
+            if loader.anti_cheat_enabled() {
+                ui.display_msg(&DisplayAntiCheatMsg.to_string());
+                ui.global::<SettingsLogic>().set_loader_disabled(true);
+                return !state;
+            }
+            loader
+                .set_enabled(&game_dir, !state)
+                .map(|_| state)
+                .unwrap_or_else(|err| {
+                    error!("{err}");
+                    ui.display_msg(&format!("{err}"));
+                    !state
+                })
+        }
+    });
+    ui.global::<SettingsLogic>().on_disable_all_mods({
+        let ui_handle = ui.as_weak();
+        move || {
+            let span = info_span!("disable_all_mods");
+            let _guard = span.enter();
+
+            ui_handle.unwrap().set_all_mods_state(false);
+        }
+    });
+    ui.global::<SettingsLogic>().on_enable_all_mods({
+        let ui_handle = ui.as_weak();
+        move || {
+            let span = info_span!("enable_all_mods");
+            let _guard = span.enter();
+
+            ui_handle.unwrap().set_all_mods_state(true);
+        }
+    });
+    ui.global::<SettingsLogic>().on_open_game_dir({
         let ui_handle = ui.as_weak();
         move || {
             let span = info_span!("open_game_dir");
@@ -1110,41 +1916,437 @@ fn main() {
                     Ok(_) => (),
                     Err(err) => {
                         error!("{err}");
-                        ui.display_msg(&format!("{err}"));
+                        ui.display_msg(&format!("{err}"));
+                    }
+                },
+                Err(err) => {
+                    error!("Thread panicked! {err:?}");
+                    ui.display_msg(&format!("{err:?}"));
+                }
+            }
+        }
+    });
+    ui.global::<SettingsLogic>().on_launch_game({
+        let ui_handle = ui.as_weak();
+        move || {
+            let span = info_span!("launch_game");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            if !ui.ensure_game_dir_available() {
+                return;
+            }
+            if is_game_running() {
+                ui.display_msg("Elden Ring is already running");
+                return;
+            }
+            let method = ui
+                .global::<SettingsLogic>()
+                .get_launch_method()
+                .parse::<LaunchMethod>()
+                .unwrap_or_else(|err| {
+                    error!("{err}");
+                    DEFAULT_LAUNCH_METHOD
+                });
+            let game_dir = get_or_update_game_dir(None).clone();
+            let anti_cheat_enabled = loader_backend(&game_dir)
+                .map(|loader| loader.anti_cheat_enabled())
+                .unwrap_or_else(|err| {
+                    error!("{err}");
+                    false
+                });
+            let warn_anti_cheat =
+                anti_cheat_enabled && !ui.global::<SettingsLogic>().get_suppress_eac_warning();
+            let span_clone = span.clone();
+            slint::spawn_local(async move {
+                let _guard = span_clone.enter();
+                if warn_anti_cheat {
+                    ui.display_confirm(
+                        "EAC appears to be enabled, mods will not be loaded\n\n\
+                        Launch Elden Ring anyway?",
+                        Buttons::YesNo,
+                    );
+                    if receive_msg().await != Message::Confirm {
+                        return;
+                    }
+                }
+                let jh = std::thread::spawn(move || launch_game(method, &game_dir));
+                match jh.join() {
+                    Ok(result) => match result {
+                        Ok(launched) => info!("Launched Elden Ring with: {}", launched.as_str()),
+                        Err(err) => {
+                            error!("{err}");
+                            ui.display_msg(&format!("{err}"));
+                        }
+                    },
+                    Err(err) => {
+                        error!("Thread panicked! {err:?}");
+                        ui.display_msg(&format!("{err:?}"));
+                    }
+                }
+            })
+            .unwrap();
+        }
+    });
+    ui.global::<MainLogic>().on_open_mod_dir({
+        let ui_handle = ui.as_weak();
+        move |key| {
+            let span = info_span!("open_mod_dir");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let game_dir = get_or_update_game_dir(None);
+            let ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            let reg_mod = match ini.get_mod(&key, &game_dir, None) {
+                Ok(reg_mod) => reg_mod,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            let install_dir = match reg_mod.install_dir(&game_dir) {
+                // a single `.dll` with no other files is installed directly in "mods"
+                Ok(dir) if !dir.exists() && reg_mod.files.len() == 1 => dir
+                    .parent()
+                    .expect("install_dir is a sub-directory of mods")
+                    .to_path_buf(),
+                Ok(dir) => dir,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            if !matches!(install_dir.try_exists(), Ok(true)) {
+                let err_str = format!("Could not find: '{}'", install_dir.display());
+                warn!("{err_str}");
+                ui.display_msg(&err_str);
+                return;
+            }
+            let select_file = reg_mod
+                .files
+                .dll
+                .first()
+                .map(|dll| game_dir.join(dll))
+                .filter(|dll| matches!(dll.try_exists(), Ok(true)));
+            let jh = std::thread::spawn(move || {
+                let mut command = std::process::Command::new("explorer");
+                if let Some(file) = select_file {
+                    command.arg("/select,").arg(file);
+                } else {
+                    command.arg(install_dir);
+                }
+                command.spawn()
+            });
+            match jh.join() {
+                Ok(result) => match result {
+                    Ok(_) => (),
+                    Err(err) => {
+                        error!("{err}");
+                        ui.display_msg(&format!("{err}"));
+                    }
+                },
+                Err(err) => {
+                    error!("Thread panicked! {err:?}");
+                    ui.display_msg(&format!("{err:?}"));
+                }
+            }
+        }
+    });
+    ui.global::<MainLogic>().on_send_message({
+        move |message| {
+            let key = GLOBAL_NUM_KEY.load(Ordering::Acquire);
+            message_sender
+                .send(MessageData { message, key })
+                .unwrap_or_else(|err| {
+                    let span = info_span!("send_message");
+                    let _guard = span.enter();
+                    error!("Failed to send message: {:?}, over channel", err.0.message);
+                });
+        }
+    });
+    ui.global::<MainLogic>().on_cancel_install({
+        move || {
+            let span = info_span!("cancel_install");
+            let _guard = span.enter();
+            CANCEL_INSTALL.store(true, Ordering::Release);
+            info!("Install cancel requested");
+        }
+    });
+    ui.global::<SettingsLogic>().on_scan_for_mods({
+        let ui_handle = ui.as_weak();
+        move || {
+            let ui = ui_handle.unwrap();
+            slint::spawn_local(async move {
+                let span = info_span!("scan_for_mods");
+                let _guard = span.enter();
+                let game_dir = get_or_update_game_dir(None).clone();
+                if let Err(err) = confirm_scan_mods(ui.as_weak(), &game_dir, None, None).await {
+                    ui.display_and_log_err(err);
+                };
+            })
+            .unwrap();
+        }
+    });
+    ui.global::<SettingsLogic>().on_recheck_game_dir({
+        let ui_handle = ui.as_weak();
+        move || {
+            let span = info_span!("recheck_game_dir");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let mut ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            let game_dir = match ini.attempt_locate_game() {
+                Ok(PathResult::Full(path)) => path,
+                Ok(PathResult::GamePass(_)) => {
+                    ui.global::<MainLogic>().set_game_path_valid(false);
+                    ui.display_msg(GAME_PASS_MSG);
+                    return;
+                }
+                Ok(PathResult::Partial(_) | PathResult::None(_)) => {
+                    ui.global::<MainLogic>().set_game_path_valid(false);
+                    ui.display_msg(
+                        "Could not locate Elden Ring\nPlease Select the install directory for Elden Ring",
+                    );
+                    return;
+                }
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            info!("Game directory: '{}', is accessible", game_dir.display());
+            let mut dsp_msgs = Vec::new();
+            let found = setup_found_game(&mut ini, &game_dir, &mut dsp_msgs);
+            ui.global::<SettingsLogic>()
+                .set_game_path(game_dir.to_string_lossy().to_string().into());
+            ui.global::<SettingsLogic>()
+                .set_game_version(game_version(Some(&game_dir)));
+            ui.global::<MainLogic>().set_game_path_valid(true);
+            ui.global::<MainLogic>().set_current_subpage(0);
+            drop(get_or_update_game_dir(Some(game_dir.clone())));
+            let suppress_eac_warning = ui.global::<SettingsLogic>().get_suppress_eac_warning();
+            apply_found_game(&ui, &ini, &found, suppress_eac_warning, &mut dsp_msgs);
+            start_file_watcher(&ini, &game_dir, ui.as_weak());
+            if dsp_msgs.is_empty() {
+                return;
+            }
+            let span_clone = span.clone();
+            slint::spawn_local(async move {
+                let _guard = span_clone.enter();
+                for msg in dsp_msgs {
+                    ui.display_msg(&msg);
+                    let _ = receive_msg().await;
+                }
+            })
+            .unwrap();
+        }
+    });
+    ui.global::<SettingsLogic>().on_run_diagnostics({
+        let ui_handle = ui.as_weak();
+        move || {
+            let span = info_span!("run_diagnostics");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            let report = ini.diagnose(&get_or_update_game_dir(None), &get_unknown_orders());
+            if report.is_healthy() {
+                info!("Diagnostics found no issues");
+            } else {
+                warn!("Diagnostics found issues:\n{report}");
+            }
+            if report.unregistered_files.is_empty() {
+                ui.display_msg(&report.to_string());
+                return;
+            }
+            let span_clone = span.clone();
+            slint::spawn_local(async move {
+                let _guard = span_clone.enter();
+                ui.display_confirm(
+                    &format!(
+                        "{report}\n\nRemove the {} file(s) in \"mods\" not registered to any mod? \
+                        This can not be undone",
+                        report.unregistered_files.len()
+                    ),
+                    Buttons::YesNo,
+                );
+                if receive_msg().await != Message::Confirm {
+                    return;
+                }
+                let game_dir = get_or_update_game_dir(None).clone();
+                let removed =
+                    clean_orphaned_files(&game_dir, get_ini_dir(), &get_unknown_orders());
+                match removed {
+                    Ok(removed) => ui.display_msg(&format!(
+                        "Removed {} file(s): {}",
+                        removed.len(),
+                        DisplayVec(&removed)
+                    )),
+                    Err(err) => ui.display_and_log_err(err),
+                }
+            })
+            .unwrap();
+        }
+    });
+    ui.global::<SettingsLogic>().on_verify_all({
+        let ui_handle = ui.as_weak();
+        move || {
+            let span = info_span!("verify_all");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let mut ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            let game_dir = get_or_update_game_dir(None);
+            match ini.verify_all(&game_dir) {
+                Ok(summary) => ui.display_msg(&summary.to_string()),
+                Err(err) => ui.display_and_log_err(err),
+            }
+            reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+        }
+    });
+    ui.global::<SettingsLogic>().on_import_load_order({
+        let ui_handle = ui.as_weak();
+        move || {
+            let span = info_span!("import_load_order");
+            let _guard = span.enter();
+
+            let ui = ui_handle.unwrap();
+            let game_dir = get_or_update_game_dir(None).clone();
+            let order_file = match get_load_order_file(&game_dir, ui.window()) {
+                Ok(file) => file,
+                Err(err) => {
+                    if err.kind() != ErrorKind::InvalidInput {
+                        error!("{err}");
                     }
-                },
+                    ui.display_msg(&err.to_string());
+                    return;
+                }
+            };
+            let contents = match std::fs::read_to_string(&order_file) {
+                Ok(contents) => contents,
                 Err(err) => {
-                    error!("Thread panicked! {err:?}");
-                    ui.display_msg(&format!("{err:?}"));
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            let mut ini = match Cfg::read(get_ini_dir()) {
+                Ok(ini_data) => ini_data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
                 }
+            };
+            let mut load_order = match ModLoaderCfg::read(get_loader_ini_dir()) {
+                Ok(data) => data,
+                Err(err) => {
+                    ui.display_and_log_err(err);
+                    return;
+                }
+            };
+            if load_order.order_suspended() {
+                ui.display_msg("Resume the load order before importing a new one");
+                return;
             }
+            let lines = contents.lines().collect::<Vec<_>>();
+            let (dlls, _, _) = ini.dll_set_order_count(load_order.mut_section());
+            let matched = lines
+                .iter()
+                .filter_map(|line| order_line_key(line, &dlls))
+                .collect::<HashSet<_>>()
+                .len();
+            let unmatched = lines
+                .iter()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .filter(|line| order_line_key(line, &dlls).is_none())
+                .collect::<Vec<_>>();
+            let ord_meta_data = load_order.import_order_from_lines(&lines, &dlls);
+            if let Err(err) = load_order.write_to_file() {
+                ui.display_and_log_err(err);
+                return;
+            }
+            info!("Imported load order from: {}", order_file.display());
+            let mut msg = format!("Imported load order for {matched} dll(s)");
+            if !unmatched.is_empty() {
+                msg.push_str(&format!(
+                    "\n\nLines that did not match a registered dll:\n{}",
+                    unmatched.join("\n")
+                ));
+            }
+            if let Some(ref vals) = ord_meta_data.missing_vals {
+                msg.push_str(&format!("\n\n{}", DisplayMissingOrd(vals)));
+            }
+            ui.display_msg(&msg);
+            ui.global::<MainLogic>()
+                .set_config_contents(ui.global::<MainLogic>().invoke_view_configs());
+            reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
         }
     });
-    ui.global::<MainLogic>().on_send_message({
-        move |message| {
-            let key = GLOBAL_NUM_KEY.load(Ordering::Acquire);
-            message_sender
-                .send(MessageData { message, key })
-                .unwrap_or_else(|err| {
-                    let span = info_span!("send_message");
-                    let _guard = span.enter();
-                    error!("Failed to send message: {:?}, over channel", err.0.message);
-                });
-        }
-    });
-    ui.global::<SettingsLogic>().on_scan_for_mods({
+    ui.global::<SettingsLogic>().on_normalize_order({
         let ui_handle = ui.as_weak();
         move || {
+            let span = info_span!("normalize_order");
+            let _guard = span.enter();
+
             let ui = ui_handle.unwrap();
-            slint::spawn_local(async move {
-                let span = info_span!("scan_for_mods");
-                let _guard = span.enter();
-                let game_dir = get_or_update_game_dir(None);
-                if let Err(err) = confirm_scan_mods(ui.as_weak(), &game_dir, None, None).await {
+            let mut load_order = match ModLoaderCfg::read(get_loader_ini_dir()) {
+                Ok(data) => data,
+                Err(err) => {
                     ui.display_and_log_err(err);
-                };
-            })
-            .unwrap();
+                    return;
+                }
+            };
+            if load_order.order_suspended() {
+                ui.display_msg("Resume the load order before normalizing it");
+                return;
+            }
+            let unknown_orders = get_unknown_orders();
+            let (ord_meta_data, moved) = load_order.normalize_order(&unknown_orders);
+            if moved == 0 {
+                info!("Load order has no gaps, nothing to normalize");
+                ui.display_msg("Load order has no gaps, no entries were moved");
+                return;
+            }
+            if let Err(err) = load_order.write_to_file() {
+                ui.display_and_log_err(err);
+                return;
+            }
+            ui.global::<MainLogic>()
+                .set_max_order(MaxOrder::from(ord_meta_data.max_order));
+            let model = ui.global::<MainLogic>().get_current_mods();
+            model.update_order(None, &load_order.parse_into_map(), &unknown_orders, ui.as_weak());
+            let mut msg = format!("Normalized load order, {moved} entries were moved");
+            info!("{msg}");
+            if let Some(ref vals) = ord_meta_data.missing_vals {
+                msg.push_str(&format!("\n\n{}", DisplayMissingOrd(vals)));
+            }
+            ui.display_msg(&msg);
+            ui.global::<MainLogic>()
+                .set_config_contents(ui.global::<MainLogic>().invoke_view_configs());
         }
     });
     ui.global::<MainLogic>().on_add_remove_order({
@@ -1163,25 +2365,26 @@ fn main() {
                 }
             };
             let load_orders = load_order.mut_section();
-            let stable_k = if state {
+            if state {
                 load_orders.insert(&key, value.to_string());
-                Some(key.as_str())
             } else {
                 if !load_orders.contains_key(&key) {
                     warn!("Could not find key: {key}, in: {}", LOADER_FILES[3]);
                     return ERROR_VAL;
                 }
                 load_orders.remove(&key);
-                None
-            };
+            }
             let unknown_orders = get_unknown_orders();
-            let ord_meta_data = load_order.update_order_entries(stable_k, &unknown_orders);
-            if let Err(err) = load_order.write_to_file() {
-                error!("{err}");
-                ui.display_msg(&format!(
-                    "Failed to write to \"mod_loader_config.ini\"\n{err}"
-                ));
-                return ERROR_VAL;
+            // only the changed line is written unless other values need to be renormalized
+            let ord_meta_data = match load_order.write_order_change(&key, state, &unknown_orders) {
+                Ok(data) => data,
+                Err(err) => {
+                    error!("{err}");
+                    ui.display_msg(&format!(
+                        "Failed to write to \"mod_loader_config.ini\"\n{err}"
+                    ));
+                    return ERROR_VAL;
+                }
             };
             let new_orders = load_order.parse_into_map();
             ui.global::<MainLogic>()
@@ -1332,129 +2535,138 @@ impl Sortable for ModelRc<DisplayMod> {
         unknown_orders: &HashSet<String>,
         ui_handle: slint::Weak<App>,
     ) {
-        let order_map_len = order_map.len();
-        if order_map_len == 0 {
+        if order_map.is_empty() {
             return;
         }
         let ui = ui_handle.unwrap();
-        let selected_key = selected_row.map(|row| {
-            self.row_data(row as usize)
-                .expect("front end gives us a valid row")
-                .name
-        });
-        let mut unsorted_idx = (0..self.row_count()).collect::<Vec<_>>();
-        let mut possible_vals = HashSet::with_capacity(order_map_len);
-        let mut order_counts = vec![0_usize; order_map_len + 1];
-        let Some(low_order) = order_map
-            .iter()
-            .filter(|(k, _)| !unknown_orders.contains(*k))
-            .map(|(_, v)| {
-                order_counts[*v] += 1;
-                possible_vals.insert(*v);
-                v
-            })
-            .min()
-        else {
-            return;
+        let selected_key = selected_row
+            .and_then(|row| self.row_data(row as usize))
+            .map(|row| row.name);
+        let mut placed = place_by_order(self, order_map, unknown_orders);
+        if let Err(err) = placed {
+            warn!("{err}, load order entries will be renormalized");
+            placed = match renormalize_order_entries(unknown_orders, ui.as_weak()) {
+                Some(new_map) => place_by_order(self, &new_map, unknown_orders),
+                None => Err(err),
+            };
+        }
+        let mut row_swapped = match placed {
+            Ok(Some(row_swapped)) => row_swapped,
+            Ok(None) => return,
+            Err(err) => {
+                error!("{err}, mods are displayed without load order placement");
+                false
+            }
         };
-        assert!(*low_order < 2);
-        let mut placement_rows = order_counts
-            .iter()
-            .enumerate()
-            .fold(
-                (vec![VecDeque::new(); possible_vals.len()], 0_usize),
-                |(mut placement_rows, mut counter), (i, &e)| {
-                    for _ in 0..e {
-                        placement_rows[i - low_order].push_back(counter);
-                        counter += 1;
-                    }
-                    (placement_rows, counter)
-                },
-            )
-            .0;
-        let (mut i, mut selected_i, mut no_order_count) = (0_usize, 0_usize, 0_usize);
-        let mut row_swapped = false;
-        let mut seen_names = HashSet::new();
-        while !unsorted_idx.is_empty() && no_order_count != unsorted_idx.len() {
-            if i >= unsorted_idx.len() {
-                i = 0
-            }
-            let unsorted_i = unsorted_idx[i];
-            let mut curr_row = self.row_data(unsorted_i).expect("unsorted_idx is valid ranges");
-            let curr_key = curr_row.dll_files.row_data(curr_row.order.i as usize);
-            let new_order: Option<&usize>;
-            if curr_key.is_some() && {
-                new_order = order_map.get(&curr_key.unwrap().to_string());
-                new_order
-            }
-            .is_some()
-            {
-                let placement_i = *new_order.unwrap() - *low_order;
-                let new_order = *new_order.unwrap() as i32;
-                if let Some(index) =
-                    placement_rows[placement_i].iter().position(|&x| x == unsorted_i)
-                {
-                    if let Some(ref key) = selected_key {
-                        if curr_row.name == key {
-                            selected_i = unsorted_i;
-                        }
-                    }
-                    if curr_row.order.at != new_order {
-                        curr_row.order.at = new_order;
-                        self.set_row_data(unsorted_i, curr_row);
-                    }
-                    match index {
-                        0 => placement_rows[placement_i].pop_front(),
-                        i if i == placement_rows[placement_i].len() - 1 => {
-                            placement_rows[placement_i].pop_back()
-                        }
-                        _ => placement_rows[placement_i].remove(index),
-                    };
-                    unsorted_idx.swap_remove(i);
-                    continue;
-                }
-                let swap_i = placement_rows[placement_i]
-                    .pop_front()
-                    .expect("placement_rows can not be empty if unsorted_idx is not empty");
-                let swap_row = self.row_data(swap_i).expect("placement rows contains valid rows");
-                if let Some(ref key) = selected_key {
-                    if swap_row.name == key {
-                        selected_i = unsorted_i;
-                    } else if curr_row.name == key {
-                        selected_i = swap_i;
-                    }
-                }
+        // placement only groups rows by value, rows are then arranged by `cmp_display_rows` so
+        // the on screen order matches `deserialize_collected_mods` and pinned mods stay on top
+        let (rows_moved, key_row) = sort_display_rows(self, selected_key.as_deref());
+        row_swapped |= rows_moved;
+        if let (Some(_), Some(row)) = (selected_row, key_row) {
+            ui.invoke_update_mod_index(row as i32, 1);
+        }
+        if row_swapped {
+            ui.invoke_redraw_checkboxes();
+        }
+        ui.global::<MainLogic>().invoke_redraw_order_elements();
+    }
+}
+
+/// moves the rows of `model` that have a load order to the rows calculated by `order_placement`  
+/// returns `Ok(None)` if there is no load order to place, otherwise if any rows were swapped  
+/// errors if `order_map` is out of sync with `model`, in this case `model` can be partially placed
+fn place_by_order(
+    model: &ModelRc<DisplayMod>,
+    order_map: &OrderMap,
+    unknown_orders: &HashSet<String>,
+) -> std::io::Result<Option<bool>> {
+    let Some(OrderPlacement {
+        low_order,
+        rows: mut placement_rows,
+    }) = order_placement(order_map, unknown_orders, model.row_count())?
+    else {
+        return Ok(None);
+    };
+    let out_of_sync = || {
+        std::io::Error::new(
+            ErrorKind::InvalidData,
+            "Load order entries are out of sync with registered mods",
+        )
+    };
+    let mut unsorted_idx = (0..model.row_count()).collect::<Vec<_>>();
+    let (mut i, mut no_order_count) = (0_usize, 0_usize);
+    let mut row_swapped = false;
+    let mut seen_names = HashSet::new();
+    while !unsorted_idx.is_empty() && no_order_count != unsorted_idx.len() {
+        if i >= unsorted_idx.len() {
+            i = 0
+        }
+        let unsorted_i = unsorted_idx[i];
+        let mut curr_row = model.row_data(unsorted_i).ok_or_else(out_of_sync)?;
+        let new_order = usize::try_from(curr_row.order.i)
+            .ok()
+            .and_then(|order_i| curr_row.dll_files.row_data(order_i))
+            .and_then(|dll| order_map.get(dll.as_str()).copied());
+        if let Some(new_order) = new_order {
+            let placement = new_order
+                .checked_sub(low_order)
+                .and_then(|placement_i| placement_rows.get_mut(placement_i))
+                .ok_or_else(out_of_sync)?;
+            let new_order = new_order as i32;
+            if let Some(index) = placement.iter().position(|&x| x == unsorted_i) {
                 if curr_row.order.at != new_order {
                     curr_row.order.at = new_order;
+                    model.set_row_data(unsorted_i, curr_row);
                 }
-                self.set_row_data(swap_i, curr_row);
-                self.set_row_data(unsorted_i, swap_row);
-                let found_i = unsorted_idx.iter().position(|x| *x == swap_i).expect(
-                    "unsorted_idx & placement_rows contain the same entries and are kept in sync",
-                );
-                row_swapped = true;
-                unsorted_idx.swap_remove(found_i);
+                placement.remove(index);
+                unsorted_idx.swap_remove(i);
                 continue;
             }
-            if let Some(ref key) = selected_key {
-                if curr_row.name == key {
-                    selected_i = unsorted_i;
-                }
-            }
-            if !seen_names.contains(&curr_row.name) {
-                seen_names.insert(curr_row.name.clone());
-                no_order_count += 1;
-            }
-            i += 1;
+            let swap_i = placement.pop_front().ok_or_else(out_of_sync)?;
+            let swap_row = model.row_data(swap_i).ok_or_else(out_of_sync)?;
+            let found_i = unsorted_idx
+                .iter()
+                .position(|x| *x == swap_i)
+                .ok_or_else(out_of_sync)?;
+            curr_row.order.at = new_order;
+            model.set_row_data(swap_i, curr_row);
+            model.set_row_data(unsorted_i, swap_row);
+            row_swapped = true;
+            unsorted_idx.swap_remove(found_i);
+            continue;
         }
-        if selected_row.is_some() {
-            ui.invoke_update_mod_index(selected_i as i32, 1);
+        if seen_names.insert(curr_row.name) {
+            no_order_count += 1;
         }
-        if row_swapped {
-            ui.invoke_redraw_checkboxes();
+        i += 1;
+    }
+    Ok(Some(row_swapped))
+}
+
+/// rewrites the load order entries saved on file so values form a contiguous range  
+/// returns the updated `OrderMap`, `None` if the changes could not be saved
+#[instrument(level = "trace", skip_all)]
+fn renormalize_order_entries(
+    unknown_orders: &HashSet<String>,
+    ui_handle: slint::Weak<App>,
+) -> Option<OrderMap> {
+    let ui = ui_handle.unwrap();
+    let mut loader = match ModLoaderCfg::read(get_loader_ini_dir()) {
+        Ok(data) => data,
+        Err(err) => {
+            error!("{err}");
+            return None;
         }
-        ui.global::<MainLogic>().invoke_redraw_order_elements();
+    };
+    let ord_meta_data = loader.update_order_entries(None, unknown_orders);
+    if let Err(err) = loader.write_to_file() {
+        error!("{err}");
+        return None;
     }
+    ui.global::<MainLogic>()
+        .set_max_order(MaxOrder::from(ord_meta_data.max_order));
+    info!("Renormalized load order entries in: {}", LOADER_FILES[3]);
+    Some(loader.parse_into_map())
 }
 
 enum Buttons {
@@ -1484,6 +2696,115 @@ impl App {
         self.invoke_show_confirm_popup();
     }
 
+    /// returns `true` if the game directory can still be reached, if not `game_path_valid` is set  
+    /// to false and the user is informed, the message is only shown the first time it is found missing
+    fn ensure_game_dir_available(&self) -> bool {
+        if game_dir_available() {
+            return true;
+        }
+        let game_dir = get_or_update_game_dir(None);
+        warn!("Game directory: '{}', is no longer accessible", game_dir.display());
+        if self.global::<MainLogic>().get_game_path_valid() {
+            self.global::<MainLogic>().set_game_path_valid(false);
+            self.display_msg(&format!(
+                "Game directory: '{}', is no longer accessible\n\n\
+                Reconnect the drive and press \"Re-check\" in settings",
+                game_dir.display()
+            ));
+        }
+        false
+    }
+
+    /// returns `true` if Elden Ring is not running, otherwise the user is told to close the game first
+    fn ensure_game_closed(&self) -> bool {
+        match check_game_closed() {
+            Ok(()) => true,
+            Err(err) => {
+                info!("{err}");
+                self.display_msg(&err.to_string());
+                false
+            }
+        }
+    }
+
+    /// validates the paths dropped onto the window, if a mod name has been entered they are added as a new mod  
+    /// otherwise they are kept until "Select Files" is pressed
+    fn handle_dropped_paths(&self) {
+        let mut dropped = DROPPED_PATHS.lock().unwrap();
+        let reject = if !self.global::<SettingsLogic>().get_loader_installed()
+            || self.global::<MainLogic>().get_installing()
+        {
+            Some(String::from("Mods can not be added right now"))
+        } else if let Some(file) = dropped.iter().find(|path| is_restricted_file(path)) {
+            Some(format!("Tried to add a restricted file: '{}'", file.display()))
+        } else if dropped.iter().filter(|path| path.is_dir()).count() > 1 {
+            Some(String::from("Only one folder can be dropped at a time"))
+        } else {
+            None
+        };
+        if let Some(msg) = reject {
+            dropped.clear();
+            drop(dropped);
+            info!("{msg}");
+            self.display_msg(&msg);
+            return;
+        }
+        let count = dropped.len();
+        drop(dropped);
+        trace!(count, "paths dropped");
+
+        self.global::<MainLogic>().set_current_subpage(0);
+        let mod_name = self.global::<MainLogic>().get_line_edit_text();
+        if mod_name.trim().is_empty() {
+            self.display_msg(&format!(
+                "Dropped {count} item(s)\n\nEnter a name for the new mod and press \"Select Files\" to add them"
+            ));
+            return;
+        }
+        self.global::<MainLogic>().invoke_select_mod_files(mod_name);
+    }
+
+    /// sets every registered mod to `state` without changing the state of the mod loader  
+    /// all mods are re-loaded from file afterwards so the ui matches the files on disk
+    fn set_all_mods_state(&self, state: bool) {
+        if !self.ensure_game_dir_available() || !self.ensure_game_closed() {
+            return;
+        }
+        let Some(toggle_guard) = ToggleGuard::acquire() else {
+            trace!("a toggle is already in progress");
+            return;
+        };
+        let mut ini = match Cfg::read(get_ini_dir()) {
+            Ok(ini_data) => ini_data,
+            Err(err) => {
+                self.display_and_log_err(err);
+                return;
+            }
+        };
+        let game_dir = get_or_update_game_dir(None).clone();
+        let collection = ini.collect_mods(&game_dir, None, false);
+        if let Some(warning) = collection.warnings {
+            warn!("{warning}");
+        }
+        let mut reg_mods = collection.mods;
+        let ui_handle = self.as_weak();
+        slint::spawn_local(async move {
+            let (job_dir, ini_path) = (game_dir.clone(), ini.path().to_path_buf());
+            let result = off_ui_thread(move || {
+                toggle_all_mods(&job_dir, state, &mut reg_mods, Some(ini_path.as_path()))
+            })
+            .await;
+            drop(toggle_guard);
+            let ui = ui_handle.unwrap();
+            match result {
+                Ok(count) => ui.display_msg(&format!("{count} mod(s) {}", DisplayState(state))),
+                Err(err) => ui.display_and_log_err(err),
+            }
+            reset_app_state(&mut ini, &game_dir, None, None, ui.as_weak());
+        })
+        .unwrap();
+    }
+
     fn display_and_log_err(&self, err: std::io::Error) {
         let err_str = err.to_string();
         error!("{err_str}");
@@ -1503,14 +2824,22 @@ impl From<(usize, bool)> for MaxOrder {
 
 impl From<&RegMod> for LoadOrder {
     fn from(value: &RegMod) -> Self {
+        let order_i = value.validated_order_index();
+        if value.order.set && order_i.is_none() {
+            warn!(
+                "{} load order index is out of range, displayed as unset",
+                DisplayName(&value.name)
+            );
+        }
+        let set = value.order.set && order_i.is_some();
         LoadOrder {
-            at: if !value.order.set { 0 } else { value.order.at as i32 },
-            i: if !value.order.set && value.files.dll.len() != 1 {
-                -1
-            } else {
-                value.order.i as i32
+            at: if !set { 0 } else { value.order.at as i32 },
+            i: match order_i {
+                Some(i) if set || value.files.dll.len() == 1 => i as i32,
+                _ if value.files.dll.len() == 1 => 0,
+                _ => -1,
             },
-            set: value.order.set,
+            set,
         }
     }
 }
@@ -1531,6 +2860,23 @@ async fn receive_msg() -> Message {
     Message::Esc
 }
 
+/// version of the game installed in `game_dir`, empty if `None` or the version could not be read
+fn game_version(game_dir: Option<&Path>) -> SharedString {
+    let Some(game_dir) = game_dir else {
+        return SharedString::new();
+    };
+    match read_game_version(game_dir) {
+        Ok(version) => {
+            info!("Game version: {version}");
+            version.into()
+        }
+        Err(err) => {
+            warn!("Could not read game version. {err}");
+            SharedString::new()
+        }
+    }
+}
+
 /// workaround for whatever bug in rfd that doesn't interact well with the app when a user  
 /// performs a secondary action within the file dialog
 fn rfd_hang_workaround(window: &slint::Window) {
@@ -1557,6 +2903,24 @@ fn get_user_folder(path: &Path, ui_window: &slint::Window) -> std::io::Result<Pa
     f_result
 }
 
+/// opens a file dialog filtered to plaintext load order files
+fn get_load_order_file(path: &Path, ui_window: &slint::Window) -> std::io::Result<PathBuf> {
+    let f_result = match rfd::FileDialog::new()
+        .set_directory(path)
+        .set_parent(&ui_window.window_handle())
+        .add_filter("Load order", &["txt"])
+        .pick_file()
+    {
+        Some(file) => {
+            trace!("User Selected File: \"{}\"", file.display());
+            Ok(file)
+        }
+        None => new_io_error!(ErrorKind::InvalidInput, "No File Selected"),
+    };
+    rfd_hang_workaround(ui_window);
+    f_result
+}
+
 fn get_user_files(path: &Path, ui_window: &slint::Window) -> std::io::Result<Vec<PathBuf>> {
     let f_result = match rfd::FileDialog::new()
         .set_directory(path)
@@ -1564,11 +2928,11 @@ fn get_user_files(path: &Path, ui_window: &slint::Window) -> std::io::Result<Vec
         .pick_files()
     {
         Some(files) => {
-            if files
-                .iter()
-                .any(|file| RESTRICTED_FILES.contains(file.file_name().expect("has valid name")))
-            {
-                new_io_error!(ErrorKind::InvalidData, "Tried to add a restricted file")
+            if let Some(file) = files.iter().find(|file| is_restricted_file(file)) {
+                new_io_error!(
+                    ErrorKind::InvalidData,
+                    format!("Tried to add a restricted file: '{}'", file.display())
+                )
             } else {
                 trace!("User Selected Files: {files:?}");
                 Ok(files)
@@ -1580,76 +2944,159 @@ fn get_user_files(path: &Path, ui_window: &slint::Window) -> std::io::Result<Vec
     f_result
 }
 
+/// takes the paths dropped onto the window that are waiting to be added as a new mod
+#[inline]
+fn take_dropped_paths() -> Vec<PathBuf> {
+    std::mem::take(&mut *DROPPED_PATHS.lock().unwrap())
+}
+
+/// held for the duration of an add mod flow, repeated requests are ignored until it is dropped
+struct AddModGuard;
+
+impl AddModGuard {
+    /// returns `None` if an add mod flow is already in progress
+    fn acquire() -> Option<Self> {
+        (!ADDING_MOD.swap(true, Ordering::AcqRel)).then_some(AddModGuard)
+    }
+}
+
+impl Drop for AddModGuard {
+    fn drop(&mut self) {
+        ADDING_MOD.store(false, Ordering::Release);
+    }
+}
+
+/// held while mod files are renamed on another thread, toggles are ignored until it is dropped
+struct ToggleGuard;
+
+impl ToggleGuard {
+    /// returns `None` if a toggle is already in progress
+    fn acquire() -> Option<Self> {
+        (!TOGGLING.swap(true, Ordering::AcqRel)).then_some(ToggleGuard)
+    }
+}
+
+impl Drop for ToggleGuard {
+    fn drop(&mut self) {
+        TOGGLING.store(false, Ordering::Release);
+    }
+}
+
+/// runs `job` on a new thread so renames that are retried with a delay while a file is locked by  
+/// another process, see `rename_with_retry`, never block the ui
+async fn off_ui_thread<T, F>(job: F) -> std::io::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> std::io::Result<T> + Send + 'static,
+{
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(job());
+    });
+    receiver.await.unwrap_or_else(|_| {
+        new_io_error!(ErrorKind::BrokenPipe, "Worker thread exited before reporting a result")
+    })
+}
+
 #[inline]
 fn get_ini_dir() -> &'static PathBuf {
     static CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
     CONFIG_PATH.get_or_init(|| {
         let exe_dir = std::env::current_dir().expect("Failed to get current dir");
-        exe_dir.join(INI_NAME)
+        ini_cfg_path(&exe_dir)
     })
 }
 
 #[inline]
 fn get_loader_ini_dir() -> &'static PathBuf {
     static LOADER_CONFIG_PATH: OnceLock<PathBuf> = OnceLock::new();
-    LOADER_CONFIG_PATH.get_or_init(|| get_or_update_game_dir(None).join(LOADER_FILES[3]))
+    LOADER_CONFIG_PATH.get_or_init(|| loader_cfg_path(&get_or_update_game_dir(None)))
+}
+
+/// reads the saved `ConfirmLevel` at the start of a confirm flow so changes to the ini apply right away
+fn get_confirm_level() -> ConfirmLevel {
+    Cfg::read(get_ini_dir())
+        .and_then(|ini| ini.get_confirm_level())
+        .unwrap_or_else(|err| {
+            error!("{err}");
+            DEFAULT_CONFIRM_LEVEL
+        })
+}
+
+/// cheap check that the saved game directory still exists, eg. a removable drive was disconnected
+#[inline]
+fn game_dir_available() -> bool {
+    matches!(get_or_update_game_dir(None).try_exists(), Ok(true))
 }
 
-fn get_or_update_game_dir(
-    update: Option<PathBuf>,
-) -> tokio::sync::RwLockReadGuard<'static, PathBuf> {
-    static GAME_DIR: OnceLock<RwLock<PathBuf>> = OnceLock::new();
+/// async tasks that need the game directory across an `.await` must clone it out of the returned guard
+fn get_or_update_game_dir(update: Option<PathBuf>) -> RwLockReadGuard<'static, PathBuf> {
+    static GAME_DIR: OnceLock<UiRwLock<PathBuf>> = OnceLock::new();
 
     if let Some(path) = update {
-        let gd = GAME_DIR.get_or_init(|| RwLock::new(PathBuf::new()));
-        let mut gd_lock = gd.blocking_write();
+        let gd = GAME_DIR.get_or_init(|| UiRwLock::new("GAME_DIR", PathBuf::new()));
+        let mut gd_lock = gd.write();
         *gd_lock = path;
     }
 
-    GAME_DIR.get().unwrap().blocking_read()
+    GAME_DIR.get().unwrap().read()
 }
 
 #[inline]
-fn get_mut_unknown_orders() -> tokio::sync::RwLockWriteGuard<'static, HashSet<String>> {
+fn get_mut_unknown_orders() -> RwLockWriteGuard<'static, HashSet<String>> {
     UNKNOWN_ORDER_KEYS
-        .get_or_init(|| RwLock::new(HashSet::new()))
-        .blocking_write()
+        .get_or_init(|| UiRwLock::new("UNKNOWN_ORDER_KEYS", HashSet::new()))
+        .write()
 }
 
 #[inline]
-fn get_unknown_orders() -> tokio::sync::RwLockReadGuard<'static, HashSet<String>> {
+fn get_unknown_orders() -> RwLockReadGuard<'static, HashSet<String>> {
     UNKNOWN_ORDER_KEYS
-        .get_or_init(|| RwLock::new(HashSet::new()))
-        .blocking_read()
-}
-
-#[inline]
-fn populate_restricted_files() -> HashSet<&'static OsStr> {
-    LOADER_FILES
-        .iter()
-        .chain(REQUIRED_GAME_FILES.iter())
-        .map(OsStr::new)
-        .collect()
+        .get_or_init(|| UiRwLock::new("UNKNOWN_ORDER_KEYS", HashSet::new()))
+        .read()
 }
 
 #[instrument(level = "trace", skip(ui_handle))]
 fn open_text_files(ui_handle: slint::Weak<App>, files: Vec<PathBuf>) {
     let ui = ui_handle.unwrap();
+    let editor = Cfg::read(get_ini_dir())
+        .map(|ini| ini.get_editor())
+        .unwrap_or_else(|err| {
+            error!("{err}");
+            DEFAULT_EDITOR.to_string()
+        });
+    let mut fallback_shown = false;
     for file in files {
         let file_clone = file.clone();
-        let jh =
-            std::thread::spawn(move || std::process::Command::new("notepad").arg(&file).spawn());
+        let editor_clone = editor.clone();
+        let jh = std::thread::spawn(move || {
+            match std::process::Command::new(&editor_clone).arg(&file).spawn() {
+                Err(err) if err.kind() == ErrorKind::NotFound && editor_clone != DEFAULT_EDITOR => {
+                    warn!("Editor: '{editor_clone}', not found, using: {DEFAULT_EDITOR}");
+                    (
+                        std::process::Command::new(DEFAULT_EDITOR).arg(&file).spawn(),
+                        true,
+                    )
+                }
+                result => (result, false),
+            }
+        });
         match jh.join() {
-            Ok(result) => match result {
-                Ok(_) => (),
-                Err(err) => {
+            Ok((result, used_fallback)) => {
+                if used_fallback && !fallback_shown {
+                    fallback_shown = true;
+                    ui.display_msg(&format!(
+                        "Configured editor: '{editor}', was not found, opened with: {DEFAULT_EDITOR}"
+                    ));
+                }
+                if let Err(err) = result {
                     error!("{err}");
                     ui.display_msg(&format!(
                         "Failed to open config file: '{}'\n\nError: {err}",
                         file_clone.display()
                     ));
                 }
-            },
+            }
             Err(err) => {
                 error!(?err, "Thread panicked!");
                 ui.display_msg(&format!("{err:?}"));
@@ -1674,6 +3121,13 @@ fn order_data_or_default(
 
     match ModLoaderCfg::read(path) {
         Ok(mut data) => {
+            let suspended = data.order_suspended();
+            ui.global::<SettingsLogic>().set_order_suspended(suspended);
+            if suspended {
+                // stashed values are only displayed, they are restored unchanged on resume
+                trace!("load order is suspended");
+                return data.suspended_order_map();
+            }
             let mut _guard_unknown_orders = None;
             let unknown_orders = unknown_orders.unwrap_or_else(|| {
                 _guard_unknown_orders = Some(get_unknown_orders());
@@ -1691,16 +3145,170 @@ fn order_data_or_default(
     }
 }
 
+/// loader state and registered mods read once a valid game directory is found
+struct FoundGame {
+    mod_loader: Box<dyn LoaderBackend>,
+    mod_loader_cfg: Box<dyn OrderStore>,
+    order_data: Option<OrderMap>,
+    ord_meta_data: Option<OrdMetaData>,
+    collected_mods: CollectedMods,
+}
+
+/// reads the mod loader installed in `game_dir` and collects all mods registered in `ini`  
+/// used on startup and when game detection is retried, errors are logged and messages that should  
+/// be shown to the user are pushed to `dsp_msgs`
+fn setup_found_game(ini: &mut Cfg, game_dir: &Path, dsp_msgs: &mut Vec<String>) -> FoundGame {
+    let mut mod_loader_cfg: Box<dyn OrderStore>;
+    let mut order_data = None;
+    let mut ord_meta_data = None;
+    let mod_loader = loader_backend(game_dir).unwrap_or_else(|err| {
+        error!(err_code = 3, "{err}");
+        dsp_msgs.push(err.to_string());
+        Box::new(DinputLoader::default())
+    });
+    if mod_loader.installed() {
+        info!(dll_hook = %DisplayState(!mod_loader.disabled()), "elden_mod_loader files found");
+        mod_loader_cfg = mod_loader.read_order().unwrap_or_else(|err| {
+            error!(err_code = 4, "{err}");
+            dsp_msgs.push(err.to_string());
+            mod_loader.default_order()
+        });
+        let (dlls, order_count, update_loader) =
+            ini.dll_set_order_count(mod_loader_cfg.order_section());
+        if update_loader {
+            mod_loader_cfg.write_order().unwrap_or_else(|err| {
+                error!(err_code = 5, "{err}");
+                dsp_msgs.push(err.to_string());
+            });
+        }
+        if let Err(key_err) = mod_loader_cfg.verify_keys(&dlls, order_count) {
+            match key_err.err {
+                EmlError::UnknownOrderKeys { order_changed: true, .. } => {
+                    ini.update().unwrap_or_else(|err| {
+                        error!(err_code = 6, "{err}");
+                    });
+                    ord_meta_data = key_err.update_ord_data;
+                    warn!("{}", key_err.err);
+                }
+                EmlError::UnknownOrderKeys { .. } => info!("{}", key_err.err),
+                _ => error!(err_code = 7, "{}", key_err.err),
+            }
+            if let Some(unknown_keys) = key_err.unknown_keys {
+                *get_mut_unknown_orders() = unknown_keys;
+            }
+            dsp_msgs.push(key_err.err.to_string());
+        }
+        order_data = if mod_loader_cfg.order_suspended() {
+            // stashed values are only displayed, they are restored unchanged on resume
+            Some(mod_loader_cfg.suspended_order_map())
+        } else {
+            mod_loader_cfg
+                .parse_order(&get_unknown_orders())
+                .map(Some)
+                .unwrap_or_else(|err| {
+                    error!(err_code = 8, "{err}");
+                    dsp_msgs.push(err.to_string());
+                    None
+                })
+        };
+    } else {
+        mod_loader_cfg = mod_loader.default_order();
+    }
+    info!("{}", DisplayAntiCheatFound(mod_loader.anti_cheat_toggle_installed()));
+    let collected_mods = {
+        let mut collection = ini.collect_mods(game_dir, order_data.as_ref(), false);
+        if collection.mods.len() != ini.mods_registered() {
+            ini.update().unwrap_or_else(|err| {
+                error!(err_code = 9, "{err}");
+            });
+        }
+        if let Some(warning) = collection.warnings.take() {
+            dsp_msgs.push(warning.to_string());
+        }
+        info!("Found {} mod(s) registered in: {}", collection.mods.len(), INI_NAME);
+        if ord_meta_data.is_none() {
+            ord_meta_data = Some(OrdMetaData::with_ord(collection.mods.max_order()));
+        }
+        collection
+    };
+    FoundGame {
+        mod_loader,
+        mod_loader_cfg,
+        order_data,
+        ord_meta_data,
+        collected_mods,
+    }
+}
+
+/// populates the ui with the mods and loader settings of `found`, see `setup_found_game`
+fn apply_found_game(
+    ui: &App,
+    ini: &Cfg,
+    found: &FoundGame,
+    suppress_eac_warning: bool,
+    dsp_msgs: &mut Vec<String>,
+) {
+    if let Some(ref meta_data) = found.ord_meta_data {
+        ui.global::<MainLogic>()
+            .set_max_order(MaxOrder::from(meta_data.max_order));
+        if let Some(ref vals) = meta_data.missing_vals {
+            let msg = DisplayMissingOrd(vals).to_string();
+            info!("{msg}");
+            dsp_msgs.push(msg);
+        }
+    }
+    deserialize_collected_mods(&found.collected_mods, ini, ui.as_weak());
+    ui.global::<SettingsLogic>()
+        .set_loader_disabled(found.mod_loader.disabled());
+    if !found.mod_loader.installed() {
+        return;
+    }
+    ui.global::<SettingsLogic>().set_loader_installed(true);
+    ui.global::<SettingsLogic>()
+        .set_order_suspended(found.mod_loader_cfg.order_suspended());
+    let delay = found.mod_loader_cfg.load_delay().unwrap_or_else(|err| {
+        // parse error ErrorKind::InvalidData
+        error!(err_code = 12, "{err}");
+        dsp_msgs.push(err.to_string());
+        DEFAULT_LOADER_VALUES[0].parse().unwrap()
+    });
+    let show_terminal = found.mod_loader_cfg.show_terminal().unwrap_or_else(|err| {
+        // parse error ErrorKind::InvalidData
+        error!(err_code = 13, "{err}");
+        dsp_msgs.push(err.to_string());
+        false
+    });
+    ui.global::<SettingsLogic>()
+        .set_load_delay(SharedString::from(format!("{delay}ms")));
+    ui.global::<SettingsLogic>().set_show_terminal(show_terminal);
+    if found.mod_loader.anti_cheat_enabled() && !suppress_eac_warning {
+        dsp_msgs.push(DisplayAntiCheatMsg.to_string());
+    }
+}
+
 /// forces all data to be re-read from file, it is fine to pass in a `Cfg::default()` here  
 /// **Note:** call to find unknown_orders is blocking, so you must give a ref to unknown_orders  
 /// if you currently have access to the global set
-#[instrument(level = "trace", skip_all)]
 fn reset_app_state(
     cfg: &mut Cfg,
     game_dir: &Path,
     loader_dir: Option<&Path>,
     unknown_orders: Option<&HashSet<String>>,
     ui_handle: slint::Weak<App>,
+) {
+    reload_app_state(cfg, game_dir, loader_dir, unknown_orders, false, ui_handle);
+}
+
+/// same as `reset_app_state`, with `skip_validation` mods whose files can not be found stay  
+/// registered, use when the files may only be missing from the current game directory
+#[instrument(level = "trace", skip(cfg, game_dir, loader_dir, unknown_orders, ui_handle))]
+fn reload_app_state(
+    cfg: &mut Cfg,
+    game_dir: &Path,
+    loader_dir: Option<&Path>,
+    unknown_orders: Option<&HashSet<String>>,
+    skip_validation: bool,
+    ui_handle: slint::Weak<App>,
 ) {
     let ui = ui_handle.unwrap();
     ui.global::<MainLogic>().set_current_subpage(0);
@@ -1711,23 +3319,206 @@ fn reset_app_state(
         cfg.empty_contents();
     });
     let order_data = order_data_or_default(ui.as_weak(), loader_dir, unknown_orders);
-    let collected_mods = cfg.collect_mods(game_dir, Some(&order_data), false);
+    let collected_mods = cfg.collect_mods(game_dir, Some(&order_data), skip_validation);
     ui.global::<MainLogic>()
         .set_max_order(MaxOrder::from(collected_mods.mods.max_order()));
-    deserialize_collected_mods(&collected_mods, ui.as_weak());
+    deserialize_collected_mods(&collected_mods, cfg, ui.as_weak());
     info!("reloaded state from file");
 }
 
+/// validates `try_path` then saves it as "game_dir" and updates the front end to match  
+/// `try_path` is also moved to the front of the recent game directories
+fn apply_game_dir(mut ini: Cfg, try_path: PathBuf, ui_handle: slint::Weak<App>) {
+    let ui = ui_handle.unwrap();
+    let missing_files = match ini.relocate_game_dir(&try_path) {
+        Ok(missing_files) => missing_files,
+        Err(err) if err.kind() == ErrorKind::NotFound => {
+            warn!("{err}");
+            ui.display_msg(&format!(
+                "Could not find Elden Ring in:\n\"{}\"",
+                try_path.display()
+            ));
+            return;
+        }
+        Err(err) if err.kind() == ErrorKind::Unsupported => {
+            warn!("{err}");
+            ui.display_msg(&err.to_string());
+            return;
+        }
+        Err(err) => {
+            error!("Failed to save directory. {err}");
+            ui.display_msg(&err.to_string());
+            return;
+        }
+    };
+
+    if let Err(err) = ini.add_recent_game_dir(&try_path) {
+        error!("Failed to save recent game directory. {err}");
+    }
+    ui.global::<SettingsLogic>()
+        .set_recent_game_dirs(recent_game_dirs_model(&ini));
+
+    let span_clone = tracing::Span::current();
+    slint::spawn_local(async move {
+        let _guard = span_clone.enter();
+        let mod_loader = loader_backend(&try_path)
+            .unwrap_or_else(|_| Box::new(DinputLoader::default()));
+        ui.global::<SettingsLogic>()
+            .set_game_path(try_path.to_string_lossy().to_string().into());
+        ui.global::<SettingsLogic>()
+            .set_game_version(game_version(Some(&try_path)));
+        ui.global::<MainLogic>().set_game_path_valid(true);
+        ui.global::<MainLogic>().set_current_subpage(0);
+        ui.global::<SettingsLogic>()
+            .set_loader_installed(mod_loader.installed());
+        ui.global::<SettingsLogic>()
+            .set_loader_disabled(mod_loader.disabled());
+        if !ini.mods_is_empty() {
+            let loader_dir = mod_loader.installed().then(|| mod_loader.config_path());
+            // switching between installs must not de-register mods that are only installed in the other
+            reload_app_state(&mut ini, &try_path, loader_dir, None, true, ui.as_weak());
+            if !missing_files.is_empty() {
+                let names = missing_files
+                    .iter()
+                    .map(|(name, _)| DisplayName(name).to_string())
+                    .collect::<Vec<_>>();
+                warn!("Files not found after moving game directory for: {names:?}");
+                ui.display_confirm(
+                    &format!(
+                        "Game directory updated, some registered files could not be found \
+                        for: {}\n\nWould you like to remove the missing files from these mods?\n\
+                        Select No to keep them as they are and run diagnostics for more details",
+                        DisplayVec(&names)
+                    ),
+                    Buttons::YesNo,
+                );
+                // missing files are only removed from the registrations once the user confirms
+                if receive_msg().await == Message::Confirm {
+                    reset_app_state(&mut ini, &try_path, loader_dir, None, ui.as_weak());
+                }
+            }
+        }
+        if mod_loader.installed() {
+            ui.display_msg(&format!(
+                "Game Files Found!\n\
+                {TUTORIAL_MSG}"
+            ));
+            let _ = receive_msg().await;
+            if ini.mods_is_empty() {
+                if let Err(err) =
+                    confirm_scan_mods(ui.as_weak(), &try_path, Some(&ini), None).await
+                {
+                    error!("{err}");
+                    ui.display_msg(&err.to_string());
+                };
+            }
+        } else {
+            ui.display_msg(&format!(
+                "Game Files Found!\n\n\
+                {TECHIE_W_MSG}"
+            ))
+        }
+        start_file_watcher(&ini, &try_path, ui.as_weak());
+        drop(get_or_update_game_dir(Some(try_path)));
+    })
+    .unwrap();
+}
+
+/// the recent game directories saved in `ini` in the format the front end expects
+fn recent_game_dirs_model(ini: &Cfg) -> ModelRc<SharedString> {
+    let recent_dirs: Rc<VecModel<SharedString>> = Default::default();
+    recent_dirs.extend(
+        ini.recent_game_dirs()
+            .iter()
+            .map(|dir| SharedString::from(dir.to_string_lossy().as_ref())),
+    );
+    ModelRc::from(recent_dirs)
+}
+
+/// compares the current config files with the backups `restore_backup` would restore  
+/// neither file is modified, a config without a backup contributes no changes
+fn backup_diff() -> std::io::Result<ConfigDiff> {
+    let ini_dir = get_ini_dir();
+    let ini_backup = backup_path(ini_dir);
+    let mut diff = if matches!(ini_backup.try_exists(), Ok(true)) {
+        Cfg::read_snapshot(ini_dir)?.diff_against(&Cfg::read_snapshot(&ini_backup)?)
+    } else {
+        ConfigDiff::default()
+    };
+    let loader_dir = get_loader_ini_dir();
+    let loader_backup = backup_path(loader_dir);
+    if matches!(loader_backup.try_exists(), Ok(true)) {
+        diff.compare_orders(&get_cfg(loader_dir)?, &get_cfg(&loader_backup)?);
+    }
+    Ok(diff)
+}
+
+/// records the hashes of the files of `reg_mod` if the user has enabled "hash_files"
+fn record_hashes_if_enabled(ini: &Cfg, reg_mod: &mut RegMod, game_dir: &Path) {
+    if !ini.get_hash_files() {
+        return;
+    }
+    if let Err(err) = reg_mod.record_hashes(game_dir, ini.path()) {
+        error!("Failed to record hashes for: {}, {err}", DisplayName(&reg_mod.name));
+    }
+}
+
+/// starts watching the ini and `game_dir\mods` if the user has enabled "watch_files"  
+/// all mods are reloaded from file once either is changed by something other than this app  
+/// replaces any watcher that was previously started
+fn start_file_watcher(ini: &Cfg, game_dir: &Path, ui_handle: slint::Weak<App>) {
+    let mut file_watcher = FILE_WATCHER.lock().unwrap();
+    if !ini.get_watch_files() {
+        *file_watcher = None;
+        return;
+    }
+    let mods_dir = game_dir.join("mods");
+    let on_change = move || {
+        let result = ui_handle.upgrade_in_event_loop(|ui| {
+            let span = info_span!("file_watcher");
+            let _guard = span.enter();
+
+            if ui.global::<MainLogic>().get_installing() {
+                info!("Files changed during install, skipped reload");
+                return;
+            }
+            reset_app_state(
+                &mut Cfg::default(get_ini_dir()),
+                &get_or_update_game_dir(None),
+                None,
+                None,
+                ui.as_weak(),
+            );
+        });
+        if let Err(err) = result {
+            error!("Failed to reload after files changed, {err}");
+        }
+    };
+    match watch_files(get_ini_dir(), &mods_dir, on_change) {
+        Ok(watcher) => {
+            *file_watcher = Some(watcher);
+            info!("Watching: {INI_NAME}, and: '{}', for changes", mods_dir.display());
+        }
+        Err(err) => {
+            *file_watcher = None;
+            error!("Failed to start file watcher: {err}");
+        }
+    }
+}
+
 type DeserializedFileData = (
     ModelRc<StandardListViewItem>,
+    ModelRc<i32>,
     ModelRc<SharedString>,
     ModelRc<SharedString>,
 );
 
 /// deserializes `SplitFiles` to `ModelRc<T>` where `T` is the type the front end expects  
-/// output is in the following order (`files`, `dll_files`, `config_files`)
+/// output is in the following order (`files`, `file_categories`, `dll_files`, `config_files`)  
+/// `file_categories` has one `FileCategory` for each entry in `files`
 fn deserialize_split_files(split_files: &SplitFiles) -> DeserializedFileData {
     let files: Rc<VecModel<StandardListViewItem>> = Default::default();
+    let file_categories: Rc<VecModel<i32>> = Default::default();
     let dll_files: Rc<VecModel<SharedString>> = Default::default();
     let config_files: Rc<VecModel<SharedString>> = Default::default();
     if !split_files.dll.is_empty() {
@@ -1765,8 +3556,10 @@ fn deserialize_split_files(split_files: &SplitFiles) -> DeserializedFileData {
                 .map(|f| SharedString::from(f.to_string_lossy().to_string()).into()),
         );
     }
+    file_categories.extend(split_files.chain_all().map(|f| FileCategory::from_path(f) as i32));
     (
         ModelRc::from(files),
+        ModelRc::from(file_categories),
         ModelRc::from(dll_files),
         ModelRc::from(config_files),
     )
@@ -1775,7 +3568,8 @@ fn deserialize_split_files(split_files: &SplitFiles) -> DeserializedFileData {
 fn deserialize_mod(mod_data: &RegMod) -> DisplayMod {
     const ELIDE_LEN: usize = 20;
 
-    let (files, dll_files, config_files) = deserialize_split_files(&mod_data.files);
+    let (files, file_categories, dll_files, config_files) =
+        deserialize_split_files(&mod_data.files);
     let name = mod_data.name.replace('_', " ");
     DisplayMod {
         // MARK: Workaround
@@ -1787,24 +3581,154 @@ fn deserialize_mod(mod_data: &RegMod) -> DisplayMod {
         }),
         name: SharedString::from(name),
         enabled: mod_data.state,
+        always_active: mod_data.is_always_active(),
         files,
+        file_categories,
+        size: SharedString::new(),
+        note: SharedString::new(),
+        pinned: false,
+        favorite: false,
+        category: SharedString::from(DEFAULT_CATEGORY),
+        collapsed: false,
         config_files,
+        toggle_configs: mod_data.toggle_configs,
         dll_files,
+        dll_states: deserialize_dll_states(&mod_data.files),
         order: LoadOrder::from(mod_data),
     }
 }
 
+/// `deserialize_mod` with the display only pin, favorite and category saved in `ini` applied
+fn deserialize_saved_mod(mod_data: &RegMod, ini: &Cfg) -> DisplayMod {
+    let mut display_mod = deserialize_mod(mod_data);
+    display_mod.pinned = ini.is_pinned(&mod_data.name);
+    display_mod.favorite = ini.is_favorite(&mod_data.name);
+    if let Some(category) = ini.get_category(&mod_data.name) {
+        display_mod.category = SharedString::from(category);
+    }
+    display_mod
+}
+
+/// rows are grouped by category, see `cmp_categories`, within each category pinned rows come  
+/// first sorted by name, the remaining rows follow the same order as `collect_mods`  
+/// rows with a set load order sorted by value then all other rows, ties are sorted by name
+fn cmp_display_rows(a: &DisplayMod, b: &DisplayMod) -> std::cmp::Ordering {
+    let order_val = |row: &DisplayMod| if row.order.set { row.order.at } else { i32::MAX };
+    cmp_categories(&a.category, &b.category)
+        .then_with(|| b.pinned.cmp(&a.pinned))
+        .then_with(|| {
+            if a.pinned {
+                std::cmp::Ordering::Equal
+            } else {
+                order_val(a).cmp(&order_val(b))
+            }
+        })
+        .then_with(|| cmp_mod_names(&a.name, &b.name))
+}
+
+/// sorts the rows of `model` with `cmp_display_rows`, only rows that changed are replaced  
+/// returns (`rows_moved`, the new row of the mod with `key`)
+fn sort_display_rows(model: &ModelRc<DisplayMod>, key: Option<&str>) -> (bool, Option<usize>) {
+    let mut rows = model.iter().collect::<Vec<_>>();
+    rows.sort_by(cmp_display_rows);
+    let (mut rows_moved, mut key_row) = (false, None);
+    for (row_i, row) in rows.into_iter().enumerate() {
+        if key.is_some_and(|key| row.name == key) {
+            key_row = Some(row_i);
+        }
+        if model
+            .row_data(row_i)
+            .is_some_and(|curr| {
+                curr.name != row.name || curr.pinned != row.pinned || curr.category != row.category
+            })
+        {
+            model.set_row_data(row_i, row);
+            rows_moved = true;
+        }
+    }
+    (rows_moved, key_row)
+}
+
+/// collapses or expands every row of `model` that is grouped under `category`
+fn set_category_collapsed(model: &ModelRc<DisplayMod>, category: &str, collapsed: bool) {
+    for (row, mut display_mod) in model.iter().enumerate() {
+        if display_mod.category == category && display_mod.collapsed != collapsed {
+            display_mod.collapsed = collapsed;
+            model.set_row_data(row, display_mod);
+        }
+    }
+}
+
+/// returns the first row after `row` in the direction of `step` that is not hidden by a collapsed  
+/// category, returns `row` if there is none
+fn next_visible_row(model: &ModelRc<DisplayMod>, row: i32, step: i32) -> i32 {
+    if step == 0 {
+        return row;
+    }
+    let mut next = row;
+    loop {
+        next += step.signum();
+        match usize::try_from(next).ok().and_then(|i| model.row_data(i)) {
+            Some(display_mod) if display_mod.collapsed => continue,
+            Some(_) => return next,
+            None => return row,
+        }
+    }
+}
+
+/// one state for each file in `split_files.dll`, in the same order as `dll_files`
+fn deserialize_dll_states(split_files: &SplitFiles) -> ModelRc<bool> {
+    let dll_states: Rc<VecModel<bool>> = Default::default();
+    dll_states.extend(split_files.dll.iter().map(FileData::is_enabled));
+    ModelRc::from(dll_states)
+}
+
+/// updates `enabled` and `dll_states` of the displayed mod that matches `reg_mod`
+fn update_displayed_state(reg_mod: &RegMod, ui_handle: slint::Weak<App>) {
+    let ui = ui_handle.unwrap();
+    let model = ui.global::<MainLogic>().get_current_mods();
+    let name = reg_mod.name.replace('_', " ");
+    let Some((row, mut display_mod)) = model.iter().enumerate().find(|(_, m)| m.name == name)
+    else {
+        return;
+    };
+    // `toggle_files` renames config files of mods with `toggle_configs` set
+    let (files, file_categories, _, config_files) = deserialize_split_files(&reg_mod.files);
+    display_mod.enabled = reg_mod.state;
+    display_mod.files = files;
+    display_mod.file_categories = file_categories;
+    display_mod.config_files = config_files;
+    display_mod.toggle_configs = reg_mod.toggle_configs;
+    display_mod.dll_states = deserialize_dll_states(&reg_mod.files);
+    model.set_row_data(row, display_mod);
+}
+
 #[instrument(level = "trace", skip_all)]
-fn deserialize_collected_mods(data: &CollectedMods, ui_handle: slint::Weak<App>) {
+fn deserialize_collected_mods(data: &CollectedMods, ini: &Cfg, ui_handle: slint::Weak<App>) {
     let ui = ui_handle.unwrap();
     if let Some(ref warning) = data.warnings {
         ui.display_msg(&warning.to_string());
     }
 
-    let display_mods: Rc<VecModel<DisplayMod>> = Default::default();
-    data.mods
+    // categories the user collapsed stay collapsed when the mod list is rebuilt
+    let collapsed = ui
+        .global::<MainLogic>()
+        .get_current_mods()
+        .iter()
+        .filter(|row| row.collapsed)
+        .map(|row| row.category)
+        .collect::<HashSet<_>>();
+    let mut rows = data
+        .mods
         .iter()
-        .for_each(|mod_data| display_mods.push(deserialize_mod(mod_data)));
+        .map(|mod_data| {
+            let mut display_mod = deserialize_saved_mod(mod_data, ini);
+            display_mod.collapsed = collapsed.contains(&display_mod.category);
+            display_mod
+        })
+        .collect::<Vec<_>>();
+    rows.sort_by(cmp_display_rows);
+    let display_mods: Rc<VecModel<DisplayMod>> = Rc::new(VecModel::from(rows));
 
     ui.global::<MainLogic>().set_current_mods(ModelRc::from(display_mods));
     ui.global::<MainLogic>()
@@ -1816,22 +3740,35 @@ fn deserialize_collected_mods(data: &CollectedMods, ui_handle: slint::Weak<App>)
 async fn install_new_mod(
     name: &str,
     files: Vec<PathBuf>,
+    dir: Option<PathBuf>,
     game_dir: &Path,
+    preview_limit: usize,
     ui_handle: slint::Weak<App>,
 ) -> std::io::Result<Vec<PathBuf>> {
     let ui = ui_handle.unwrap();
     let mod_name = name.trim();
-    ui.display_confirm(
-        &format!(
-            "Mod files are not installed in game directory.\nAttempt to install \"{mod_name}\"?"
-        ),
-        Buttons::YesNo,
-    );
-    if receive_msg().await != Message::Confirm {
-        return new_io_error!(ErrorKind::ConnectionAborted, "Mod install canceled");
+    if get_confirm_level().confirm_routine() {
+        ui.display_confirm(
+            &format!(
+                "Mod files are not installed in game directory.\nAttempt to install \"{mod_name}\"?"
+            ),
+            Buttons::YesNo,
+        );
+        if receive_msg().await != Message::Confirm {
+            return new_io_error!(ErrorKind::ConnectionAborted, "Mod install canceled");
+        }
+    }
+    ensure_mods_dir(game_dir)?;
+    let mut data = if files.is_empty() {
+        InstallData::empty(mod_name, game_dir)
+    } else {
+        InstallData::new(mod_name, files, game_dir)?
+    };
+    if let Some(dir) = dir {
+        data.update_fields_with_new_dir(&dir, utils::installer::DisplayItems::Limit(preview_limit))
+            .await?;
     }
-    let data = InstallData::new(mod_name, files, game_dir)?;
-    add_dir_to_install_data(data, ui_handle).await
+    add_dir_to_install_data(data, preview_limit, ui_handle).await
 }
 
 #[instrument(level = "trace", skip_all)]
@@ -1842,96 +3779,179 @@ async fn install_new_files_to_mod(
     ui_handle: slint::Weak<App>,
 ) -> std::io::Result<Vec<PathBuf>> {
     let ui = ui_handle.unwrap();
-    ui.display_confirm(
-        "Selected files are not installed? Would you like to try and install them?",
-        Buttons::YesNo,
-    );
-    if receive_msg().await != Message::Confirm {
-        return new_io_error!(
-            ErrorKind::ConnectionAborted,
-            "Did not select to install files"
+    if get_confirm_level().confirm_routine() {
+        ui.display_confirm(
+            "Selected files are not installed? Would you like to try and install them?",
+            Buttons::YesNo,
         );
-    };
+        if receive_msg().await != Message::Confirm {
+            return new_io_error!(
+                ErrorKind::ConnectionAborted,
+                "Did not select to install files"
+            );
+        };
+    }
     let data = InstallData::amend(mod_data, files, game_dir)?;
-    confirm_install(data, ui_handle).await
+    confirm_install(data, None, ui_handle).await
 }
 
 #[instrument(level = "trace", skip_all)]
 async fn add_dir_to_install_data(
     mut install_files: InstallData,
+    preview_limit: usize,
     ui_handle: slint::Weak<App>,
 ) -> std::io::Result<Vec<PathBuf>> {
     let ui = ui_handle.unwrap();
-    ui.display_confirm(&format!(
-        "Current Files to install:\n{}\n\nWould you like to add a directory eg. Folder containing a config file?", 
-        install_files.display_paths), Buttons::YesNo);
-    let result = match receive_msg().await {
-        Message::Confirm => match get_user_folder(&install_files.parent_dir, ui.window()) {
-            Ok(path) => {
-                install_files
-                    .update_fields_with_new_dir(&path, utils::installer::DisplayItems::Limit(9))
-                    .await
-            }
-            Err(err) => Err(err),
-        },
-        Message::Deny => Ok(()),
-        Message::Esc => new_io_error!(ErrorKind::ConnectionAborted, "Mod install canceled"),
+    let confirm_routine = get_confirm_level().confirm_routine();
+    if confirm_routine {
+        ui.display_confirm(&format!(
+            "Current Files to install:\n{}\n\nWould you like to add a directory eg. Folder containing a config file?", 
+            install_files.display_paths), Buttons::YesNo);
+        match receive_msg().await {
+            Message::Confirm => (),
+            Message::Deny => return confirm_install(install_files, None, ui_handle).await,
+            Message::Esc => {
+                return new_io_error!(ErrorKind::ConnectionAborted, "Mod install canceled")
+            }
+        }
+    }
+    // when the prompt is skipped it is assumed yes, closing the folder picker then continues the
+    // install with the current files instead of asking again
+    let result = match get_user_folder(&install_files.parent_dir, ui.window()) {
+        Ok(path) => install_files
+            .update_fields_with_new_dir(&path, utils::installer::DisplayItems::Limit(preview_limit))
+            .await
+            .map(|summary| Some(DisplayAddDirSummary(summary, &path).to_string())),
+        Err(err) if !confirm_routine && err.kind() == ErrorKind::InvalidInput => Ok(None),
+        Err(err) => Err(err),
     };
-    if let Err(err) = result {
-        if err.kind() == ErrorKind::InvalidInput {
+    let added = match result {
+        Ok(added) => added,
+        Err(err) if err.kind() == ErrorKind::InvalidInput => {
             ui.display_msg(&err.to_string());
             let _ = receive_msg().await;
-            let reselect_dir =
-                Box::pin(async { add_dir_to_install_data(install_files, ui_handle).await });
+            let reselect_dir = Box::pin(async {
+                add_dir_to_install_data(install_files, preview_limit, ui_handle).await
+            });
             return reselect_dir.await;
         }
-        return Err(err);
-    }
-    confirm_install(install_files, ui_handle).await
+        Err(err) => return Err(err),
+    };
+    confirm_install(install_files, added, ui_handle).await
 }
 
 #[instrument(level = "trace", skip_all)]
+/// `added` is shown above the selected files when given, eg. the summary of a directory just added
 async fn confirm_install(
     install_files: InstallData,
+    added: Option<String>,
     ui_handle: slint::Weak<App>,
 ) -> std::io::Result<Vec<PathBuf>> {
     let ui = ui_handle.unwrap();
-    ui.display_confirm(
-        &format!(
-            "Confirm install of mod: {}\n\nSelected files:\n{}\n\nInstall at:\n{}",
-            install_files.name,
-            install_files.display_paths,
-            &install_files.install_dir.display()
-        ),
-        Buttons::OkCancel,
-    );
-    if receive_msg().await != Message::Confirm {
-        return new_io_error!(ErrorKind::ConnectionAborted, "Mod install canceled");
+    let move_files = Cfg::read(get_ini_dir())
+        .map(|ini| ini.get_move_files())
+        .unwrap_or_else(|err| {
+            error!("{err}");
+            DEFAULT_MOVE_FILES
+        });
+    if get_confirm_level().confirm_summary() {
+        ui.display_confirm(
+            &format!(
+                "Confirm install of mod: {}\n\n{}Selected files:\n{}\n\n{}:\n{}",
+                install_files.name,
+                added.map(|msg| format!("{msg}\n\n")).unwrap_or_default(),
+                install_files.display_paths,
+                if move_files { "Files will be moved to" } else { "Install at" },
+                &install_files.install_dir.display()
+            ),
+            Buttons::OkCancel,
+        );
+        if receive_msg().await != Message::Confirm {
+            return new_io_error!(ErrorKind::ConnectionAborted, "Mod install canceled");
+        }
     }
     let zip = install_files.zip_from_to_paths()?;
-    if zip
+    if let Some((_, to_path)) = zip
         .iter()
-        .any(|(_, to_path)| !matches!(to_path.try_exists(), Ok(false)))
+        .find(|(_, to_path)| !matches!(to_path.try_exists(), Ok(false)))
     {
-        return new_io_error!(
-            ErrorKind::InvalidInput,
-            format!(
-                "Could not install: {}\".\nA selected file is already installed",
-                install_files.name
-            )
-        );
+        let owner = Cfg::read(get_ini_dir())
+            .ok()
+            .and_then(|ini| ini.owner_of(to_path));
+        return Err(EmlError::FileConflict {
+            mod_name: install_files.name.clone(),
+            path: to_path.to_path_buf(),
+            owner,
+        }
+        .into());
     };
-    let parents = zip
+    let copy_data = zip
         .iter()
-        .map(|(_, to_path)| parent_or_err(to_path))
-        .collect::<std::io::Result<Vec<&Path>>>()?;
-    parents.iter().try_for_each(std::fs::create_dir_all)?;
-    zip.iter()
-        .try_for_each(|(from_path, to_path)| std::fs::copy(from_path, to_path).map(|_| ()))?;
+        .map(|(from_path, to_path)| (from_path.to_path_buf(), to_path.to_path_buf()))
+        .collect::<Vec<_>>();
+    let installed = copy_data.iter().map(|(_, to_path)| to_path.clone()).collect();
+
+    CANCEL_INSTALL.store(false, Ordering::Release);
+    ui.global::<MainLogic>().set_install_progress(0.0);
+    ui.global::<MainLogic>().set_install_progress_text(SharedString::new());
+    ui.global::<MainLogic>().set_installing(true);
+
+    let (progress_sender, progress_receiver) = std::sync::mpsc::channel::<CopyProgress>();
+    let (result_sender, result_receiver) = tokio::sync::oneshot::channel();
+    std::thread::spawn(move || {
+        let result = copy_with_progress(&copy_data, move_files, &progress_sender, &CANCEL_INSTALL);
+        let _ = result_sender.send(result);
+    });
+    std::thread::spawn({
+        let ui_handle = ui.as_weak();
+        move || {
+            while let Ok(progress) = progress_receiver.recv() {
+                // only the latest progress is shown and the ui is updated at most once per interval
+                let progress = progress_receiver.try_iter().last().unwrap_or(progress);
+                let ui_handle = ui_handle.clone();
+                let _ = slint::invoke_from_event_loop(move || {
+                    let Some(ui) = ui_handle.upgrade() else {
+                        return;
+                    };
+                    ui.global::<MainLogic>().set_install_progress(
+                        progress.files_done as f32 / progress.files_total.max(1) as f32,
+                    );
+                    ui.global::<MainLogic>().set_install_progress_text(SharedString::from(
+                        format!(
+                            "{}/{} files, {}",
+                            progress.files_done,
+                            progress.files_total,
+                            DisplaySize(progress.bytes_done)
+                        ),
+                    ));
+                });
+                std::thread::sleep(PROGRESS_UPDATE_INTERVAL);
+            }
+        }
+    });
+    let result = result_receiver.await.unwrap_or_else(|_| {
+        new_io_error!(ErrorKind::BrokenPipe, "Install thread exited before reporting a result")
+    });
+    ui.global::<MainLogic>().set_installing(false);
+    result?;
     ui.display_msg(&format!("Installed mod: {}", &install_files.name));
-    Ok(zip.iter().map(|(_, to_path)| to_path.to_path_buf()).collect())
+    Ok(installed)
+}
+
+/// formats up to `limit` of `paths` relative to `game_dir` with `DisplayVec`, the rest are only counted
+fn display_capped_paths(paths: &[PathBuf], game_dir: &Path, limit: usize) -> String {
+    let shown = paths
+        .iter()
+        .take(limit)
+        .map(|path| path.strip_prefix(game_dir).unwrap_or(path).to_path_buf())
+        .collect::<Vec<_>>();
+    match paths.len().saturating_sub(limit) {
+        0 => DisplayVec(&shown).to_string(),
+        hidden => format!("{} and {hidden} more", DisplayVec(&shown)),
+    }
 }
 
+/// both confirmations are destructive so they are shown at every `ConfirmLevel`
 #[instrument(level = "trace", skip_all, fields(mod_name = reg_mod.name))]
 async fn confirm_remove_mod(
     ui_handle: slint::Weak<App>,
@@ -1939,19 +3959,12 @@ async fn confirm_remove_mod(
     loader_dir: &Path,
     reg_mod: &RegMod,
     ini_dir: &Path,
-) -> std::io::Result<()> {
+) -> Result<(), EmlError> {
     let ui = ui_handle.unwrap();
-    let Some(install_dir) = reg_mod
-        .files
-        .chain_all()
-        .min_by_key(|file| file.ancestors().count())
-        .and_then(|path| Some(game_dir.join(path.parent()?)))
-    else {
-        return new_io_error!(ErrorKind::InvalidData, "Failed to create an install_dir");
-    };
+    let install_dir = reg_mod.install_dir(game_dir)?;
 
     let match_user_msg = || async {
-        let esc_result = new_io_error!(ErrorKind::Interrupted, "De-registration canceled");
+        let esc_result = Err(EmlError::UserCanceled("De-registration canceled"));
         match receive_msg().await {
             Message::Confirm => Ok(()),
             Message::Deny => {
@@ -1975,14 +3988,10 @@ async fn confirm_remove_mod(
                     }
                 }
                 reg_mod.remove_from_file(ini_dir)?;
-                new_io_error!(
-                    ErrorKind::ConnectionAborted,
-                    format!(
-                        "Files registered with: {}, are still installed at: '{}'",
-                        DisplayName(&reg_mod.name),
-                        install_dir.display()
-                    )
-                )
+                Err(EmlError::FilesLeftInstalled {
+                    mod_name: reg_mod.name.clone(),
+                    install_dir: install_dir.clone(),
+                })
             }
             Message::Esc => esc_result,
         }
@@ -1994,14 +4003,31 @@ async fn confirm_remove_mod(
     );
     match_user_msg().await?;
 
-    ui.display_confirm(
-        "This is a distructive action. Are you sure you want to continue?",
-        Buttons::OkCancel,
-    );
+    let preview = reg_mod.removal_preview(game_dir);
+    let limit = Cfg::read(ini_dir)
+        .map(|ini| ini.get_install_preview_limit())
+        .unwrap_or(DEFAULT_INSTALL_PREVIEW_LIMIT);
+    let mut msg = String::from("This is a destructive action.");
+    if !preview.files.is_empty() {
+        msg.push_str(&format!(
+            "\n\nFiles to remove: {}",
+            display_capped_paths(&preview.files, game_dir, limit)
+        ));
+    }
+    if !preview.dirs.is_empty() {
+        msg.push_str(&format!(
+            "\n\nEmpty directories to remove: {}",
+            display_capped_paths(&preview.dirs, game_dir, limit)
+        ));
+    }
+    msg.push_str("\n\nAre you sure you want to continue?");
+    ui.display_confirm(&msg, Buttons::OkCancel);
     match_user_msg().await?;
 
-    reg_mod.remove_from_file(ini_dir)?;
-    remove_mod_files(game_dir, loader_dir, reg_mod)
+    // files are checked for locks before anything is removed, so the ini is only modified once
+    // all files are gone
+    remove_mod_files(game_dir, loader_dir, reg_mod)?;
+    Ok(reg_mod.remove_from_file(ini_dir)?)
 }
 
 #[instrument(level = "trace", skip_all)]
@@ -2013,15 +4039,54 @@ async fn confirm_scan_mods(
     order_map: Option<&OrderMap>,
 ) -> std::io::Result<()> {
     let ui = ui_handle.unwrap();
+    let confirm_level = get_confirm_level();
 
-    ui.display_confirm(
-        "Would you like to attempt to auto-import already installed mods to Elden Mod Loader GUI?",
-        Buttons::YesNo,
-    );
-    if receive_msg().await != Message::Confirm {
+    if confirm_level.confirm_routine() {
+        ui.display_confirm(
+            "Would you like to attempt to auto-import already installed mods to Elden Mod Loader GUI?",
+            Buttons::YesNo,
+        );
+        if receive_msg().await != Message::Confirm {
+            return Ok(());
+        };
+    }
+
+    if !matches!(game_dir.join("mods").try_exists(), Ok(true)) {
+        if !ui.global::<SettingsLogic>().get_loader_installed() {
+            ui.display_msg("No \"mods\" folder found yet, add a mod to create it");
+            return Ok(());
+        }
+        if confirm_level.confirm_routine() {
+            ui.display_confirm(
+                "No \"mods\" folder found in the game directory yet, nothing to import\n\n\
+                Would you like to create it now?",
+                Buttons::YesNo,
+            );
+            if receive_msg().await != Message::Confirm {
+                return Ok(());
+            }
+        }
+        ensure_mods_dir(game_dir)?;
         return Ok(());
+    }
+    let mut scan = match discover_mods(game_dir) {
+        Ok(scan) => scan,
+        Err(err) => {
+            ui.display_msg(&err.to_string());
+            return Ok(());
+        }
     };
-
+    if scan.folder_is_empty() {
+        ui.display_msg("\"mods\" folder is empty, nothing to import");
+        return Ok(());
+    }
+    if scan.dlls_found() == 0 {
+        ui.display_msg(&format!(
+            "Found {} file(s) and {} folder(s) in \"mods\" but no .dll files, nothing to import",
+            scan.non_dll_files, scan.dirs_seen
+        ));
+        return Ok(());
+    }
     let mut _new_ini = None;
     let ini = match ini {
         Some(data) => data,
@@ -2030,6 +4095,48 @@ async fn confirm_scan_mods(
             _new_ini.as_ref().unwrap()
         }
     };
+    let mode = if ini.mods_is_empty() {
+        ScanMode::Reset
+    } else {
+        ui.display_confirm(
+            "You already have registered mods\n\n\
+            Yes: only add mods that are not registered yet, your current mods are kept as they are\n\
+            No: replace all registered mods with the mods found",
+            Buttons::YesNo,
+        );
+        match receive_msg().await {
+            Message::Confirm => ScanMode::Merge,
+            Message::Deny => ScanMode::Reset,
+            Message::Esc => return Ok(()),
+        }
+    };
+    if mode == ScanMode::Merge {
+        scan.retain_unregistered(ini);
+        if scan.mods.is_empty() {
+            ui.display_msg("All mods found are already registered, nothing to import");
+            return Ok(());
+        }
+    }
+    let mut scanned_mods = scan.mods;
+    if confirm_level.confirm_summary() {
+        ui.display_confirm(
+            &format!(
+                "Found {} {}mod(s):\n{}\n\nWould you like to register the mod(s) found?",
+                scanned_mods.len(),
+                if mode == ScanMode::Merge { "new " } else { "" },
+                scanned_mods
+                    .iter()
+                    .map(|m| DisplayName(&m.name).to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            ),
+            Buttons::YesNo,
+        );
+        if receive_msg().await != Message::Confirm {
+            return Ok(());
+        };
+    }
+
     let loader_dir = get_loader_ini_dir();
     let mut _new_map = None;
     let order_map = order_map.unwrap_or_else(|| {
@@ -2037,9 +4144,11 @@ async fn confirm_scan_mods(
         _new_map.as_ref().unwrap()
     });
 
-    let mut old_mods = if ini.mods_is_empty() {
+    let mut saved_sections = Vec::new();
+    let mut old_mods = if mode == ScanMode::Merge || ini.mods_is_empty() {
         Vec::new()
     } else {
+        // destructive, shown at every `ConfirmLevel`
         ui.display_confirm("Warning: This action will reset current registered mods, are you sure you want to continue?", Buttons::YesNo);
         if receive_msg().await != Message::Confirm {
             return Ok(());
@@ -2051,8 +4160,14 @@ async fn confirm_scan_mods(
         }
 
         let dark_mode = ui.global::<SettingsLogic>().get_dark_mode();
-        let save_log = ini.get_save_log().unwrap_or(true);
+        let save_log = ini.get_save_log();
+        // notes, pins and categories are restored for mods that are found again
+        saved_sections = [NOTES_SECTION, PINNED_SECTION, CATEGORIES_SECTION]
+            .into_iter()
+            .filter_map(|section| Some((section, ini.data().section(section)?.clone())))
+            .collect();
 
+        mark_self_write();
         std::fs::remove_file(ini.path())?;
         new_cfg(ini.path())?;
         if dark_mode != DEFAULT_INI_VALUES[0] {
@@ -2065,9 +4180,19 @@ async fn confirm_scan_mods(
         data.mods
     };
 
-    let new_mods = match scan_for_mods(game_dir, ini.path()) {
+    let new_mods = match commit_scanned_mods(&mut scanned_mods, game_dir, ini.path()) {
         Ok(len) => {
-            let new_ini = Cfg::read(ini.path())?;
+            let mut new_ini = Cfg::read(ini.path())?;
+            if !saved_sections.is_empty() {
+                let keys = new_ini.keys();
+                for (section, properties) in saved_sections {
+                    properties
+                        .iter()
+                        .filter(|(k, _)| keys.contains(&canonical_key(k)))
+                        .for_each(|(k, v)| new_ini.set(section, k, v));
+                }
+                new_ini.write_to_file()?;
+            }
             ui.global::<MainLogic>().set_current_subpage(0);
             let mut unknown_orders = get_mut_unknown_orders();
             let order_data =
@@ -2082,8 +4207,11 @@ async fn confirm_scan_mods(
                         unknown_orders.remove(f);
                     })
             });
-            deserialize_collected_mods(&new_mods, ui.as_weak());
-            ui.display_msg(&format!("Found {len} mod(s)"));
+            deserialize_collected_mods(&new_mods, &new_ini, ui.as_weak());
+            match mode {
+                ScanMode::Reset => ui.display_msg(&format!("Found {len} mod(s)")),
+                ScanMode::Merge => ui.display_msg(&format!("Added {len} new mod(s)")),
+            }
             new_mods
         }
         Err(err) => {
@@ -2108,7 +4236,8 @@ async fn confirm_scan_mods(
         // unsure if we want to remove order data, currently on mod removal user decides to remove,
         // or, is deleted on mod uninstallation
         old_mods.iter().try_for_each(|m| {
-            if m.order.set && !all_new_files.contains(m.files.dll[m.order.i].as_path()) {
+            let order_file = m.validated_order_index().map(|i| m.files.dll[i].as_path());
+            if m.order.set && order_file.is_some_and(|file| !all_new_files.contains(file)) {
                 remove_order_entry(m, loader_dir)
             } else {
                 Ok(())
@@ -0,0 +1,174 @@
+use elden_mod_loader_gui::{
+    ini_cfg_path, loader_cfg_path, new_io_error,
+    utils::ini::{
+        common::{Cfg, Config, ModLoaderCfg},
+        parser::RegMod,
+        writer::{new_cfg, save_value_ext},
+    },
+    LOADER_SECTIONS, OFF_STATE,
+};
+use std::{
+    collections::HashSet,
+    fs::{create_dir_all, remove_dir_all, File},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+use tracing::error;
+
+/// prints the outcome of `stage`, returns the value of `result` if it passed
+fn report<T>(stage: &str, result: std::io::Result<T>) -> Option<T> {
+    match result {
+        Ok(value) => {
+            println!("PASS: {stage}");
+            Some(value)
+        }
+        Err(err) => {
+            error!("self test failed at: {stage}, {err}");
+            println!("FAIL: {stage}, {err}");
+            None
+        }
+    }
+}
+
+fn check(passed: bool, msg: &str) -> std::io::Result<()> {
+    if passed {
+        return Ok(());
+    }
+    new_io_error!(ErrorKind::InvalidData, msg.to_string())
+}
+
+/// paths into the synthetic game directory made by `SelfTestDir::write`  
+/// the directory and everything in it is removed when this is dropped
+struct SelfTestDir {
+    root: PathBuf,
+    game_dir: PathBuf,
+    ini_dir: PathBuf,
+    loader_dir: PathBuf,
+}
+
+impl SelfTestDir {
+    /// writes "a_mod" at order 1, disabled "b_mod" at order 2 and the always active "c_mod"
+    fn write(root: PathBuf) -> std::io::Result<Self> {
+        let game_dir = root.join("Game");
+        // construct the guard first so a failed write is still cleaned up
+        let dir = SelfTestDir {
+            ini_dir: ini_cfg_path(&root),
+            loader_dir: loader_cfg_path(&game_dir),
+            game_dir,
+            root,
+        };
+        if dir.root.exists() {
+            remove_dir_all(&dir.root)?;
+        }
+        new_cfg(&dir.ini_dir)?;
+        new_cfg(&dir.loader_dir)?;
+
+        let mods = [
+            ("a_mod", true, vec!["mods\\a_mod.dll", "mods\\a_mod\\config.ini"], Some(1)),
+            ("b_mod", false, vec!["mods\\b_mod.dll"], Some(2)),
+            ("c_mod", true, vec!["mods\\c_mod.txt"], None),
+        ];
+        for (name, state, files, order) in mods {
+            let files = files
+                .into_iter()
+                .map(|file| match (state, file.ends_with(".dll")) {
+                    (false, true) => PathBuf::from(format!("{file}{OFF_STATE}")),
+                    _ => PathBuf::from(file),
+                })
+                .collect::<Vec<_>>();
+            for file in files.iter() {
+                let path = dir.game_dir.join(file);
+                create_dir_all(path.parent().expect("short paths have a parent"))?;
+                File::create(path)?;
+            }
+            RegMod::new(name, state, files).write_to_file(&dir.ini_dir, false)?;
+            if let Some(order) = order {
+                let key = format!("{name}.dll");
+                save_value_ext(&dir.loader_dir, LOADER_SECTIONS[1], &key, &order.to_string())?;
+            }
+        }
+        Ok(dir)
+    }
+
+    fn game_dir(&self) -> &Path {
+        &self.game_dir
+    }
+
+    fn ini_dir(&self) -> &Path {
+        &self.ini_dir
+    }
+
+    fn loader_dir(&self) -> &Path {
+        &self.loader_dir
+    }
+}
+
+impl Drop for SelfTestDir {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.root);
+    }
+}
+
+/// every stage depends on the one before it so testing stops at the first failure  
+/// the temp directory is removed once `SelfTestDir` is dropped  
+/// returns `true` if all stages passed
+pub fn run() -> bool {
+    let root = std::env::temp_dir().join(format!(
+        "elden_mod_loader_gui_self_test_{}",
+        std::process::id()
+    ));
+    let Some(test_dir) = report("write synthetic config", SelfTestDir::write(root)) else {
+        return false;
+    };
+    let Some(ini) = report("read Cfg", Cfg::read(test_dir.ini_dir())) else {
+        return false;
+    };
+    let Some(mut loader) = report("read ModLoaderCfg", ModLoaderCfg::read(test_dir.loader_dir())) else {
+        return false;
+    };
+    let no_unknown_keys = HashSet::new();
+    let Some(order_map) = report(
+        "parse load order",
+        loader.parse_section(&no_unknown_keys).and_then(|order_map| {
+            check(order_map.get("a_mod.dll") == Some(&1), "a_mod.dll is not order 1")?;
+            check(order_map.get("b_mod.dll") == Some(&2), "b_mod.dll is not order 2")?;
+            Ok(order_map)
+        }),
+    ) else {
+        return false;
+    };
+    let collect_mods = || {
+        let collection = ini.collect_mods(test_dir.game_dir(), Some(&order_map), false);
+        if let Some(err) = collection.warnings {
+            return Err(err);
+        }
+        let mods = collection.mods;
+        check(mods.len() == 3, "expected 3 registered mods")?;
+        let find = |name: &str| mods.iter().find(|reg_mod| reg_mod.name == name);
+        check(find("a_mod").is_some_and(|m| m.state && m.order.at == 1), "a_mod is incorrect")?;
+        check(find("b_mod").is_some_and(|m| !m.state && m.order.at == 2), "b_mod is incorrect")?;
+        check(find("c_mod").is_some_and(|m| m.is_always_active()), "c_mod is incorrect")
+    };
+    if report("collect_mods", collect_mods()).is_none() {
+        return false;
+    }
+    let (dlls, order_count, _) = ini.dll_set_order_count(loader.mut_section());
+    let verify_keys = loader
+        .verify_keys(&dlls, order_count)
+        .map_err(|key_err| std::io::Error::other(key_err.err.to_string()));
+    if report("verify_keys", verify_keys).is_none() {
+        return false;
+    }
+    let mut update_order_entries = || {
+        loader.mut_section().remove("a_mod.dll");
+        let ord_meta_data = loader.update_order_entries(None, &no_unknown_keys);
+        check(ord_meta_data.max_order == (1, false), "max order is not 1")?;
+        loader.write_to_file()?;
+        let saved = ModLoaderCfg::read(test_dir.loader_dir())?;
+        check(
+            saved.data().get_from(LOADER_SECTIONS[1], "b_mod.dll") == Some("1"),
+            "b_mod.dll was not moved to order 1",
+        )
+    };
+    report("update_order_entries", update_order_entries()).is_some()
+}
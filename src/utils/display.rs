@@ -3,10 +3,20 @@ use std::{
     path::{Path, PathBuf},
 };
 
-use crate::{utils::ini::parser::LoadOrder, ANTI_CHEAT_EXE};
+use crate::{
+    utils::{
+        error::EmlError,
+        ini::parser::{ConfigDiff, DiagnosisReport, LoadOrder, VerifySummary, BOOL_FORMS},
+        installer::AddDirSummary,
+    },
+    InstallType, ANTI_CHEAT_EXE,
+};
 
 pub const TECHIE_W_MSG: &str = "Could not find Elden Mod Loader Script!\n\
     This tool requires 'Elden Mod Loader' by TechieW to be installed!";
+pub const GAME_PASS_MSG: &str = "Found a Game Pass / Windows Store install of Elden Ring\n\
+    Elden Mod Loader only supports the Steam version of Elden Ring, please select the \"Game\" \
+    directory of a Steam install";
 pub const TUTORIAL_MSG: &str =
     "Add mods to the app by entering a name and selecting mod files with \"Select Files\"\n\n\
     You can always add more files to a mod or de-register a mod at any time from within the app\n\n\
@@ -71,12 +81,13 @@ impl DisplayItem for usize {
     }
 }
 
+/// formats one item as is, multiple items as "[a, b]" and no items as "[]"
 pub struct DisplayVec<'a, D: DisplayItem>(pub &'a [D]);
 
 impl<'a, D: DisplayItem> std::fmt::Display for DisplayVec<'a, D> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         if self.0.is_empty() {
-            panic!("Tried to format an empty Vec");
+            return write!(f, "[]");
         }
         if self.0.len() == 1 {
             return self.0[0].display_item(f, "");
@@ -93,12 +104,13 @@ impl<'a, D: DisplayItem> std::fmt::Display for DisplayVec<'a, D> {
     }
 }
 
+/// formats the items of `self.1` found at each index in `self.0` the same way as `DisplayVec`
 pub struct DisplayIndices<'a, D: DisplayItem>(pub &'a [usize], pub &'a [D]);
 
 impl<'a, D: DisplayItem> std::fmt::Display for DisplayIndices<'a, D> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.0.is_empty() || self.1.is_empty() {
-            panic!("Tried to format an empty Vec");
+        if self.0.is_empty() {
+            return write!(f, "[]");
         }
         if *self.0.iter().max().unwrap() >= self.1.len() {
             panic!("index is larger than what is trying to be displayed")
@@ -171,6 +183,56 @@ impl std::fmt::Display for DisplayState {
     }
 }
 
+/// describes the files added from the directory `.1`, see `update_fields_with_new_dir`
+pub struct DisplayAddDirSummary<'a>(pub AddDirSummary, pub &'a Path);
+
+impl<'a> std::fmt::Display for DisplayAddDirSummary<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let count = self.0.files_added;
+        write!(
+            f,
+            "Added {count} {} from: '{}'",
+            if count == 1 { "file" } else { "files" },
+            self.1.display()
+        )?;
+        if self.0.reconstructed {
+            write!(f, "\nThe previously selected files were re-read from this folder")?;
+        } else if self.0.moved_whole_folder {
+            write!(f, "\nThe entire folder will be installed")?;
+        }
+        Ok(())
+    }
+}
+
+/// formats a number of bytes into the largest unit that keeps the value >= 1, eg. "1.5 MB"
+pub struct DisplaySize(pub u64);
+
+impl std::fmt::Display for DisplaySize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        const UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < UNITS.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{size:.1} {}", UNITS[unit])
+    }
+}
+
+impl std::fmt::Display for InstallType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstallType::Steam => write!(f, "Steam"),
+            InstallType::GamePass => write!(f, "Game Pass"),
+            InstallType::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
 impl std::fmt::Display for LoadOrder {
     #[inline]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -182,6 +244,162 @@ impl std::fmt::Display for LoadOrder {
     }
 }
 
+impl std::fmt::Display for DiagnosisReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_healthy() {
+            return write!(f, "No inconsistencies found between the ini and the files on disk");
+        }
+        let mut lines = Vec::with_capacity(self.missing_files.len() + self.changed_files.len() + 3);
+        if !self.state_mismatch.is_empty() {
+            let names = self
+                .state_mismatch
+                .iter()
+                .map(|name| DisplayName(name).to_string())
+                .collect::<Vec<_>>();
+            lines.push(format!(
+                "Files on disk do not match the saved state of: {}",
+                DisplayVec(&names)
+            ));
+        }
+        for (name, files) in self.missing_files.iter() {
+            if files.is_empty() {
+                lines.push(format!("{} has no registered files", DisplayName(name)));
+            } else {
+                lines.push(format!(
+                    "{} is missing file(s): {}",
+                    DisplayName(name),
+                    DisplayVec(files)
+                ));
+            }
+        }
+        if !self.unregistered_files.is_empty() {
+            lines.push(format!(
+                "Files in \"mods\" not registered to any mod: {}",
+                DisplayVec(&self.unregistered_files)
+            ));
+        }
+        if !self.orphaned_orders.is_empty() {
+            lines.push(format!(
+                "Load order set for files that are not registered: {}",
+                DisplayVec(&self.orphaned_orders)
+            ));
+        }
+        for (name, files) in self.changed_files.iter() {
+            lines.push(format!(
+                "{} has file(s) that changed since they were registered: {}",
+                DisplayName(name),
+                DisplayVec(files)
+            ));
+        }
+        write!(f, "{}", lines.join("\n\n"))
+    }
+}
+
+impl std::fmt::Display for ConfigDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn display_names(names: &[String]) -> Vec<String> {
+            names.iter().map(|name| DisplayName(name).to_string()).collect()
+        }
+        fn display_order(order: Option<usize>) -> String {
+            order.map_or_else(|| String::from("not set"), |order| order.to_string())
+        }
+
+        if self.is_empty() {
+            return write!(f, "No changes, the backup matches the current config");
+        }
+        let mut lines = Vec::with_capacity(self.state_changed.len() + self.order_changed.len() + 3);
+        if !self.added.is_empty() {
+            lines.push(format!("Added: {}", DisplayVec(&display_names(&self.added))));
+        }
+        if !self.removed.is_empty() {
+            lines.push(format!("Removed: {}", DisplayVec(&display_names(&self.removed))));
+        }
+        for (name, state) in self.state_changed.iter() {
+            lines.push(format!("{} will be {}", DisplayName(name), DisplayState(*state)));
+        }
+        if !self.files_changed.is_empty() {
+            let names = display_names(&self.files_changed);
+            lines.push(format!("Files changed: {}", DisplayVec(&names)));
+        }
+        for (file_name, current, saved) in self.order_changed.iter() {
+            lines.push(format!(
+                "{file_name} load order: {} -> {}",
+                display_order(*current),
+                display_order(*saved)
+            ));
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl std::fmt::Display for VerifySummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn display_names<'a>(names: impl Iterator<Item = &'a String>) -> Vec<String> {
+            names.map(|name| DisplayName(name).to_string()).collect()
+        }
+
+        write!(
+            f,
+            "Verified {} mods: {} fixed, {} unchanged, {} failed",
+            self.fixed.len() + self.unchanged.len() + self.failed.len(),
+            self.fixed.len(),
+            self.unchanged.len(),
+            self.failed.len()
+        )?;
+        if !self.fixed.is_empty() {
+            let names = display_names(self.fixed.iter());
+            write!(f, "\n\nFixed: {}", DisplayVec(&names))?;
+        }
+        if !self.failed.is_empty() {
+            let names = display_names(self.failed.iter().map(|(name, _)| name));
+            write!(f, "\n\nFailed: {}", DisplayVec(&names))?;
+            self.failed.iter().try_for_each(|(_, err)| write!(f, "\n{err}"))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for EmlError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EmlError::UnknownOrderKeys { keys, order_changed: true } => write!(
+                f,
+                "Found load order set for file(s) not registered with the app. One or more of the following key(s) order has been changed: {}",
+                DisplayVec(keys)
+            ),
+            EmlError::UnknownOrderKeys { keys, order_changed: false } => write!(
+                f,
+                "Found load order set for the following file(s) not registered with the app: {}",
+                DisplayVec(keys)
+            ),
+            EmlError::UserCanceled(msg) => write!(f, "{msg}"),
+            EmlError::FileConflict { mod_name, path, owner } => {
+                write!(
+                    f,
+                    "Could not install: {mod_name}.\nA selected file is already installed at: '{}'",
+                    path.display()
+                )?;
+                match owner {
+                    Some(owner) => write!(f, "\nFile is registered to: {}", DisplayName(owner)),
+                    None => Ok(()),
+                }
+            }
+            EmlError::FilesLeftInstalled { mod_name, install_dir } => write!(
+                f,
+                "Files registered with: {}, are still installed at: '{}'",
+                DisplayName(mod_name),
+                install_dir.display()
+            ),
+            EmlError::SymlinkFound(path) => write!(
+                f,
+                "Found a symbolic link at '{}'; symlinks aren't supported — please use regular files",
+                path.display()
+            ),
+            EmlError::Io(err) => write!(f, "{err}"),
+        }
+    }
+}
+
 pub struct DisplayTheme(pub bool);
 
 impl std::fmt::Display for DisplayTheme {
@@ -221,7 +439,8 @@ impl IntoIoError for std::str::ParseBoolError {
         std::io::Error::new(
             ErrorKind::InvalidData,
             format!(
-                "string: '{context}', saved with key: '{key}', was not `true`, `false`, `1`, or `0`"
+                "string: '{context}', saved with key: '{key}', was not one of: {}",
+                DisplayVec(&BOOL_FORMS.map(|(form, _)| form))
             ),
         )
     }
@@ -0,0 +1,77 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+/// errors that carry meaning to the caller beyond a failed io operation  
+/// use `EmlError::Io` to wrap any other `io::Error`, convert to `io::Error` at the ui boundary
+#[derive(Debug)]
+pub enum EmlError {
+    /// load order entries were found for files that are not registered with the app  
+    /// `order_changed` is `true` if entries were moved to be sorted after registered files
+    UnknownOrderKeys {
+        keys: Vec<String>,
+        order_changed: bool,
+    },
+
+    /// the user canceled the operation before any changes were made
+    UserCanceled(&'static str),
+
+    /// `path` is already installed and would be overwritten by installing `mod_name`  
+    /// `owner` is the registered mod that `path` is saved to if any
+    FileConflict {
+        mod_name: String,
+        path: PathBuf,
+        owner: Option<String>,
+    },
+
+    /// `mod_name` was de-registered but the user chose to keep its files at `install_dir`
+    FilesLeftInstalled {
+        mod_name: String,
+        install_dir: PathBuf,
+    },
+
+    /// a symbolic link was found at `0` while reading a directory tree, symlinks are not followed  
+    /// converts into an `io::Error` that wraps `self` so the path can be retrieved with `downcast_ref`
+    SymlinkFound(PathBuf),
+
+    Io(std::io::Error),
+}
+
+impl EmlError {
+    /// the `ErrorKind` used when converting `self` into an `io::Error`
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            EmlError::UnknownOrderKeys { .. } => ErrorKind::Other,
+            EmlError::UserCanceled(_) => ErrorKind::Interrupted,
+            EmlError::FileConflict { .. } => ErrorKind::AlreadyExists,
+            EmlError::FilesLeftInstalled { .. } => ErrorKind::Other,
+            EmlError::SymlinkFound(_) => ErrorKind::InvalidData,
+            EmlError::Io(err) => err.kind(),
+        }
+    }
+}
+
+impl std::error::Error for EmlError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EmlError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for EmlError {
+    #[inline]
+    fn from(err: std::io::Error) -> Self {
+        EmlError::Io(err)
+    }
+}
+
+impl From<EmlError> for std::io::Error {
+    #[inline]
+    fn from(err: EmlError) -> Self {
+        match err {
+            EmlError::Io(err) => err,
+            err @ EmlError::SymlinkFound(_) => std::io::Error::new(err.kind(), err),
+            err => std::io::Error::new(err.kind(), err.to_string()),
+        }
+    }
+}
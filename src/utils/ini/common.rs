@@ -1,25 +1,46 @@
 use ini::Ini;
 use std::{
-    collections::HashSet,
-    io,
+    collections::{HashMap, HashSet},
+    io::{self, ErrorKind},
     marker::Sized,
     path::{Path, PathBuf},
+    sync::OnceLock,
 };
-use tracing::{info, instrument};
+use tracing::{info, instrument, trace, warn, Level};
 
 use crate::{
-    get_or_setup_cfg,
+    canonical_key, files_not_found, format_key, get_cfg, get_or_setup_cfg, new_io_error,
     utils::{
-        display::{DisplayTheme, DisplayTime, IntoIoError, ModError},
+        display::{DisplayName, DisplayTheme, DisplayTime, DisplayVec, IntoIoError, ModError},
         ini::{
             parser::{parse_bool, IniProperty},
-            writer::{save_bool, save_value_ext, EXT_OPTIONS, WRITE_OPTIONS},
+            writer::{
+                backup_file, save_value, save_value_ext, write_atomic, EXT_OPTIONS, WRITE_OPTIONS,
+            },
         },
+        watcher::mark_self_write,
     },
-    ARRAY_KEY, ARRAY_VALUE, DEFAULT_INI_VALUES, DEFAULT_LOADER_VALUES, INI_KEYS, INI_NAME,
-    INI_SECTIONS, LOADER_FILES, LOADER_KEYS, LOADER_SECTIONS,
+    ARRAY_KEY, ARRAY_VALUE, CATEGORIES_SECTION, ConfirmLevel, DEFAULT_BACKUP_CONFIGS,
+    DEFAULT_CATEGORY, DEFAULT_CONFIRM_LEVEL, DEFAULT_EDITOR, DEFAULT_HASH_FILES, DEFAULT_INI_VALUES,
+    DEFAULT_INSTALL_PREVIEW_LIMIT, DEFAULT_LAUNCH_METHOD, DEFAULT_LOADER_VALUES, DEFAULT_LOG_LEVEL,
+    DEFAULT_MOVE_FILES, DEFAULT_SUPPRESS_EAC_WARNING, DEFAULT_WATCH_FILES, FAVORITES_SECTION,
+    INI_KEYS, INI_NAME, INI_SECTIONS, INSTALL_PREVIEW_RANGE, LOADER_FILES, LOADER_KEYS,
+    LOADER_SECTIONS, LOAD_DELAY_RANGE, LOG_LEVELS, LaunchMethod, MAX_FAVORITES, MAX_RECENT_DIRS,
+    NOTES_SECTION, OFF_STATE, OrderMap, PINNED_SECTION, RECENT_DIRS_SECTION, REQUIRED_GAME_FILES,
+    SCHEMA_VERSION, SUSPENDED_ORDER_SECTION, TOGGLE_CONFIGS_SECTION, TRACKED_DIRS_SECTION,
 };
 
+/// settings in "app-settings" that are saved as a `bool`, with their default
+const BOOL_SETTINGS: [(&str, bool); 7] = [
+    (INI_KEYS[0], DEFAULT_INI_VALUES[0]),
+    (INI_KEYS[1], DEFAULT_INI_VALUES[1]),
+    (INI_KEYS[4], DEFAULT_SUPPRESS_EAC_WARNING),
+    (INI_KEYS[6], DEFAULT_WATCH_FILES),
+    (INI_KEYS[8], DEFAULT_BACKUP_CONFIGS),
+    (INI_KEYS[9], DEFAULT_HASH_FILES),
+    (INI_KEYS[10], DEFAULT_MOVE_FILES),
+];
+
 pub trait Config {
     /// reads a .ini file into memory  
     fn read(ini_dir: &Path) -> io::Result<Self>
@@ -148,7 +169,9 @@ impl Config for Cfg {
 
     #[inline]
     fn write_to_file(&self) -> io::Result<()> {
-        self.data.write_to_file_opt(&self.dir, WRITE_OPTIONS)
+        backup_file(&self.dir);
+        mark_self_write();
+        write_atomic(&self.data, &self.dir, WRITE_OPTIONS)
     }
 
     fn save_default_val(
@@ -158,11 +181,19 @@ impl Config for Cfg {
         mut in_err: io::Error,
     ) -> io::Error {
         let default_val = match key {
-            k if k == INI_KEYS[0] => DEFAULT_INI_VALUES[0],
-            k if k == INI_KEYS[1] => DEFAULT_INI_VALUES[1],
+            k if k == INI_KEYS[0] => DEFAULT_INI_VALUES[0].to_string(),
+            k if k == INI_KEYS[1] => DEFAULT_INI_VALUES[1].to_string(),
+            k if k == INI_KEYS[4] => DEFAULT_SUPPRESS_EAC_WARNING.to_string(),
+            k if k == INI_KEYS[6] => DEFAULT_WATCH_FILES.to_string(),
+            k if k == INI_KEYS[7] => DEFAULT_LOG_LEVEL.as_str().to_lowercase(),
+            k if k == INI_KEYS[8] => DEFAULT_BACKUP_CONFIGS.to_string(),
+            k if k == INI_KEYS[9] => DEFAULT_HASH_FILES.to_string(),
+            k if k == INI_KEYS[10] => DEFAULT_MOVE_FILES.to_string(),
+            k if k == INI_KEYS[12] => DEFAULT_LAUNCH_METHOD.as_str().to_string(),
+            k if k == INI_KEYS[13] => DEFAULT_CONFIRM_LEVEL.as_str().to_string(),
             _ => panic!("Key: {key}, is unknown to: {INI_NAME}"),
         };
-        if let Err(err) = save_bool(&self.dir, section, key, default_val) {
+        if let Err(err) = save_value(&self.dir, section, key, &default_val) {
             in_err.add_msg(&err.to_string(), false);
         } else {
             in_err.add_msg(&format!("Reset: {key}, to: {default_val}"), false);
@@ -172,25 +203,367 @@ impl Config for Cfg {
 }
 
 impl Cfg {
-    /// returns the value stored with key "dark_mode" as a `bool`  
-    /// if error calls `self.save_default_val` to correct error  
-    pub fn get_dark_mode(&self) -> io::Result<bool> {
-        match IniProperty::<bool>::read(&self.data, INI_SECTIONS[0], INI_KEYS[0]) {
-            Ok(dark_mode) => {
-                info!("{} theme loaded", DisplayTheme(dark_mode.value));
-                Ok(dark_mode.value)
+    /// brings a config saved with an older "schema_version" up to `SCHEMA_VERSION` then writes it to  
+    /// file, each step transforms a config from the version before it so older configs are migrated  
+    /// one version at a time. a config saved by a newer version of the app is left unmodified
+    #[instrument(level = "trace", skip_all)]
+    pub fn migrate(&mut self) -> io::Result<()> {
+        let version = match self.data.get_from(INI_SECTIONS[0], INI_KEYS[11]) {
+            Some(value) => value.trim().parse::<u32>().unwrap_or_else(|_| {
+                warn!("Invalid {}: '{value}', found in: {INI_NAME}", INI_KEYS[11]);
+                0
+            }),
+            None => 0,
+        };
+        if version >= SCHEMA_VERSION {
+            if version > SCHEMA_VERSION {
+                let key = INI_KEYS[11];
+                warn!("{INI_NAME} was saved by a newer version of this app, {key}: {version}");
             }
-            Err(err) => Err(self.save_default_val(INI_SECTIONS[0], INI_KEYS[0], err)),
+            return Ok(());
         }
+        for from in version..SCHEMA_VERSION {
+            match from {
+                // saved before "schema_version" existed, the layout is the same as version 1
+                0 => (),
+                _ => unreachable!("every version below `SCHEMA_VERSION` has a migration step"),
+            }
+            info!("Migrated {INI_NAME} from {}: {from}, to: {}", INI_KEYS[11], from + 1);
+        }
+        self.set(INI_SECTIONS[0], INI_KEYS[11], &SCHEMA_VERSION.to_string());
+        self.write_to_file()
     }
 
-    /// returns the value stored with key "save_log" as a `bool`  
-    /// if error calls `self.save_default_val` to correct error  
-    pub fn get_save_log(&self) -> io::Result<bool> {
-        match IniProperty::<bool>::read(&self.data, INI_SECTIONS[0], INI_KEYS[1]) {
-            Ok(save_log) => Ok(save_log.value),
-            Err(err) => Err(self.save_default_val(INI_SECTIONS[0], INI_KEYS[1], err)),
+    /// reads the ini at `ini_dir` without creating or repairing it  
+    /// use for files that must be left as they are, eg. a backup that is only being previewed
+    pub fn read_snapshot(ini_dir: &Path) -> io::Result<Self> {
+        Ok(Cfg {
+            data: get_cfg(ini_dir)?,
+            dir: PathBuf::from(ini_dir),
+        })
+    }
+
+    /// returns the value stored with `key` in section "app-settings" as a `bool`  
+    /// a missing or invalid value returns `default` and calls `self.save_default_val` to correct it  
+    /// `default` must match the default `save_default_val` has for `key`
+    pub fn get_bool_setting(&self, key: &str, default: bool) -> bool {
+        match IniProperty::<bool>::read(&self.data, INI_SECTIONS[0], key) {
+            Ok(setting) => setting.value,
+            Err(err) => {
+                let err = self.save_default_val(INI_SECTIONS[0], key, err);
+                warn!("{err}");
+                default
+            }
+        }
+    }
+
+    /// resets settings in "app-settings" that are saved with an invalid `bool` to their default  
+    /// missing settings are added with their default, returns a message for each invalid value  
+    /// **Note:** this does not write the changes to file
+    pub fn reset_bool_settings(&mut self) -> Vec<String> {
+        let mut messages = Vec::new();
+        let settings = self
+            .data
+            .entry(INI_SECTIONS[0].map(String::from))
+            .or_insert_with(ini::Properties::new);
+        for (key, default) in BOOL_SETTINGS {
+            match settings.get(key) {
+                Some(value) => {
+                    let Err(err) = parse_bool(value) else {
+                        continue;
+                    };
+                    let err = err.into_io_error(key, value);
+                    let msg = format!("{err}, reset: {key}, to: {default}");
+                    info!("{msg}");
+                    messages.push(msg);
+                }
+                None => trace!("{key} not found, saved with default: {default}"),
+            }
+            settings.insert(key, default.to_string());
+        }
+        messages
+    }
+
+    /// returns the value stored with key "dark_mode" as a `bool`, see `get_bool_setting`
+    pub fn get_dark_mode(&self) -> bool {
+        let dark_mode = self.get_bool_setting(INI_KEYS[0], DEFAULT_INI_VALUES[0]);
+        info!("{} theme loaded", DisplayTheme(dark_mode));
+        dark_mode
+    }
+
+    /// returns the value stored with key "save_log" as a `bool`, see `get_bool_setting`
+    pub fn get_save_log(&self) -> bool {
+        self.get_bool_setting(INI_KEYS[1], DEFAULT_INI_VALUES[1])
+    }
+
+    /// returns the value stored with key "suppress_eac_warning" as a `bool`, see `get_bool_setting`
+    pub fn get_suppress_eac_warning(&self) -> bool {
+        self.get_bool_setting(INI_KEYS[4], DEFAULT_SUPPRESS_EAC_WARNING)
+    }
+
+    /// returns the value stored with key "watch_files" as a `bool`, see `get_bool_setting`
+    pub fn get_watch_files(&self) -> bool {
+        self.get_bool_setting(INI_KEYS[6], DEFAULT_WATCH_FILES)
+    }
+
+    /// returns the value stored with key "backup_configs" as a `bool`, see `get_bool_setting`
+    pub fn get_backup_configs(&self) -> bool {
+        self.get_bool_setting(INI_KEYS[8], DEFAULT_BACKUP_CONFIGS)
+    }
+
+    /// returns the value stored with key "hash_files" as a `bool`, see `get_bool_setting`
+    pub fn get_hash_files(&self) -> bool {
+        self.get_bool_setting(INI_KEYS[9], DEFAULT_HASH_FILES)
+    }
+
+    /// returns the value stored with key "move_files" as a `bool`, see `get_bool_setting`
+    pub fn get_move_files(&self) -> bool {
+        self.get_bool_setting(INI_KEYS[10], DEFAULT_MOVE_FILES)
+    }
+
+    /// returns the level saved with key "log_level", `DEFAULT_LOG_LEVEL` if the key is missing  
+    /// if the value is not one of `LOG_LEVELS` calls `self.save_default_val` to correct error
+    pub fn get_log_level(&self) -> io::Result<Level> {
+        let Some(value) = self.data.get_from(INI_SECTIONS[0], INI_KEYS[7]) else {
+            return Ok(DEFAULT_LOG_LEVEL);
+        };
+        let value = value.trim().to_lowercase();
+        match LOG_LEVELS.contains(&value.as_str()).then(|| value.parse::<Level>()) {
+            Some(Ok(level)) => Ok(level),
+            _ => {
+                let err = io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!(
+                        "{}: '{value}', is not one of: {}",
+                        INI_KEYS[7],
+                        DisplayVec(&LOG_LEVELS)
+                    ),
+                );
+                Err(self.save_default_val(INI_SECTIONS[0], INI_KEYS[7], err))
+            }
+        }
+    }
+
+    /// returns the method saved with key "launch_method", `DEFAULT_LAUNCH_METHOD` if the key is missing  
+    /// if the value is not one of `LAUNCH_METHODS` calls `self.save_default_val` to correct error
+    pub fn get_launch_method(&self) -> io::Result<LaunchMethod> {
+        let Some(value) = self.data.get_from(INI_SECTIONS[0], INI_KEYS[12]) else {
+            return Ok(DEFAULT_LAUNCH_METHOD);
+        };
+        value.parse::<LaunchMethod>().map_err(|err| {
+            let err = io::Error::new(ErrorKind::InvalidData, format!("{}: {err}", INI_KEYS[12]));
+            self.save_default_val(INI_SECTIONS[0], INI_KEYS[12], err)
+        })
+    }
+
+    /// returns the level saved with key "confirm_level", `DEFAULT_CONFIRM_LEVEL` if the key is missing  
+    /// if the value is not one of `CONFIRM_LEVELS` calls `self.save_default_val` to correct error
+    pub fn get_confirm_level(&self) -> io::Result<ConfirmLevel> {
+        let Some(value) = self.data.get_from(INI_SECTIONS[0], INI_KEYS[13]) else {
+            return Ok(DEFAULT_CONFIRM_LEVEL);
+        };
+        value.parse::<ConfirmLevel>().map_err(|err| {
+            let err = io::Error::new(ErrorKind::InvalidData, format!("{}: {err}", INI_KEYS[13]));
+            self.save_default_val(INI_SECTIONS[0], INI_KEYS[13], err)
+        })
+    }
+
+    /// returns the executable saved with key "editor" that is used to open config files  
+    /// if no editor is saved returns `DEFAULT_EDITOR`
+    pub fn get_editor(&self) -> String {
+        self.data
+            .get_from(INI_SECTIONS[0], INI_KEYS[3])
+            .map(str::trim)
+            .filter(|editor| !editor.is_empty())
+            .unwrap_or(DEFAULT_EDITOR)
+            .to_string()
+    }
+
+    /// returns the number of file names to show when confirming an install saved with key "install_preview_limit"  
+    /// returns `DEFAULT_INSTALL_PREVIEW_LIMIT` if the key is missing or not within `INSTALL_PREVIEW_RANGE`
+    pub fn get_install_preview_limit(&self) -> usize {
+        let Some(value) = self.data.get_from(INI_SECTIONS[0], INI_KEYS[5]) else {
+            return DEFAULT_INSTALL_PREVIEW_LIMIT;
+        };
+        match value.trim().parse::<usize>() {
+            Ok(limit) if INSTALL_PREVIEW_RANGE.contains(&limit) => limit,
+            _ => {
+                warn!(
+                    "{}: '{value}', is not within {INSTALL_PREVIEW_RANGE:?}, using default: {DEFAULT_INSTALL_PREVIEW_LIMIT}",
+                    INI_KEYS[5]
+                );
+                DEFAULT_INSTALL_PREVIEW_LIMIT
+            }
+        }
+    }
+
+    /// returns the decoded note saved for the mod with `key`, `None` if no note is saved
+    pub fn get_mod_note(&self, key: &str) -> Option<String> {
+        self.data
+            .get_from(NOTES_SECTION, &format_key(key))
+            .map(unescape_note)
+    }
+
+    /// saves `note` for the mod with `key` in section "descriptions", an empty note removes the entry  
+    /// newlines and quotes are escaped so the note is stored as a single line  
+    /// **Note:** this does not write the changes to file
+    pub fn set_mod_note(&mut self, key: &str, note: &str) {
+        let key = format_key(key);
+        let note = note.trim();
+        if note.is_empty() {
+            self.data.delete_from(NOTES_SECTION, &key);
+            return;
+        }
+        self.set(NOTES_SECTION, &key, &escape_note(note));
+    }
+
+    /// returns `true` if the mod with `key` is pinned to the top of the mod list
+    pub fn is_pinned(&self, key: &str) -> bool {
+        self.data
+            .get_from(PINNED_SECTION, &format_key(key))
+            .is_some_and(|pinned| parse_bool(pinned).unwrap_or(false))
+    }
+
+    /// pins or unpins the mod with `key` in section "pinned-mods"  
+    /// pinning only changes where the mod is displayed, load order is not modified  
+    /// **Note:** this does not write the changes to file
+    pub fn set_pinned(&mut self, key: &str, pinned: bool) {
+        let key = format_key(key);
+        if pinned {
+            self.set(PINNED_SECTION, &key, "true");
+        } else {
+            self.data.delete_from(PINNED_SECTION, &key);
+        }
+    }
+
+    /// returns `true` if the mod with `key` is shown in the favorites bar
+    pub fn is_favorite(&self, key: &str) -> bool {
+        self.data
+            .get_from(FAVORITES_SECTION, &format_key(key))
+            .is_some()
+    }
+
+    /// returns the keys saved in section "favorites" in the order they were added
+    pub fn favorites(&self) -> Vec<String> {
+        self.data
+            .section(FAVORITES_SECTION)
+            .map(|favorites| favorites.iter().map(|(k, _)| k.to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    /// adds the mod with `key` to the favorites bar, or removes it if it is already a favorite  
+    /// returns `true` if the mod is now a favorite, errors if there are already `MAX_FAVORITES`  
+    /// **Note:** this does not write the changes to file
+    pub fn toggle_favorite(&mut self, key: &str) -> io::Result<bool> {
+        let key = format_key(key);
+        if self.data.delete_from(FAVORITES_SECTION, &key).is_some() {
+            return Ok(false);
+        }
+        if self.favorites().len() >= MAX_FAVORITES {
+            return new_io_error!(
+                ErrorKind::InvalidInput,
+                format!("Only {MAX_FAVORITES} mods can be favorites, remove a favorite first")
+            );
+        }
+        self.set(FAVORITES_SECTION, &key, "true");
+        Ok(true)
+    }
+
+    /// removes favorites that are no longer registered, returns the keys that were removed  
+    /// **Note:** this does not write the changes to file
+    pub fn prune_favorites(&mut self) -> Vec<String> {
+        let registered = self
+            .data
+            .section(INI_SECTIONS[2])
+            .map(|mods| mods.iter().map(|(k, _)| canonical_key(k)).collect::<HashSet<_>>())
+            .unwrap_or_default();
+        let stale = self
+            .favorites()
+            .into_iter()
+            .filter(|key| !registered.contains(&canonical_key(key)))
+            .collect::<Vec<_>>();
+        stale.iter().for_each(|key| {
+            self.data.delete_from(FAVORITES_SECTION, key);
+        });
+        stale
+    }
+
+    /// returns `true` if the `.ini` files of the mod with `key` are toggled along with its `.dll` files  
+    /// see `RegMod::set_toggle_configs`
+    pub fn toggles_configs(&self, key: &str) -> bool {
+        self.data
+            .get_from(TOGGLE_CONFIGS_SECTION, &format_key(key))
+            .is_some_and(|toggle| parse_bool(toggle).unwrap_or(false))
+    }
+
+    /// returns the category saved for the mod with `key`, `None` if the mod is uncategorized
+    pub fn get_category(&self, key: &str) -> Option<&str> {
+        self.data
+            .get_from(CATEGORIES_SECTION, &format_key(key))
+            .map(str::trim)
+            .filter(|category| !category.is_empty())
+    }
+
+    /// saves `category` for the mod with `key` in section "categories"  
+    /// an empty category or `DEFAULT_CATEGORY` removes the entry, categories are display only  
+    /// **Note:** this does not write the changes to file
+    pub fn set_category(&mut self, key: &str, category: &str) {
+        let key = format_key(key);
+        let category = category.trim();
+        if category.is_empty() || category.eq_ignore_ascii_case(DEFAULT_CATEGORY) {
+            self.data.delete_from(CATEGORIES_SECTION, &key);
+            return;
         }
+        self.set(CATEGORIES_SECTION, &key, category);
+    }
+
+    /// returns the saved recent game directories, most recent first  
+    /// entries that no longer contain all `REQUIRED_GAME_FILES` are skipped
+    pub fn recent_game_dirs(&self) -> Vec<PathBuf> {
+        let Some(recent_dirs) = self.data.section(RECENT_DIRS_SECTION) else {
+            return Vec::new();
+        };
+        recent_dirs
+            .iter()
+            .map(|(_, dir)| PathBuf::from(dir))
+            .filter(|dir| {
+                files_not_found(dir, &REQUIRED_GAME_FILES)
+                    .is_ok_and(|not_found| not_found.is_empty())
+            })
+            .take(MAX_RECENT_DIRS)
+            .collect()
+    }
+
+    /// moves `dir` to the front of the recent game directories keeping at most `MAX_RECENT_DIRS`  
+    /// entries that are no longer valid are dropped, then the changes are written to file
+    #[instrument(level = "trace", skip(self))]
+    pub fn add_recent_game_dir(&mut self, dir: &Path) -> io::Result<()> {
+        let mut recent_dirs = self.recent_game_dirs();
+        recent_dirs.retain(|recent| recent != dir);
+        recent_dirs.insert(0, PathBuf::from(dir));
+        recent_dirs.truncate(MAX_RECENT_DIRS);
+        self.set_recent_game_dirs(&recent_dirs)
+    }
+
+    /// removes saved recent game directories that no longer contain all `REQUIRED_GAME_FILES`  
+    /// only writes to file if an entry was removed
+    #[instrument(level = "trace", skip_all)]
+    pub fn prune_recent_game_dirs(&mut self) -> io::Result<()> {
+        let saved = self.data.section(RECENT_DIRS_SECTION).map_or(0, |dirs| dirs.len());
+        let recent_dirs = self.recent_game_dirs();
+        if recent_dirs.len() == saved {
+            return Ok(());
+        }
+        info!("Removed: {} invalid recent game directories", saved - recent_dirs.len());
+        self.set_recent_game_dirs(&recent_dirs)
+    }
+
+    fn set_recent_game_dirs(&mut self, dirs: &[PathBuf]) -> io::Result<()> {
+        self.data.delete(RECENT_DIRS_SECTION);
+        dirs.iter().enumerate().for_each(|(i, dir)| {
+            self.set(RECENT_DIRS_SECTION, &i.to_string(), &dir.to_string_lossy())
+        });
+        self.write_to_file()
     }
 
     /// replaces invalid entries with valid ones and returns a message to display to the user if so  
@@ -207,11 +580,15 @@ impl Cfg {
                         if !keys.insert(k) {
                             keys_to_remove.push(k.to_owned());
                         }
-                        if let Err(err) = parse_bool(v) {
-                            let msg = err.into_io_error(k, v).to_string();
-                            info!("{msg}");
-                            messages.push(msg);
-                            *v = String::from("true");
+                        match parse_bool(v) {
+                            // normalize accepted forms so they are written back as `true` | `false`
+                            Ok(state) => *v = state.to_string(),
+                            Err(err) => {
+                                let msg = err.into_io_error(k, v).to_string();
+                                info!("{msg}");
+                                messages.push(msg);
+                                *v = String::from("true");
+                            }
                         }
                         (keys, keys_to_remove)
                     },
@@ -238,11 +615,293 @@ impl Cfg {
                 last_key
             });
         }
+        messages.extend(self.reset_bool_settings());
+        messages.extend(self.repair_file_arrays());
+        self.prune_favorites().into_iter().for_each(|key| {
+            let msg = format!(
+                "Removed favorite: {}, mod is no longer registered",
+                DisplayName(&key)
+            );
+            info!("{msg}");
+            messages.push(msg);
+        });
+        self.deduplicate_files()
+            .into_iter()
+            .for_each(|(owner, removed_from, file)| {
+                let msg = format!(
+                    "File: '{}', was registered to both: {} and {}, it was removed from: {}",
+                    file.display(),
+                    DisplayName(&owner),
+                    DisplayName(&removed_from),
+                    DisplayName(&removed_from)
+                );
+                info!("{msg}");
+                messages.push(msg);
+            });
         if !messages.is_empty() {
             return Err(messages);
         }
         Ok(())
     }
+
+    /// finds short paths that are registered to more than one mod, the first mod to register the path  
+    /// keeps it and it is removed from all others, arrays left with one file are collapsed to a single entry  
+    /// paths are compared ignoring case and `OFF_STATE`, mods left with no files are removed  
+    /// returns (`owner_key`, `removed_from_key`, `short_path`) for each conflict resolved  
+    ///
+    /// **Note:** this does not write the changes to file
+    #[instrument(level = "trace", skip_all)]
+    pub fn deduplicate_files(&mut self) -> Vec<(String, String, PathBuf)> {
+        let Some(mod_files) = self.data.section_mut(INI_SECTIONS[3]) else {
+            return Vec::new();
+        };
+        let (mut grouped, _) = group_mod_files(mod_files);
+        let mut owners: HashMap<String, &str> = HashMap::new();
+        let mut conflicts = Vec::new();
+        for (key, _, files) in grouped.iter_mut() {
+            files.retain(|&file| {
+                let on_state = file.strip_suffix(OFF_STATE).unwrap_or(file).to_lowercase();
+                match owners.get(&on_state) {
+                    Some(&owner) if owner != *key => {
+                        conflicts.push((owner.to_string(), key.to_string(), PathBuf::from(file)));
+                        false
+                    }
+                    Some(_) => true,
+                    None => {
+                        owners.insert(on_state, *key);
+                        true
+                    }
+                }
+            });
+        }
+        if conflicts.is_empty() {
+            trace!("no files are registered to more than one mod");
+            return conflicts;
+        }
+        let emptied = grouped
+            .iter()
+            .filter(|(_, _, files)| files.is_empty())
+            .map(|(key, _, _)| key.to_string())
+            .collect::<Vec<_>>();
+        let deduplicated = ungroup_mod_files(grouped);
+        *mod_files = deduplicated;
+        emptied.iter().for_each(|key| {
+            self.data.delete_from(INI_SECTIONS[2], key);
+            info!(
+                "{} was left with no files, it was removed from: {INI_NAME}",
+                DisplayName(key)
+            );
+        });
+        conflicts
+    }
+
+    /// repairs malformed arrays in "mod-files" and returns a message for each entry removed  
+    /// - `array[]` rows not preceded by an `array` key are removed  
+    /// - `array` keys with no `array[]` rows are removed along with their state  
+    /// - `array` keys with a single `array[]` row are converted to a plain `key = value`  
+    ///
+    /// **Note:** this does not write the changes to file
+    #[instrument(level = "trace", skip_all)]
+    pub fn repair_file_arrays(&mut self) -> Vec<String> {
+        let Some(mod_files) = self.data.section_mut(INI_SECTIONS[3]) else {
+            return Vec::new();
+        };
+        let (grouped, orphaned) = group_mod_files(mod_files);
+        let mut messages = orphaned
+            .into_iter()
+            .map(|file| {
+                format!(
+                    "Found file: {file}, not saved with a registered mod, it was removed from: {INI_NAME}"
+                )
+            })
+            .collect::<Vec<_>>();
+        let mut update = !messages.is_empty();
+        let mut emptied = Vec::new();
+        grouped
+            .iter()
+            .filter(|(_, is_array, _)| *is_array)
+            .for_each(|(key, _, files)| match files.len() {
+                0 => {
+                    messages.push(format!(
+                        "Found mod: {}, saved with no files, it was removed from: {INI_NAME}",
+                        DisplayName(key)
+                    ));
+                    emptied.push(key.to_string());
+                    update = true;
+                }
+                1 => {
+                    trace!(key, "array with one file converted to a single entry");
+                    update = true;
+                }
+                _ => (),
+            });
+        messages.iter().for_each(|msg| info!("{msg}"));
+        if update {
+            let repaired = ungroup_mod_files(grouped);
+            *mod_files = repaired;
+        }
+        emptied.iter().for_each(|key| {
+            self.data.delete_from(INI_SECTIONS[2], key);
+        });
+        messages
+    }
+
+    /// moves the state, file, note, pin, favorite and category entries saved with `old_key` to a new _snake_case_ key made from `new_name`  
+    /// the position of all entries within their section is preserved and changes are written to file  
+    ///
+    /// load order entries stored in `loader_dir` are keyed by `.dll` file name so they are left unmodified  
+    #[instrument(level = "trace", skip(self, loader_dir))]
+    pub fn rename_mod(
+        &mut self,
+        old_key: &str,
+        new_name: &str,
+        loader_dir: &Path,
+    ) -> io::Result<()> {
+        let new_key = format_key(new_name);
+        if new_key.is_empty() {
+            return new_io_error!(ErrorKind::InvalidInput, "New mod name can not be empty");
+        }
+        if new_key == old_key {
+            trace!("new name is the same as the old name");
+            return Ok(());
+        }
+        let keys = self.keys();
+        if !keys.contains(&canonical_key(old_key)) {
+            return new_io_error!(
+                ErrorKind::NotFound,
+                format!("{} is not a registered mod", DisplayName(old_key))
+            );
+        }
+        if canonical_key(&new_key) != canonical_key(old_key)
+            && keys.contains(&canonical_key(&new_key))
+        {
+            return new_io_error!(
+                ErrorKind::AlreadyExists,
+                format!(
+                    "There is already a registered mod with the name: {}",
+                    DisplayName(&new_key)
+                )
+            );
+        }
+        let old_canonical = canonical_key(old_key);
+        for section in [INI_SECTIONS[2], INI_SECTIONS[3]] {
+            let properties = self
+                .data
+                .section_mut(section)
+                .expect("Validated by Ini::is_setup on startup");
+            let mut renamed = ini::Properties::new();
+            properties.iter().for_each(|(k, v)| {
+                let is_old_key = k != ARRAY_KEY && canonical_key(k) == old_canonical;
+                renamed.append(if is_old_key { new_key.as_str() } else { k }, v)
+            });
+            std::mem::swap(properties, &mut renamed);
+        }
+        for section in [
+            NOTES_SECTION,
+            PINNED_SECTION,
+            FAVORITES_SECTION,
+            CATEGORIES_SECTION,
+            TRACKED_DIRS_SECTION,
+            TOGGLE_CONFIGS_SECTION,
+        ] {
+            let Some(saved_key) = self.data.section(section).and_then(|properties| {
+                properties
+                    .iter()
+                    .map(|(k, _)| k)
+                    .find(|k| canonical_key(k) == old_canonical)
+                    .map(String::from)
+            }) else {
+                continue;
+            };
+            if let Some(value) = self.data.delete_from(section, &saved_key) {
+                self.set(section, &new_key, &value);
+            }
+        }
+        self.write_to_file()?;
+        trace!(loader = %loader_dir.display(), "load order entries are not keyed by mod name, left unmodified");
+        info!(
+            "Renamed: {}, to: {}",
+            DisplayName(old_key),
+            DisplayName(&new_key)
+        );
+        Ok(())
+    }
+}
+
+/// escapes backslashes, newlines and quotes so `note` can be saved as a single ini value
+fn escape_note(note: &str) -> String {
+    let mut escaped = String::with_capacity(note.len());
+    note.chars().for_each(|c| match c {
+        '\\' => escaped.push_str("\\\\"),
+        '\n' => escaped.push_str("\\n"),
+        '\r' => escaped.push_str("\\r"),
+        '"' => escaped.push_str("\\\""),
+        '\'' => escaped.push_str("\\'"),
+        _ => escaped.push(c),
+    });
+    escaped
+}
+
+/// reverses `escape_note`
+fn unescape_note(stored: &str) -> String {
+    let mut note = String::with_capacity(stored.len());
+    let mut chars = stored.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            note.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => note.push('\n'),
+            Some('r') => note.push('\r'),
+            Some(escaped) => note.push(escaped),
+            None => note.push(c),
+        }
+    }
+    note
+}
+
+/// (`key`, `is_array`, `files`)
+type GroupedFiles<'a> = Vec<(&'a str, bool, Vec<&'a str>)>;
+
+/// groups the entries of section "mod-files" by key  
+/// returns (`grouped_files`, `orphaned_files`) where `orphaned_files` are `array[]` rows that are  
+/// not preceded by an `array` key
+fn group_mod_files(mod_files: &ini::Properties) -> (GroupedFiles<'_>, Vec<&str>) {
+    let mut grouped: GroupedFiles = Vec::with_capacity(mod_files.len());
+    let mut orphaned = Vec::new();
+    for (k, v) in mod_files.iter() {
+        if k == ARRAY_KEY {
+            match grouped.last_mut() {
+                Some((_, true, files)) => files.push(v),
+                _ => orphaned.push(v),
+            }
+        } else if v == ARRAY_VALUE {
+            grouped.push((k, true, Vec::new()));
+        } else {
+            grouped.push((k, false, vec![v]));
+        }
+    }
+    (grouped, orphaned)
+}
+
+/// rebuilds section "mod-files" from `group_mod_files`, keys with no files are skipped  
+/// and keys with a single file are saved as a plain `key = value`
+fn ungroup_mod_files(grouped: GroupedFiles<'_>) -> ini::Properties {
+    let mut mod_files = ini::Properties::new();
+    for (key, _, files) in grouped {
+        match files.len() {
+            0 => info!("{} has no files registered", DisplayName(key)),
+            1 => mod_files.append(key, files[0]),
+            _ => {
+                mod_files.append(key, ARRAY_VALUE);
+                files
+                    .into_iter()
+                    .for_each(|file| mod_files.append(ARRAY_KEY, file));
+            }
+        }
+    }
+    mod_files
 }
 
 #[derive(Debug)]
@@ -316,28 +975,18 @@ impl Config for ModLoaderCfg {
 
     #[inline]
     fn mods_is_empty(&self) -> bool {
-        self.data.section(LOADER_SECTIONS[1]).is_none()
-            || self
-                .data
-                .section(LOADER_SECTIONS[1])
-                .expect("ModLoader installed and verified")
-                .is_empty()
+        self.section().is_empty()
     }
 
+    #[inline]
     fn mods_registered(&self) -> usize {
-        if self.mods_is_empty() {
-            0
-        } else {
-            self.data
-                .section(LOADER_SECTIONS[1])
-                .expect("ModLoader installed and verified")
-                .len()
-        }
+        self.section().len()
     }
 
     #[inline]
     fn write_to_file(&self) -> io::Result<()> {
-        self.data.write_to_file_opt(&self.dir, EXT_OPTIONS)
+        backup_file(&self.dir);
+        write_atomic(&self.data, &self.dir, EXT_OPTIONS)
     }
 
     fn save_default_val(
@@ -346,11 +995,8 @@ impl Config for ModLoaderCfg {
         key: &str,
         mut in_err: io::Error,
     ) -> io::Error {
-        let default_val = match key {
-            k if k == LOADER_KEYS[0] => DEFAULT_LOADER_VALUES[0],
-            k if k == LOADER_KEYS[1] => DEFAULT_LOADER_VALUES[1],
-            _ => panic!("Key: {key}, is unknown to: {}", LOADER_FILES[3]),
-        };
+        let default_val = default_loader_value(key)
+            .unwrap_or_else(|| panic!("Key: {key}, is unknown to: {}", LOADER_FILES[3]));
         if let Err(err) = save_value_ext(&self.dir, section, key, default_val) {
             in_err.add_msg(&err.to_string(), false);
         } else {
@@ -362,43 +1008,84 @@ impl Config for ModLoaderCfg {
 
 impl ModLoaderCfg {
     /// returns value stored with key "load_delay" as `u32`  
+    /// a value outside of `LOAD_DELAY_RANGE` is clamped and saved to file  
     /// if error calls `self.save_default_val` to correct error  
     pub fn get_load_delay(&self) -> io::Result<u32> {
         match IniProperty::<u32>::read(&self.data, LOADER_SECTIONS[0], LOADER_KEYS[0]) {
-            Ok(delay_time) => {
+            Ok(delay_time) if LOAD_DELAY_RANGE.contains(&delay_time.value) => {
                 info!("Load delay: {}", DisplayTime(delay_time.value));
                 Ok(delay_time.value)
             }
+            Ok(delay_time) => {
+                let clamped =
+                    delay_time.value.clamp(*LOAD_DELAY_RANGE.start(), *LOAD_DELAY_RANGE.end());
+                warn!(
+                    "{}: {}, is not within {LOAD_DELAY_RANGE:?}, reset to: {}",
+                    LOADER_KEYS[0],
+                    DisplayTime(delay_time.value),
+                    DisplayTime(clamped)
+                );
+                let delay = clamped.to_string();
+                save_value_ext(&self.dir, LOADER_SECTIONS[0], LOADER_KEYS[0], &delay)?;
+                Ok(clamped)
+            }
             Err(err) => Err(self.save_default_val(LOADER_SECTIONS[0], LOADER_KEYS[0], err)),
         }
     }
 
     /// returns value stored with key "show_terminal" as `bool`  
     /// if error calls `self.save_default_val` to correct error  
+    #[inline]
     pub fn get_show_terminal(&self) -> io::Result<bool> {
-        match IniProperty::<bool>::read(&self.data, LOADER_SECTIONS[0], LOADER_KEYS[1]) {
-            Ok(show_terminal) => {
-                info!("Show terminal: {}", show_terminal.value);
-                Ok(show_terminal.value)
+        self.get_bool_key(LOADER_KEYS[1])
+    }
+
+    /// returns the value stored with `key` in section "modloader" as `bool`  
+    /// if error calls `self.save_default_val` to correct error  
+    ///
+    /// **Note:** `key` must be one of `LOADER_KEYS`
+    pub fn get_bool_key(&self, key: &str) -> io::Result<bool> {
+        match IniProperty::<bool>::read(&self.data, LOADER_SECTIONS[0], key) {
+            Ok(value) => {
+                info!("{key}: {}", value.value);
+                Ok(value.value)
             }
-            Err(err) => Err(self.save_default_val(LOADER_SECTIONS[0], LOADER_KEYS[1], err)),
+            Err(err) => Err(self.save_default_val(LOADER_SECTIONS[0], key, err)),
+        }
+    }
+
+    /// saves `value` with `key` in section "modloader" as "1" or "0", the form the mod loader expects  
+    /// only `key` is written to file, errors if `key` is not one of `LOADER_KEYS`
+    pub fn set_bool_key(&mut self, key: &str, value: bool) -> io::Result<()> {
+        if default_loader_value(key).is_none() {
+            return new_io_error!(
+                ErrorKind::InvalidInput,
+                format!("Key: {key}, is unknown to: {}", LOADER_FILES[3])
+            );
         }
+        let value = if value { "1" } else { "0" };
+        save_value_ext(&self.dir, LOADER_SECTIONS[0], key, value)?;
+        self.set(LOADER_SECTIONS[0], key, value);
+        Ok(())
     }
 
     /// retuns mutable reference to key value pairs stored in "loadorder"  
+    /// the section is created if it is missing, eg. on a `default()` or corrupted config
     #[inline]
     pub fn mut_section(&mut self) -> &mut ini::Properties {
         self.data
-            .section_mut(LOADER_SECTIONS[1])
-            .expect("ModLoader installed and verified")
+            .entry(LOADER_SECTIONS[1].map(String::from))
+            .or_insert_with(ini::Properties::new)
     }
 
     /// retuns immutable reference to key value pairs stored in "loadorder"  
+    /// a missing section is read as empty
     #[inline]
     pub fn section(&self) -> &ini::Properties {
+        static EMPTY_SECTION: OnceLock<ini::Properties> = OnceLock::new();
         self.data
             .section(LOADER_SECTIONS[1])
-            .expect("ModLoader installed and verified")
+            .unwrap_or_else(|| EMPTY_SECTION.get_or_init(ini::Properties::new))
     }
 
     /// get an iterator of the key value pairs stored in "loadorder"  
@@ -406,4 +1093,93 @@ impl ModLoaderCfg {
     pub fn iter(&self) -> ini::PropertyIter {
         self.section().iter()
     }
+
+    /// returns `true` if the load order was suspended by `suspend_order` and not yet resumed
+    pub fn order_suspended(&self) -> bool {
+        self.data
+            .section(SUSPENDED_ORDER_SECTION)
+            .is_some_and(|section| !section.is_empty())
+    }
+
+    /// returns the values stashed by `suspend_order` parsed the same as `parse_into_map`  
+    /// stashed values are never renormalized so they are restored exactly as they were saved
+    pub fn suspended_order_map(&self) -> OrderMap {
+        self.data
+            .section(SUSPENDED_ORDER_SECTION)
+            .map(|section| {
+                section
+                    .iter()
+                    .filter_map(|(k, v)| Some((k.to_string(), v.parse::<usize>().ok()?)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// moves every entry of section "loadorder" into section "suspended-loadorder" and saves to file  
+    /// with no entries in "loadorder" the loader falls back to its default order  
+    /// errors if the load order is already suspended or no load order is set
+    pub fn suspend_order(&mut self) -> io::Result<()> {
+        if self.order_suspended() {
+            return new_io_error!(ErrorKind::InvalidInput, "Load order is already suspended");
+        }
+        if self.mods_is_empty() {
+            return new_io_error!(ErrorKind::InvalidInput, "No load order is set to suspend");
+        }
+        let stashed = std::mem::take(self.mut_section());
+        for (k, v) in stashed.iter() {
+            self.set(SUSPENDED_ORDER_SECTION, k, v);
+        }
+        self.write_to_file()?;
+        info!("Suspended load order of {} files", stashed.len());
+        Ok(())
+    }
+
+    /// restores the entries stashed by `suspend_order` to section "loadorder" and saves to file  
+    /// a stashed value replaces any value set for the same key while the load order was suspended  
+    /// errors if the load order is not suspended
+    pub fn resume_order(&mut self) -> io::Result<()> {
+        if !self.order_suspended() {
+            return new_io_error!(ErrorKind::InvalidInput, "Load order is not suspended");
+        }
+        let stashed = self
+            .data
+            .delete(SUSPENDED_ORDER_SECTION)
+            .expect("checked by `order_suspended`");
+        for (k, v) in stashed.iter() {
+            self.mut_section().insert(k, v);
+        }
+        self.write_to_file()?;
+        info!("Resumed load order of {} files", stashed.len());
+        Ok(())
+    }
+}
+
+/// returns the default value of `key` in section "modloader", `None` if `key` is not in `LOADER_KEYS`
+pub fn default_loader_value(key: &str) -> Option<&'static str> {
+    LOADER_KEYS
+        .iter()
+        .position(|k| *k == key)
+        .map(|i| DEFAULT_LOADER_VALUES[i])
+}
+
+/// parses user input into a load delay in milliseconds, an optional trailing "ms" is ignored  
+/// errors if `input` is not a whole number within `LOAD_DELAY_RANGE`
+pub fn parse_load_delay(input: &str) -> io::Result<u32> {
+    let trimmed = input.trim();
+    let number = trimmed
+        .strip_suffix("ms")
+        .or_else(|| trimmed.strip_suffix("MS"))
+        .unwrap_or(trimmed)
+        .trim();
+    match number.parse::<u32>() {
+        Ok(delay) if LOAD_DELAY_RANGE.contains(&delay) => Ok(delay),
+        _ => new_io_error!(
+            ErrorKind::InvalidInput,
+            format!(
+                "Load delay: '{input}', must be a whole number from {} to {}",
+                DisplayTime(LOAD_DELAY_RANGE.start()),
+                DisplayTime(LOAD_DELAY_RANGE.end())
+            )
+        ),
+    }
 }
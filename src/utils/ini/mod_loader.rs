@@ -1,19 +1,25 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
     io::ErrorKind,
     path::{Path, PathBuf},
 };
 use tracing::{info, instrument, trace, warn};
 
 use crate::{
-    does_dir_contain,
-    utils::ini::{
-        common::{Config, ModLoaderCfg},
-        parser::RegMod,
-        writer::new_cfg,
+    check_game_closed, does_dir_contain, file_name_from_str, loader_cfg_path, new_io_error,
+    omit_off_state, rename_with_retry,
+    utils::{
+        display::{DisplayAntiCheatMsg, ModError},
+        error::EmlError,
+        ini::{
+            common::{Config, ModLoaderCfg},
+            parser::RegMod,
+            writer::{new_cfg, write_entry_in_place, EXT_OPTIONS},
+        },
+        subscriber::OpTimer,
     },
-    DisplayState, DisplayVec, DllSet, Operation, OperationResult, OrderMap, ANTI_CHEAT_EXE,
-    LOADER_EXAMPLE, LOADER_FILES,
+    DisplayState, DllSet, Operation, OperationResult, OrderMap, ANTI_CHEAT_EXE, LOADER_EXAMPLE,
+    LOADER_FILES, LOADER_SECTIONS, OFF_STATE,
 };
 
 #[derive(Debug, Default)]
@@ -22,6 +28,7 @@ pub struct ModLoader {
     disabled: bool,
     anti_cheat_toggle_installed: bool,
     anti_cheat_enabled: bool,
+    renamed_hook: Option<String>,
     path: PathBuf,
 }
 
@@ -32,7 +39,7 @@ impl ModLoader {
     /// can only error if it finds loader hook installed && "elden_mod_loader_config.ini" is not found so it fails on writing a new one to disk
     #[instrument(level = "trace", name = "mod_loader_properties", skip_all)]
     pub fn properties(game_dir: &Path) -> std::io::Result<ModLoader> {
-        let mut cfg_dir = game_dir.join(LOADER_FILES[3]);
+        let mut cfg_dir = loader_cfg_path(game_dir);
         let mut properties = ModLoader::default();
         let search_for = LOADER_FILES
             .iter()
@@ -73,6 +80,9 @@ impl ModLoader {
                 }
                 if files.contains(LOADER_FILES[3]) {
                     std::mem::swap(&mut cfg_dir, &mut properties.path);
+                    if !properties.installed {
+                        properties.renamed_hook = find_renamed_hook(game_dir)?;
+                    }
                 }
             }
             Err(err) => return Err(err),
@@ -83,7 +93,9 @@ impl ModLoader {
             new_cfg(&cfg_dir)?;
             properties.path = cfg_dir;
         }
-        if !properties.installed {
+        if let Some(ref file_name) = properties.renamed_hook {
+            warn!("Mod loader dll hook: {}, found saved as: {file_name}", LOADER_FILES[1]);
+        } else if !properties.installed {
             warn!("Mod loader dll hook: {}, not found", LOADER_FILES[1]);
         } else {
             trace!(dll_hook = %DisplayState(!properties.disabled), "elden_mod_loader files found");
@@ -98,10 +110,60 @@ impl ModLoader {
             disabled,
             anti_cheat_toggle_installed: false,
             anti_cheat_enabled: false,
+            renamed_hook: None,
             path: PathBuf::new(),
         }
     }
 
+    /// renames a dll hook found by `ModLoader::properties()` saved with an unknown name back to  
+    /// its canonical enabled or disabled name, on success `self` is updated to reflect the installed state  
+    #[instrument(level = "trace", skip_all)]
+    pub fn restore_renamed_hook(&mut self, game_dir: &Path) -> std::io::Result<()> {
+        let Some((from, to)) = self.renamed_hook() else {
+            return new_io_error!(ErrorKind::NotFound, "No renamed dll hook was found");
+        };
+        std::fs::rename(game_dir.join(from), game_dir.join(to))?;
+        info!("Renamed: {from}, to: {to}");
+        self.disabled = to == LOADER_FILES[0];
+        self.installed = true;
+        self.renamed_hook = None;
+        Ok(())
+    }
+
+    /// renames the dll hook between `LOADER_FILES[1]` and `LOADER_FILES[0]`, does nothing if it is already  
+    /// in the requested state, this does not update `self` see `LoaderBackend::set_enabled`  
+    /// errors if Elden Ring is running, the hook is not found or the hook is saved as `LOADER_FILES[2]`  
+    /// by the anti-cheat toggle, the hook must then be toggled with `ANTI_CHEAT_EXE`
+    #[instrument(level = "trace", skip(self, game_dir))]
+    pub fn set_enabled(&self, game_dir: &Path, enabled: bool) -> std::io::Result<()> {
+        check_game_closed()?;
+        if game_dir.join(LOADER_FILES[2]).is_file() {
+            return new_io_error!(ErrorKind::PermissionDenied, DisplayAntiCheatMsg.to_string());
+        }
+        let (from, to) = if enabled {
+            (LOADER_FILES[0], LOADER_FILES[1])
+        } else {
+            (LOADER_FILES[1], LOADER_FILES[0])
+        };
+        if game_dir.join(to).is_file() {
+            trace!(to, "dll hook is already in the requested state");
+            return Ok(());
+        }
+        let from_path = game_dir.join(from);
+        if !from_path.is_file() {
+            return new_io_error!(
+                ErrorKind::NotFound,
+                format!("Mod loader dll hook: {}, not found", LOADER_FILES[1])
+            );
+        }
+        rename_with_retry(&from_path, &game_dir.join(to)).map_err(|mut err| {
+            err.add_msg(&format!("could not rename: {from}, the file may be in use"), false);
+            err
+        })?;
+        info!("Renamed: {from}, to: {to}");
+        Ok(())
+    }
+
     #[inline]
     pub fn installed(&self) -> bool {
         self.installed
@@ -122,6 +184,19 @@ impl ModLoader {
         self.anti_cheat_enabled
     }
 
+    /// returns (`found_name`, `canonical_name`) if the dll hook was found saved with an unknown name  
+    /// call `restore_renamed_hook()` to rename the file back to `canonical_name`
+    #[inline]
+    pub fn renamed_hook(&self) -> Option<(&str, &'static str)> {
+        self.renamed_hook.as_deref().map(|file_name| {
+            if file_name.to_lowercase().ends_with(OFF_STATE) {
+                (file_name, LOADER_FILES[0])
+            } else {
+                (file_name, LOADER_FILES[1])
+            }
+        })
+    }
+
     #[inline]
     pub fn path(&self) -> &Path {
         &self.path
@@ -133,10 +208,193 @@ impl ModLoader {
     }
 }
 
+/// the default `LoaderBackend`, the _elden_mod_loader_ dll hook by TechieW
+pub type DinputLoader = ModLoader;
+
+/// operations the app needs from the installed mod loader  
+/// the app only talks to the loader through this trait so other loaders can be added as a new backend
+pub trait LoaderBackend: std::fmt::Debug + Send {
+    /// name of the loader to display to the user
+    fn name(&self) -> &'static str;
+
+    fn installed(&self) -> bool;
+
+    fn disabled(&self) -> bool;
+
+    fn anti_cheat_toggle_installed(&self) -> bool;
+
+    fn anti_cheat_enabled(&self) -> bool;
+
+    /// path to the config file the loader reads its settings and load order from
+    fn config_path(&self) -> &Path;
+
+    /// enables or disables the loader within `game_dir`, on success `self` reflects the new state
+    fn set_enabled(&mut self, game_dir: &Path, enabled: bool) -> std::io::Result<()>;
+
+    /// reads the settings and load order saved in `config_path()`
+    fn read_order(&self) -> std::io::Result<Box<dyn OrderStore>>;
+
+    /// an empty `OrderStore` for `config_path()`, used when the loader is not installed or its config can not be read
+    fn default_order(&self) -> Box<dyn OrderStore>;
+
+    /// returns (`found_name`, `canonical_name`) if the loader was found saved with an unknown name
+    fn renamed_hook(&self) -> Option<(&str, &'static str)> {
+        None
+    }
+
+    /// renames a loader found by `renamed_hook()` back to `canonical_name`
+    fn restore_renamed_hook(&mut self, _game_dir: &Path) -> std::io::Result<()> {
+        new_io_error!(ErrorKind::Unsupported, format!("{} can not be renamed", self.name()))
+    }
+}
+
+impl LoaderBackend for DinputLoader {
+    fn name(&self) -> &'static str {
+        "elden_mod_loader"
+    }
+
+    fn installed(&self) -> bool {
+        ModLoader::installed(self)
+    }
+
+    fn disabled(&self) -> bool {
+        ModLoader::disabled(self)
+    }
+
+    fn anti_cheat_toggle_installed(&self) -> bool {
+        ModLoader::anti_cheat_toggle_installed(self)
+    }
+
+    fn anti_cheat_enabled(&self) -> bool {
+        ModLoader::anti_cheat_enabled(self)
+    }
+
+    fn config_path(&self) -> &Path {
+        self.path()
+    }
+
+    fn set_enabled(&mut self, game_dir: &Path, enabled: bool) -> std::io::Result<()> {
+        ModLoader::set_enabled(self, game_dir, enabled)?;
+        self.disabled = !enabled;
+        Ok(())
+    }
+
+    fn read_order(&self) -> std::io::Result<Box<dyn OrderStore>> {
+        ModLoaderCfg::read(self.path()).map(|cfg| Box::new(cfg) as Box<dyn OrderStore>)
+    }
+
+    fn default_order(&self) -> Box<dyn OrderStore> {
+        Box::new(<ModLoaderCfg as Config>::default(self.path()))
+    }
+
+    fn renamed_hook(&self) -> Option<(&str, &'static str)> {
+        ModLoader::renamed_hook(self)
+    }
+
+    fn restore_renamed_hook(&mut self, game_dir: &Path) -> std::io::Result<()> {
+        ModLoader::restore_renamed_hook(self, game_dir)
+    }
+}
+
+/// settings and load order of a `LoaderBackend`, read with `LoaderBackend::read_order`  
+/// the app only reads and verifies the order a backend saves through this trait
+pub trait OrderStore: std::fmt::Debug + Send {
+    /// entries of the load order, keyed by `.dll` file name
+    fn order_section(&mut self) -> &mut ini::Properties;
+
+    /// writes any changes made to the load order to `LoaderBackend::config_path()`
+    fn write_order(&self) -> std::io::Result<()>;
+
+    /// see `ModLoaderCfg::verify_keys`
+    #[allow(clippy::result_large_err)]
+    fn verify_keys(&mut self, dlls: &DllSet, order_count: usize) -> Result<(), UnknownKeyErr>;
+
+    /// returns `true` if the load order is suspended
+    fn order_suspended(&self) -> bool;
+
+    /// the load order saved when the order was suspended, see `ModLoaderCfg::suspended_order_map`
+    fn suspended_order_map(&self) -> OrderMap;
+
+    /// see `ModLoaderCfg::parse_section`
+    fn parse_order(&mut self, unknown_keys: &HashSet<String>) -> std::io::Result<OrderMap>;
+
+    /// delay in ms before the loader loads mods
+    fn load_delay(&self) -> std::io::Result<u32>;
+
+    /// returns `true` if the loader opens a terminal when the game is launched
+    fn show_terminal(&self) -> std::io::Result<bool>;
+}
+
+impl OrderStore for ModLoaderCfg {
+    fn order_section(&mut self) -> &mut ini::Properties {
+        self.mut_section()
+    }
+
+    fn write_order(&self) -> std::io::Result<()> {
+        self.write_to_file()
+    }
+
+    fn verify_keys(&mut self, dlls: &DllSet, order_count: usize) -> Result<(), UnknownKeyErr> {
+        ModLoaderCfg::verify_keys(self, dlls, order_count)
+    }
+
+    fn order_suspended(&self) -> bool {
+        ModLoaderCfg::order_suspended(self)
+    }
+
+    fn suspended_order_map(&self) -> OrderMap {
+        ModLoaderCfg::suspended_order_map(self)
+    }
+
+    fn parse_order(&mut self, unknown_keys: &HashSet<String>) -> std::io::Result<OrderMap> {
+        self.parse_section(unknown_keys)
+    }
+
+    fn load_delay(&self) -> std::io::Result<u32> {
+        self.get_load_delay()
+    }
+
+    fn show_terminal(&self) -> std::io::Result<bool> {
+        self.get_show_terminal()
+    }
+}
+
+/// detects the mod loader installed in `game_dir`, currently only `DinputLoader` is supported  
+/// errors the same as `ModLoader::properties()`
+pub fn loader_backend(game_dir: &Path) -> std::io::Result<Box<dyn LoaderBackend>> {
+    DinputLoader::properties(game_dir).map(|loader| Box::new(loader) as Box<dyn LoaderBackend>)
+}
+
+/// returns the file name of the first `dinput8*.dll` found in `game_dir` that is not one of the known `LOADER_FILES`  
+/// also matches files that end in `.dll.disabled`
+#[instrument(level = "trace", skip_all)]
+fn find_renamed_hook(game_dir: &Path) -> std::io::Result<Option<String>> {
+    const HOOK_PREFIX: &str = "dinput8";
+    const DLL_EXT: &str = ".dll";
+    for entry in std::fs::read_dir(game_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let Some(file_name) = entry.file_name().to_str().map(String::from) else {
+            continue;
+        };
+        let lowercase = file_name.to_lowercase();
+        if LOADER_FILES.contains(&lowercase.as_str()) || !lowercase.starts_with(HOOK_PREFIX) {
+            continue;
+        }
+        if lowercase.ends_with(DLL_EXT) || lowercase.ends_with(&format!("{DLL_EXT}{OFF_STATE}")) {
+            trace!(file_name, "found renamed dll hook");
+            return Ok(Some(file_name));
+        }
+    }
+    Ok(None)
+}
+
 /// it is save to update the global `UNKNOWN_ORDER_KEYS` with `unknown_keys` if `is_some()`  
 /// this is because of the case a write to file fails `unknown_keys` will be `None`
 pub struct UnknownKeyErr {
-    pub err: std::io::Error,
+    pub err: EmlError,
     pub unknown_keys: Option<HashSet<String>>,
     pub update_ord_data: Option<OrdMetaData>,
 }
@@ -144,7 +402,7 @@ pub struct UnknownKeyErr {
 impl UnknownKeyErr {
     fn empty_with_err(err: std::io::Error) -> Self {
         UnknownKeyErr {
-            err,
+            err: EmlError::Io(err),
             unknown_keys: None,
             update_ord_data: None,
         }
@@ -166,12 +424,53 @@ impl OrdMetaData {
     }
 }
 
+/// classification of a value stored in section "loadorder"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderValue {
+    /// value was parsed into a `usize`
+    Known(usize),
+    /// value failed to parse and its key is a file _not_ registered with the app
+    Unknown,
+    /// value failed to parse and its key is a file registered with the app
+    Invalid,
+}
+
+impl OrderValue {
+    /// parses a value from section "loadorder", `unknown_key` is used to classify parse errors
+    pub fn parse(value: &str, unknown_key: bool) -> Self {
+        match value.parse::<usize>() {
+            Ok(v) => OrderValue::Known(v),
+            Err(_) if unknown_key => OrderValue::Unknown,
+            Err(_) => OrderValue::Invalid,
+        }
+    }
+
+    #[inline]
+    pub fn known(self) -> Option<usize> {
+        match self {
+            OrderValue::Known(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// value used for sorting entries, values that failed to parse are sorted to the end  
+    /// `Invalid` entries are placed before `Unknown` entries
+    #[inline]
+    fn sort_val(self) -> usize {
+        match self {
+            OrderValue::Known(v) => v,
+            OrderValue::Invalid => usize::MAX / 2,
+            OrderValue::Unknown => usize::MAX,
+        }
+    }
+}
+
 impl ModLoaderCfg {
     /// verifies that all keys stored in "elden_mod_loader_config.ini" are registered with the app  
     /// a _unknown_ file is found as a key this will change the order to be greater than _known_ files  
     /// `DllSet` and `order_count` are retrieved by calling `dll_set_order_count` on `Cfg`  
     ///
-    /// **Note:** if `UnknownKeyErr.err.kind() == Unsupported` then  
+    /// **Note:** if `UnknownKeyErr.err` is `EmlError::UnknownOrderKeys { order_changed: true, .. }` then  
     /// `update_order_entries()` & `self.write_to_file()` are called  
     /// as a result `OrdMetaData` is re-calculated and returned
     #[instrument(level = "trace", skip_all)]
@@ -188,9 +487,8 @@ impl ModLoaderCfg {
                 trace!("{LOADER_EXAMPLE} ignored");
                 continue;
             }
-            let curr_v = v.parse::<usize>().unwrap_or(42069);
             if dlls.contains(k) {
-                if curr_v != 42069 {
+                if let Some(curr_v) = OrderValue::parse(v, false).known() {
                     if let Some(ref mut prev_high) = high_order {
                         if curr_v > *prev_high {
                             *prev_high = curr_v;
@@ -201,7 +499,7 @@ impl ModLoaderCfg {
                 }
             } else {
                 unknown_keys.push(k.to_string());
-                unknown_vals.push(curr_v);
+                unknown_vals.push(OrderValue::parse(v, true));
             }
         }
         if unknown_keys.is_empty() {
@@ -215,6 +513,10 @@ impl ModLoaderCfg {
         };
         let mut no_user_vals_counter = 0_usize;
         unknown_keys.iter().zip(unknown_vals).for_each(|(k, v)| {
+            let Some(v) = v.known() else {
+                update_entry(k, 0);
+                return;
+            };
             if order_count == 0 {
                 if v != no_user_vals_counter && {
                     no_user_vals_counter += 1;
@@ -231,12 +533,12 @@ impl ModLoaderCfg {
                 update_entry(k, v);
             }
         });
+        let unknown_key_set = unknown_keys.iter().cloned().collect::<HashSet<_>>();
+        let err = EmlError::UnknownOrderKeys {
+            keys: unknown_keys,
+            order_changed: update_order,
+        };
         if update_order {
-            let err = std::io::Error::new(ErrorKind::Unsupported,
-                    format!("Found load order set for file(s) not registered with the app. One or more of the following key(s) order has been changed: {}", 
-                    DisplayVec(&unknown_keys))
-                );
-            let unknown_key_set = unknown_keys.into_iter().collect::<HashSet<_>>();
             let update_ord_data = self.update_order_entries(None, &unknown_key_set);
             self.write_to_file().map_err(UnknownKeyErr::empty_with_err)?;
             return Err(UnknownKeyErr {
@@ -246,15 +548,22 @@ impl ModLoaderCfg {
             });
         }
         Err(UnknownKeyErr {
-            err: std::io::Error::other(format!(
-                "Found load order set for the following file(s) not registered with the app: {}",
-                DisplayVec(&unknown_keys)
-            )),
-            unknown_keys: Some(unknown_keys.into_iter().collect::<HashSet<_>>()),
+            err,
+            unknown_keys: Some(unknown_key_set),
             update_ord_data: None,
         })
     }
 
+    /// returns each key in section "loadorder" that is not contained in `dlls` along with its value  
+    /// `LOADER_EXAMPLE` is skipped, values that fail to parse are returned as `usize::MAX`  
+    /// `DllSet` is retrieved by calling `dll_set_order_count` on `Cfg`
+    pub fn unregistered_keys(&self, dlls: &DllSet) -> Vec<(String, usize)> {
+        self.iter()
+            .filter(|(k, _)| *k != LOADER_EXAMPLE && !dlls.contains(k))
+            .map(|(k, v)| (k.to_string(), OrderValue::parse(v, true).sort_val()))
+            .collect()
+    }
+
     /// returns an owned `HashMap` with values parsed into K: `String`, V: `usize`  
     /// this function also fixes usize.parse() errors and if values are out of order
     #[instrument(level = "trace", skip_all)]
@@ -307,10 +616,40 @@ impl ModLoaderCfg {
     /// this will not filter out invalid entries, do not use unless you _know_ all entries are valid  
     pub fn parse_into_map(&self) -> OrderMap {
         self.iter()
-            .filter_map(|(k, v)| Some((k.to_string(), v.parse::<usize>().ok()?)))
+            .filter_map(|(k, v)| Some((k.to_string(), OrderValue::parse(v, false).known()?)))
             .collect::<OrderMap>()
     }
 
+    /// saves the insertion (`inserted` = `true`) or removal of the load order entry `key` that has  
+    /// already been made in memory, the value of `key` is kept stable, see `update_order_entries`  
+    /// if no other value needs to change only the line of `key` is written so comments and the layout  
+    /// of the file are preserved, otherwise all entries are renormalized and the file is rewritten
+    #[instrument(level = "trace", skip(self, unknown_keys))]
+    pub fn write_order_change(
+        &mut self,
+        key: &str,
+        inserted: bool,
+        unknown_keys: &HashSet<String>,
+    ) -> std::io::Result<OrdMetaData> {
+        let saved = self.section().clone();
+        let ord_meta_data = self.update_order_entries(inserted.then_some(key), unknown_keys);
+        let renormalized = saved.len() != self.section().len()
+            || self.iter().any(|(k, v)| saved.get(k) != Some(v));
+        if renormalized {
+            self.write_to_file()?;
+            trace!("renormalized and rewrote all load order entries");
+        } else {
+            write_entry_in_place(
+                self.path(),
+                LOADER_SECTIONS[1],
+                key,
+                self.section().get(key),
+                &EXT_OPTIONS,
+            )?;
+        }
+        Ok(ord_meta_data)
+    }
+
     /// updates the load order values in `Some("loadorder")` so there are no gaps in values  
     /// if you want a key's value to remain the unedited you can supply `Some(stable_key)`  
     /// this also calculates the correct max_order val (same logic appears in `[RegMod].max_order()`)  
@@ -323,6 +662,7 @@ impl ModLoaderCfg {
         stable: Option<&str>,
         unknown_keys: &HashSet<String>,
     ) -> OrdMetaData {
+        let _timer = OpTimer::start("update_order_entries");
         if self.mods_is_empty() {
             trace!("nothing to update");
             return OrdMetaData {
@@ -338,13 +678,11 @@ impl ModLoaderCfg {
                 info!("Removed: '{LOADER_EXAMPLE}' from: {}", LOADER_FILES[3]);
                 continue;
             }
-            let curr_v = v.parse::<usize>().unwrap_or_else(|_| {
-                if unknown_keys.contains(k) {
-                    usize::MAX
-                } else {
-                    usize::MAX / 2
-                }
-            });
+            let order_val = OrderValue::parse(v, unknown_keys.contains(k));
+            if order_val == OrderValue::Invalid {
+                warn!("Invalid load order value: '{v}' for: {k}, value will be reset");
+            }
+            let curr_v = order_val.sort_val();
             input_vals.insert(curr_v);
             if let Some(input_k) = stable {
                 if k == input_k {
@@ -437,6 +775,136 @@ impl ModLoaderCfg {
             missing_vals,
         }
     }
+
+    /// compacts any gaps in the saved load order on demand, see `update_order_entries`  
+    /// returns the resulting `OrdMetaData` and the number of entries whose value changed  
+    ///
+    /// **Note:** does not write to file, call `self.write_to_file()` to save the changes
+    #[instrument(level = "trace", skip_all)]
+    pub fn normalize_order(&mut self, unknown_keys: &HashSet<String>) -> (OrdMetaData, usize) {
+        let prev_vals = self
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect::<HashMap<_, _>>();
+        let ord_meta_data = self.update_order_entries(None, unknown_keys);
+        let moved = self
+            .iter()
+            .filter(|(k, v)| prev_vals.get(*k).is_some_and(|prev_v| prev_v != v))
+            .count();
+        (ord_meta_data, moved)
+    }
+
+    /// sets the load order from a plaintext list, as exported by other mod managers  
+    /// lines that match a registered dll are given sequential order values in the listed order,  
+    /// entries that are not listed keep their relative order and are placed after listed entries  
+    /// lines that do not match a registered dll are ignored, see `order_line_key`  
+    ///
+    /// **Note:** does not write to file, call `self.write_to_file()` to save the changes
+    #[instrument(level = "trace", skip_all)]
+    pub fn import_order_from_lines(&mut self, lines: &[&str], dlls: &DllSet) -> OrdMetaData {
+        let mut imported = Vec::new();
+        for line in lines {
+            match order_line_key(line, dlls) {
+                Some(key) if !imported.contains(&key) => imported.push(key),
+                Some(key) => trace!("{key} listed more than once, only first position is used"),
+                None => {
+                    let line = line.trim();
+                    if !line.is_empty() && !line.starts_with('#') {
+                        warn!("Load order line: '{line}' does not match a registered dll");
+                    }
+                }
+            }
+        }
+        let mut unlisted = self
+            .iter()
+            .filter(|(k, _)| !imported.contains(k))
+            .filter_map(|(k, v)| v.parse::<usize>().ok().map(|v| (k.to_string(), v)))
+            .collect::<Vec<_>>();
+        unlisted.sort_by_key(|(_, v)| *v);
+        let (mut offset, mut prev_v) = (imported.len(), None);
+        for (k, v) in unlisted {
+            if prev_v != Some(v) {
+                offset += 1;
+                prev_v = Some(v);
+            }
+            self.mut_section().insert(k, offset.to_string());
+        }
+        for (i, key) in imported.iter().enumerate() {
+            self.mut_section().insert(*key, (i + 1).to_string());
+        }
+        let unknown_keys = self
+            .iter()
+            .filter(|(k, _)| !dlls.contains(k))
+            .map(|(k, _)| k.to_string())
+            .collect::<HashSet<_>>();
+        self.update_order_entries(None, &unknown_keys)
+    }
+}
+
+/// returns the registered dll that `line` of a plaintext load order refers to  
+/// leading `*`, `+` markers and any parent directories are ignored, matching is case-insensitive  
+/// blank lines and lines starting with `#` or `-` are never matched
+pub fn order_line_key<'a>(line: &str, dlls: &DllSet<'a>) -> Option<&'a str> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with(['#', '-']) {
+        return None;
+    }
+    let name = omit_off_state(file_name_from_str(line.trim_start_matches(['*', '+']).trim()));
+    dlls.iter().copied().find(|dll| dll.eq_ignore_ascii_case(name))
+}
+
+/// rows each load order value is placed at, see `order_placement`
+#[derive(Debug, PartialEq, Eq)]
+pub struct OrderPlacement {
+    /// lowest load order value, `rows` is indexed by `value - low_order`
+    pub low_order: usize,
+    pub rows: Vec<VecDeque<usize>>,
+}
+
+/// calculates the rows each value in `order_map` is placed at when `row_count` mods are displayed  
+/// rows are filled from the top in order of value, values of keys in `unknown_keys` are skipped  
+/// returns `Ok(None)` if there are no values to place  
+///
+/// errors if the values are not a contiguous range starting at 0 or 1 or there are more values than  
+/// `row_count`, values can be fixed with `update_order_entries` before trying again
+pub fn order_placement(
+    order_map: &OrderMap,
+    unknown_keys: &HashSet<String>,
+    row_count: usize,
+) -> std::io::Result<Option<OrderPlacement>> {
+    let mut order_counts = BTreeMap::<usize, usize>::new();
+    order_map
+        .iter()
+        .filter(|(k, _)| !unknown_keys.contains(*k))
+        .for_each(|(_, &v)| *order_counts.entry(v).or_default() += 1);
+    let (Some((&low_order, _)), Some((&high_order, _))) =
+        (order_counts.first_key_value(), order_counts.last_key_value())
+    else {
+        return Ok(None);
+    };
+    if low_order > 1 || high_order - low_order + 1 != order_counts.len() {
+        return new_io_error!(
+            ErrorKind::InvalidData,
+            format!("Load order values: {low_order}..={high_order}, are not a contiguous range starting at 0 or 1")
+        );
+    }
+    let value_count = order_counts.values().sum::<usize>();
+    if value_count > row_count {
+        return new_io_error!(
+            ErrorKind::InvalidData,
+            format!("Found: {value_count} load order values, but only: {row_count} mods are displayed")
+        );
+    }
+    let mut counter = 0_usize;
+    let rows = order_counts
+        .into_values()
+        .map(|count| {
+            let rows = (counter..counter + count).collect::<VecDeque<_>>();
+            counter += count;
+            rows
+        })
+        .collect();
+    Ok(Some(OrderPlacement { low_order, rows }))
 }
 
 pub trait RegModsExt {
@@ -1,24 +1,35 @@
 use ini::Ini;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     io::ErrorKind,
     path::{Path, PathBuf},
     str::ParseBoolError,
+    sync::atomic::AtomicBool,
 };
 use tracing::{error, info, instrument, trace, warn};
 
 use crate::{
-    file_name_from_str, files_not_found, get_cfg, new_io_error, omit_off_state, toggle_files,
-    toggle_path_state,
+    canonical_key, cmp_mod_names, file_name_from_str, file_sha256, files_not_found, format_key,
+    get_cfg, is_loader_hook, is_restricted_file, loader_cfg_path, new_io_error,
+    normalize_separators, omit_off_state, toggle_files, toggle_path_state, toggle_paths_state,
     utils::{
-        display::{DisplayIndices, DisplayName, DisplayVec, IntoIoError, Merge, ModError},
+        display::{
+            DisplayIndices, DisplayName, DisplayState, DisplayVec, IntoIoError, Merge, ModError,
+        },
         ini::{
-            common::{Cfg, Config},
-            writer::{remove_array, remove_entry, save_bool, save_path, save_paths},
+            common::{Cfg, Config, ModLoaderCfg},
+            writer::{
+                remove_array, remove_entry, remove_hashes, save_bool, save_hashes, save_path,
+                save_paths,
+            },
         },
+        installer::{copy_with_progress, plan_removal, RemovalPreview},
+        subscriber::OpTimer,
+        watcher::mark_self_write,
     },
-    DllSet, FileData, OrderMap, ARRAY_KEY, ARRAY_VALUE, INI_KEYS, INI_SECTIONS,
-    REQUIRED_GAME_FILES,
+    DllSet, FileData, OrderMap, ARRAY_KEY, ARRAY_VALUE, CATEGORIES_SECTION, DEFAULT_HASH_FILES,
+    FAVORITES_SECTION, HASHES_SECTION, INI_KEYS, INI_SECTIONS, LOADER_SECTIONS, NOTES_SECTION,
+    OFF_STATE, PINNED_SECTION, REQUIRED_GAME_FILES, TOGGLE_CONFIGS_SECTION, TRACKED_DIRS_SECTION,
 };
 
 pub trait Parsable: Sized {
@@ -46,15 +57,59 @@ impl Parsable for bool {
     }
 }
 
-#[inline]
+/// every form `parse_bool` accepts and the `bool` it maps to, forms are matched case-insensitively  
+/// values are always written back to file as `true` or `false`
+pub const BOOL_FORMS: [(&str, bool); 8] = [
+    ("true", true),
+    ("1", true),
+    ("yes", true),
+    ("on", true),
+    ("false", false),
+    ("0", false),
+    ("no", false),
+    ("off", false),
+];
+
+/// parses any form found in `BOOL_FORMS`, surrounding whitespace is ignored
+///
+/// ```
+/// use elden_mod_loader_gui::utils::ini::parser::{parse_bool, BOOL_FORMS};
+///
+/// for (form, value) in BOOL_FORMS {
+///     assert_eq!(parse_bool(form).unwrap(), value);
+///     assert_eq!(parse_bool(&format!(" {} ", form.to_uppercase())).unwrap(), value);
+/// }
+/// assert!(parse_bool("maybe").is_err());
+/// ```
 pub fn parse_bool(str: &str) -> Result<bool, ParseBoolError> {
-    match str {
-        "0" => Ok(false),
-        "1" => Ok(true),
-        c => c.to_lowercase().parse::<bool>(),
+    let str = str.trim();
+    match BOOL_FORMS.iter().find(|(form, _)| form.eq_ignore_ascii_case(str)) {
+        Some(&(_, value)) => Ok(value),
+        None => str.parse::<bool>(),
     }
 }
 
+/// trims whitespace and a matching pair of surrounding quotes from a path value read from an ini  
+/// all stored paths are normalized this way so hand-edited values resolve to the same path
+///
+/// ```
+/// use elden_mod_loader_gui::utils::ini::parser::path_from_ini_value;
+/// use std::path::Path;
+///
+/// for value in ["mods\\a.dll", " mods\\a.dll ", "\"mods\\a.dll\"", " ' mods\\a.dll' "] {
+///     assert_eq!(path_from_ini_value(value), Path::new("mods\\a.dll"));
+/// }
+/// ```
+pub fn path_from_ini_value(value: &str) -> PathBuf {
+    let value = value.trim();
+    let unquoted = ['"', '\''].into_iter().find_map(|quote| {
+        value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+    });
+    PathBuf::from(unquoted.map_or(value, str::trim))
+}
+
 impl Parsable for u32 {
     fn parse_str(
         ini: &Ini,
@@ -78,7 +133,7 @@ impl Parsable for PathBuf {
         key: &str,
         skip_validation: bool,
     ) -> std::io::Result<Self> {
-        let parsed_value = PathBuf::from({
+        let parsed_value = path_from_ini_value({
             let value = ini
                 .get_from(section, key)
                 .expect("Validated by IniProperty::is_valid");
@@ -131,7 +186,7 @@ impl Parsable for Vec<PathBuf> {
                 .expect("Validated by IniProperty::is_valid")
                 .1
                 .iter()
-                .map(PathBuf::from)
+                .map(|value| path_from_ini_value(value))
                 .collect();
         if skip_validation {
             return Ok(parsed_value);
@@ -307,10 +362,42 @@ impl IniProperty<u32> {
     }
 }
 impl IniProperty<PathBuf> {
+    /// reads, parses and optionally validates a full length `PathBuf`, e.g. from Section: "paths"
+    #[inline]
+    pub fn read_full_path(
+        ini: &Ini,
+        section: Option<&str>,
+        key: &str,
+        skip_validation: bool,
+    ) -> std::io::Result<IniProperty<PathBuf>> {
+        IniProperty::<PathBuf>::read(ini, section, key, None, skip_validation)
+    }
+
+    /// reads, parses and optionally validates the short path saved for `key` in Section: "mod-files"  
+    /// the path is validated to exist within `game_dir`
+    #[inline]
+    pub fn read_mod_file_path(
+        ini: &Ini,
+        key: &str,
+        game_dir: &Path,
+        skip_validation: bool,
+    ) -> std::io::Result<IniProperty<PathBuf>> {
+        IniProperty::<PathBuf>::read(
+            ini,
+            INI_SECTIONS[3],
+            key,
+            Some(game_dir),
+            skip_validation,
+        )
+    }
+
     /// reads, parses and optionally validates a `Pathbuf` from a given Ini  
+    /// prefer `read_full_path` or `read_mod_file_path` as they always supply a valid `path_prefix`  
     /// **Important:**
     /// - When reading a full length path, e.g. from Section: "paths", you _must not_ give a `path_prefix`  
     /// - When reading a partial path, e.g. from Section: "mod-files", you _must_ give a `path_prefix`  
+    ///
+    /// errors with `ErrorKind::InvalidInput` if `path_prefix` does not match the above
     pub fn read(
         ini: &Ini,
         section: Option<&str>,
@@ -319,14 +406,20 @@ impl IniProperty<PathBuf> {
         skip_validation: bool,
     ) -> std::io::Result<IniProperty<PathBuf>> {
         if section == INI_SECTIONS[1] && path_prefix.is_some() {
-            panic!(
-                "path_prefix is invalid when reading a path from: {}",
-                INI_SECTIONS[1].unwrap()
+            return new_io_error!(
+                ErrorKind::InvalidInput,
+                format!(
+                    "path_prefix is invalid when reading a path from: {}",
+                    INI_SECTIONS[1].unwrap()
+                )
             );
         } else if section == INI_SECTIONS[3] && path_prefix.is_none() {
-            panic!(
-                "path_prefix is required when reading a path from: {}",
-                INI_SECTIONS[3].unwrap()
+            return new_io_error!(
+                ErrorKind::InvalidInput,
+                format!(
+                    "path_prefix is required when reading a path from: {}",
+                    INI_SECTIONS[3].unwrap()
+                )
             );
         }
         Ok(IniProperty {
@@ -390,6 +483,15 @@ impl<T: Parsable> IniProperty<T> {
     }
 }
 
+/// (`hash_key`, `sha256`) of the files of a registered mod, see `RegMod::record_hashes`
+pub type FileHashes = HashMap<String, String>;
+
+/// returns the key a hash of `short_path` is saved under in section "file-hashes"  
+/// the off state is omitted so a hash stays valid when the file is toggled
+pub fn hash_key(short_path: &Path) -> String {
+    omit_off_state(&short_path.to_string_lossy()).replace('/', "\\")
+}
+
 #[derive(Debug, Default)]
 pub struct RegMod {
     /// user defined Key in snake_case
@@ -403,6 +505,27 @@ pub struct RegMod {
 
     /// contains properties related to if a mod has a set load order
     pub order: LoadOrder,
+
+    /// hashes saved when the mod was registered, empty unless "hash_files" was enabled at the time
+    pub hashes: FileHashes,
+
+    /// if the files in `files` are _all_ of the files of the mod, see `RegMod::all_files`
+    pub file_set: FileSet,
+
+    /// if `files.config` are toggled along with `files.dll`, saved in section "toggle-configs"
+    pub toggle_configs: bool,
+}
+
+/// how the files of a `RegMod` are tracked
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum FileSet {
+    /// every file is saved in section "mod-files"
+    #[default]
+    Explicit,
+
+    /// short path to a directory whose files are read from disk when needed  
+    /// only `.dll`, `.ini` and files outside of the directory are saved in section "mod-files"
+    Dir(PathBuf),
 }
 
 #[derive(Debug, Default)]
@@ -434,6 +557,12 @@ pub struct LoadOrder {
 }
 
 impl LoadOrder {
+    /// manual constructor for `LoadOrder`, typically use `RegMod::with_load_order()`
+    #[inline]
+    pub fn new(set: bool, i: usize, at: usize) -> Self {
+        LoadOrder { set, i, at }
+    }
+
     fn from(dll_files: &[PathBuf], parsed_order_val: &OrderMap) -> Self {
         if dll_files.is_empty() {
             return LoadOrder::default();
@@ -474,7 +603,7 @@ impl From<Vec<PathBuf>> for SplitFiles {
         let mut dll = Vec::with_capacity(len);
         let mut config = Vec::with_capacity(len);
         let mut other = Vec::with_capacity(len);
-        value.into_iter().for_each(|file| {
+        value.into_iter().map(normalize_separators).for_each(|file| {
             match FileData::from(&file.to_string_lossy()).extension {
                 ".dll" => dll.push(file),
                 ".ini" => config.push(file),
@@ -546,7 +675,7 @@ impl SplitFiles {
     /// adds a path to the correct field within `Self`
     pub fn add(&mut self, path: &Path) {
         let section = get_correct_bucket(self, path);
-        section.push(PathBuf::from(path))
+        section.push(normalize_separators(PathBuf::from(path)))
     }
 
     #[inline]
@@ -573,23 +702,35 @@ type ModData<'a> = (&'a str, bool, SplitFiles, LoadOrder);
 impl<'a> From<ModData<'a>> for RegMod {
     /// manual constructor for RegMod, note does not convert name to _snake_case_
     fn from(value: ModData) -> Self {
-        RegMod {
-            name: String::from(value.0),
-            state: value.1,
-            files: value.2,
-            order: value.3,
-        }
+        RegMod::from_parts(value.0, value.1, value.2, value.3)
     }
 }
 
 impl RegMod {
+    /// manual constructor for `RegMod` with all fields populated  
+    /// note does not convert name to _snake_case_
+    pub fn from_parts(name: &str, state: bool, files: SplitFiles, order: LoadOrder) -> Self {
+        RegMod {
+            name: String::from(name),
+            state,
+            files,
+            order,
+            hashes: FileHashes::new(),
+            file_set: FileSet::Explicit,
+            toggle_configs: false,
+        }
+    }
+
     /// this function omits the population of the `order` field
     pub fn new(name: &str, state: bool, in_files: Vec<PathBuf>) -> Self {
         RegMod {
-            name: name.trim().replace(' ', "_"),
+            name: format_key(name),
             state,
             files: SplitFiles::from(in_files),
             order: LoadOrder::default(),
+            hashes: FileHashes::new(),
+            file_set: FileSet::Explicit,
+            toggle_configs: false,
         }
     }
 
@@ -604,26 +745,283 @@ impl RegMod {
         let split_files = SplitFiles::from(in_files);
         let load_order = LoadOrder::from(&split_files.dll, parsed_order_val);
         RegMod {
-            name: name.trim().replace(' ', "_"),
+            name: format_key(name),
             state,
             files: split_files,
             order: load_order,
+            hashes: FileHashes::new(),
+            file_set: FileSet::Explicit,
+            toggle_configs: false,
         }
     }
 
+    /// returns `self.order.i` if it indexes into `self.files.dll`, `None` if it is out of range  
+    /// eg. `.dll` files were removed from `self` without updating `self.order`
+    #[inline]
+    pub fn validated_order_index(&self) -> Option<usize> {
+        (self.order.i < self.files.dll.len()).then_some(self.order.i)
+    }
+
     /// returns true if `Self` is _currently_ an array
     #[inline]
     pub fn is_array(&self) -> bool {
         self.files.len() > 1
     }
 
+    /// returns true if `Self` has no `.dll` files, these mods can not be toggled and are always active
+    #[inline]
+    pub fn is_always_active(&self) -> bool {
+        self.files.dll.is_empty()
+    }
+
+    /// returns the files renamed by `toggle_files`, `self.files.dll` followed by `self.files.config`  
+    /// if `self.toggle_configs` is set
+    pub fn toggled_files(&self) -> Vec<PathBuf> {
+        let configs = if self.toggle_configs {
+            self.files.config.as_slice()
+        } else {
+            &[]
+        };
+        self.files.dll.iter().chain(configs).cloned().collect()
+    }
+
+    /// sets `self.toggle_configs` then saves it to file, `self.files.config` are renamed to match  
+    /// while set configs share the state of `self`, otherwise they are always enabled
+    pub fn set_toggle_configs(
+        &mut self,
+        toggle: bool,
+        game_dir: &Path,
+        ini_dir: &Path,
+    ) -> std::io::Result<()> {
+        if self.toggle_configs == toggle {
+            return Ok(());
+        }
+        let config_state = !toggle || self.state;
+        let config_path_new = toggle_paths_state(&self.files.config, config_state);
+        mark_self_write();
+        for (i, (path, new_path)) in self.files.config.iter().zip(&config_path_new).enumerate() {
+            if path == new_path {
+                continue;
+            }
+            if let Err(err) = std::fs::rename(game_dir.join(path), game_dir.join(new_path)) {
+                // save the configs renamed so far so the saved paths match the files on disk
+                self.files.config[..i].clone_from_slice(&config_path_new[..i]);
+                self.write_to_file(ini_dir, self.is_array())?;
+                return Err(err);
+            }
+        }
+        self.files.config = config_path_new;
+        self.toggle_configs = toggle;
+        self.write_to_file(ini_dir, self.is_array())?;
+        if !toggle {
+            remove_entry(ini_dir, TOGGLE_CONFIGS_SECTION, &self.name)?;
+        }
+        info!(
+            "{} config files toggled with mod: {toggle}",
+            DisplayName(&self.name)
+        );
+        Ok(())
+    }
+
+    /// returns the sum of the size of every file registered to `self`  
+    /// files that can not be found are counted as 0 bytes
+    #[instrument(level = "trace", skip_all)]
+    pub fn total_size_on_disk(&self, game_dir: &Path) -> std::io::Result<u64> {
+        self.all_files(game_dir)?.iter().try_fold(0, |total, file| {
+            match std::fs::metadata(file) {
+                Ok(metadata) => Ok(total + metadata.len()),
+                Err(err) if err.kind() == ErrorKind::NotFound => {
+                    trace!(file = %file.display(), "not found, counted as 0 bytes");
+                    Ok(total)
+                }
+                Err(err) => Err(err),
+            }
+        })
+    }
+
+    /// returns the full paths of _all_ files of `self`, for `FileSet::Dir` the files within the  
+    /// tracked directory are read from disk and returned along with `self.files`  
+    /// can error on fs::read_dir, a tracked directory that does not exist contributes no files
+    pub fn all_files(&self, game_dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+        let mut files = self.files.full_paths(game_dir);
+        if let FileSet::Dir(ref dir) = self.file_set {
+            let dir = game_dir.join(dir);
+            if !dir.try_exists()? {
+                trace!(dir = %dir.display(), "tracked directory not found");
+                return Ok(files);
+            }
+            let saved = files
+                .iter()
+                .map(|file| file.to_string_lossy().to_lowercase())
+                .collect::<HashSet<_>>();
+            files.extend(
+                files_in_dir_tree(&dir)?
+                    .into_iter()
+                    .filter(|file| !saved.contains(&file.to_string_lossy().to_lowercase())),
+            );
+        }
+        Ok(files)
+    }
+
+    /// switches `self` to `FileSet::Dir` and drops every file in `self.files.other` within `dir`  
+    /// `.dll` and `.ini` files stay saved so they can still be toggled and edited, if no other file  
+    /// would remain the first dropped file is kept so `self` is never saved without files  
+    /// `dir` must be a short path with `game_dir` truncated, returns the number of files dropped
+    pub fn track_by_dir(&mut self, dir: PathBuf) -> usize {
+        let dir = normalize_separators(dir);
+        let prev_len = self.files.len();
+        let anchor = self
+            .files
+            .other
+            .iter()
+            .find(|file| file.starts_with(&dir))
+            .cloned();
+        self.files.other.retain(|file| !file.starts_with(&dir));
+        if self.files.is_empty() {
+            self.files.other.extend(anchor);
+        }
+        self.file_set = FileSet::Dir(dir);
+        prev_len - self.files.len()
+    }
+
+    /// returns the existing files of `self` and the directories that would be left empty and pruned  
+    /// by `remove_mod_files`, nothing is modified, an empty preview is returned if a file can not be  
+    /// accessed as `remove_mod_files` would fail in this case
+    pub fn removal_preview(&self, game_dir: &Path) -> RemovalPreview {
+        self.all_files(game_dir)
+            .and_then(|files| plan_removal(game_dir, files))
+            .unwrap_or_else(|err| {
+                warn!("{err}");
+                RemovalPreview::default()
+            })
+    }
+
+    /// returns the directory `self` is installed in, "game_dir\mods\dll_name" for mods with one `.dll`  
+    /// otherwise the parent of the registered file with the _least_ ammount of ancestors
+    pub fn install_dir(&self, game_dir: &Path) -> std::io::Result<PathBuf> {
+        if let [dll] = self.files.dll.as_slice() {
+            let path_string = dll.to_string_lossy();
+            let file_data = FileData::from(file_name_from_str(&path_string));
+            return Ok(game_dir.join("mods").join(file_data.name));
+        }
+        self.files
+            .chain_all()
+            .min_by_key(|file| file.ancestors().count())
+            .and_then(|path| Some(game_dir.join(path.parent()?)))
+            .ok_or_else(|| {
+                std::io::Error::new(ErrorKind::InvalidData, "Failed to create an install_dir")
+            })
+    }
+
+    /// returns the state of the `.dll` files of `self` as they are currently found on disk  
+    /// `Some(state)` if all files share the same state, `None` if files are found in mixed states  
+    /// mods with no `.dll` files are always active so `Some(true)` is returned  
+    ///
+    /// errors if a file can not be found in either state
+    #[instrument(level = "trace", skip_all)]
+    pub fn disk_state(&self, game_dir: &Path) -> std::io::Result<Option<bool>> {
+        let mut states = Vec::with_capacity(self.files.dll.len());
+        let mut not_found = Vec::new();
+        for (i, dll) in self.files.dll.iter().enumerate() {
+            if game_dir.join(dll).try_exists()? {
+                states.push(FileData::is_enabled(dll));
+            } else if game_dir.join(toggle_path_state(dll)).try_exists()? {
+                states.push(FileData::is_disabled(dll));
+            } else {
+                not_found.push(i);
+            }
+        }
+        if !not_found.is_empty() {
+            return new_io_error!(
+                ErrorKind::NotFound,
+                format!(
+                    "File(s): {}, can not be found on machine",
+                    DisplayIndices(&not_found, &self.files.dll)
+                )
+            );
+        }
+        match states.split_first() {
+            None => Ok(Some(true)),
+            Some((first, rest)) if rest.iter().all(|state| state == first) => Ok(Some(*first)),
+            Some(_) => {
+                trace!(fnames = ?self.files.dll, "files found in mixed states");
+                Ok(None)
+            }
+        }
+    }
+
+    /// returns the files of `self` that can not be found in `game_dir`  
+    /// only checks if each path exists, no attempt is made to recover files saved in the wrong state
+    pub fn files_missing(&self, game_dir: &Path) -> Vec<PathBuf> {
+        self.files
+            .chain_all()
+            .filter(|file| !matches!(game_dir.join(file).try_exists(), Ok(true)))
+            .cloned()
+            .collect()
+    }
+
+    /// computes the sha-256 of each file of `self` that has no hash yet then saves them to  
+    /// section "file-hashes", existing hashes are kept so changed files are not re-recorded  
+    /// files larger than `HASH_SIZE_LIMIT` are skipped, errors if a file can not be read
+    #[instrument(level = "trace", skip_all, fields(mod_name = self.name))]
+    pub fn record_hashes(&mut self, game_dir: &Path, ini_dir: &Path) -> std::io::Result<()> {
+        for file in self.files.chain_all() {
+            let key = hash_key(file);
+            if self.hashes.contains_key(&key) {
+                continue;
+            }
+            if let Some(hash) = file_sha256(&game_dir.join(file))? {
+                self.hashes.insert(key, hash);
+            }
+        }
+        save_hashes(ini_dir, &self.hashes)
+    }
+
+    /// returns the files of `self` whose current sha-256 differs from the hash in `self.hashes`  
+    /// files with no saved hash, that can not be found, or that are larger than `HASH_SIZE_LIMIT`  
+    /// are skipped
+    #[instrument(level = "trace", skip_all, fields(mod_name = self.name))]
+    pub fn verify_hashes(&self, game_dir: &Path) -> Vec<PathBuf> {
+        self.files
+            .chain_all()
+            .filter(|file| {
+                let Some(saved) = self.hashes.get(&hash_key(file)) else {
+                    return false;
+                };
+                let mut path = game_dir.join(file);
+                if !path.is_file() {
+                    path = game_dir.join(toggle_path_state(file));
+                }
+                match file_sha256(&path) {
+                    Ok(hash) => hash.is_some_and(|hash| hash != *saved),
+                    Err(err) => {
+                        trace!(file = %file.display(), "could not be hashed, {err}");
+                        false
+                    }
+                }
+            })
+            .cloned()
+            .collect()
+    }
+
     /// verifies that files exist and recovers from the case where the file paths are saved in the  
     /// incorect state compaired to the name of the files currently saved on disk  
     ///
-    /// then verifies that the saved state matches the state of the files  
-    /// if not correct, runs toggle files to put them in the correct state  
+    /// then verifies that the saved state matches the state of the files, a mod is enabled if any of  
+    /// its `.dll` files are enabled. if not correct, runs toggle files to put them in the correct state  
+    ///
+    /// mods with no `.dll` files are always active, if saved as disabled the state is corrected  
     #[instrument(level = "trace", skip_all)]
     pub fn verify_state(&mut self, game_dir: &Path, ini_dir: &Path) -> std::io::Result<()> {
+        if self.is_always_active() {
+            if !self.state {
+                self.state = true;
+                save_bool(ini_dir, INI_SECTIONS[2], &self.name, self.state)?;
+                trace!("mod has no .dll files, saved state set to: true");
+            }
+            trace!("{} is always active", DisplayName(&self.name));
+            return Ok(());
+        }
         let count_try_verify_ouput = || -> (usize, Vec<usize>, usize) {
             let (mut exists, mut errors) = (0_usize, 0_usize);
             let mut not_found_indices = Vec::new();
@@ -673,9 +1071,29 @@ impl RegMod {
                 )
             );
         }
-        if (!self.state && self.files.dll.iter().any(FileData::is_enabled))
-            || (self.state && self.files.dll.iter().any(FileData::is_disabled))
-        {
+        // configs not found in either state are left for `collect_mods` to remove
+        let mut configs_recovered = false;
+        for config in self.files.config.iter_mut() {
+            if !matches!(game_dir.join(&config).try_exists(), Ok(false)) {
+                continue;
+            }
+            let alt_path_state = toggle_path_state(config);
+            if matches!(game_dir.join(&alt_path_state).try_exists(), Ok(true)) {
+                *config = alt_path_state;
+                configs_recovered = true;
+            }
+        }
+        if configs_recovered {
+            self.write_to_file(ini_dir, self.is_array())?;
+            info!(
+                "{}'s config files were saved in the incorrect state, updated files to reflect the correct state",
+                DisplayName(&self.name),
+            );
+        }
+        let configs_in_state = !self.toggle_configs
+            || self.files.config.iter().all(|f| FileData::is_enabled(f) == self.state);
+        // dlls toggled individually with `toggle_single_file` leave a mod enabled in a mixed state
+        if self.state != self.files.dll.iter().any(FileData::is_enabled) || !configs_in_state {
             info!(
                 "Wrong file state for mod: '{}', changing file state",
                 DisplayName(&self.name)
@@ -686,7 +1104,8 @@ impl RegMod {
         Ok(())
     }
 
-    /// saves `self.state` and all `self.files` to file  
+    /// saves `self.state`, all `self.files`, the tracked directory of `FileSet::Dir`, and  
+    /// `self.toggle_configs` if set to file  
     /// it is important to keep track of the length of `self.files.file_refs()` before  
     /// making modifications to `self.files` to insure that the .ini file remains valid  
     pub fn write_to_file(&self, ini_dir: &Path, was_array: bool) -> std::io::Result<()> {
@@ -709,10 +1128,16 @@ impl RegMod {
                 self.files.file_refs()[0],
             )?
         }
+        if let FileSet::Dir(ref dir) = self.file_set {
+            save_path(ini_dir, TRACKED_DIRS_SECTION, &self.name, dir)?;
+        }
+        if self.toggle_configs {
+            save_bool(ini_dir, TOGGLE_CONFIGS_SECTION, &self.name, true)?;
+        }
         Ok(())
     }
 
-    /// removes `self` from the given ini_dir, removes files based on the current status of self.is_array()  
+    /// removes `self`, its note, pin, favorite, category, tracked directory, toggle configs flag, and hashes from the given ini_dir, removes files based on the current status of self.is_array()  
     /// note if you modify `self.files` you might run into unexpected behavior
     pub fn remove_from_file(&self, ini_dir: &Path) -> std::io::Result<()> {
         remove_entry(ini_dir, INI_SECTIONS[2], &self.name)?;
@@ -721,6 +1146,26 @@ impl RegMod {
         } else {
             remove_entry(ini_dir, INI_SECTIONS[3], &self.name)?;
         }
+        let saved = get_cfg(ini_dir)?;
+        if saved.get_from(NOTES_SECTION, &self.name).is_some() {
+            remove_entry(ini_dir, NOTES_SECTION, &self.name)?;
+        }
+        if saved.get_from(PINNED_SECTION, &self.name).is_some() {
+            remove_entry(ini_dir, PINNED_SECTION, &self.name)?;
+        }
+        if saved.get_from(FAVORITES_SECTION, &self.name).is_some() {
+            remove_entry(ini_dir, FAVORITES_SECTION, &self.name)?;
+        }
+        if saved.get_from(CATEGORIES_SECTION, &self.name).is_some() {
+            remove_entry(ini_dir, CATEGORIES_SECTION, &self.name)?;
+        }
+        if saved.get_from(TRACKED_DIRS_SECTION, &self.name).is_some() {
+            remove_entry(ini_dir, TRACKED_DIRS_SECTION, &self.name)?;
+        }
+        if saved.get_from(TOGGLE_CONFIGS_SECTION, &self.name).is_some() {
+            remove_entry(ini_dir, TOGGLE_CONFIGS_SECTION, &self.name)?;
+        }
+        remove_hashes(ini_dir, &self.files.file_refs())?;
         Ok(())
     }
 }
@@ -731,6 +1176,224 @@ pub struct CollectedMods {
     pub warnings: Option<std::io::Error>,
 }
 
+/// names of mods processed by `Cfg::verify_all` grouped by the outcome of `RegMod::verify_state`
+#[derive(Debug, Default)]
+pub struct VerifySummary {
+    /// mods that had their saved data or the state of their files corrected
+    pub fixed: Vec<String>,
+
+    /// (`mod_name`, `err`) for mods that could not be verified
+    pub failed: Vec<(String, std::io::Error)>,
+
+    /// mods that were already in the correct state
+    pub unchanged: Vec<String>,
+}
+
+/// read-only report of inconsistencies found between the ini and the files on disk  
+/// see `Cfg::diagnose`
+#[derive(Debug, Default)]
+pub struct DiagnosisReport {
+    /// mods where the state of their `.dll` files on disk disagrees with the saved state  
+    /// a mod is enabled if any of its `.dll` files are enabled
+    pub state_mismatch: Vec<String>,
+
+    /// (`mod_name`, `short_paths`) of registered files that could not be found in either state  
+    /// mods saved with a state but no files are included with no paths
+    pub missing_files: Vec<(String, Vec<PathBuf>)>,
+
+    /// files found in the "mods" folder that are not registered to any mod  
+    /// saved as short paths with `game_dir` truncated
+    pub unregistered_files: Vec<PathBuf>,
+
+    /// keys in "loadorder" of "mod_loader_config.ini" that do not belong to a registered `.dll`  
+    /// `LOADER_EXAMPLE` is not reported, see `ModLoaderCfg::unregistered_keys`
+    pub orphaned_orders: Vec<String>,
+
+    /// (`mod_name`, `short_paths`) of files that changed since their hash was recorded  
+    /// only checked if "hash_files" is enabled, see `RegMod::verify_hashes`
+    pub changed_files: Vec<(String, Vec<PathBuf>)>,
+}
+
+impl DiagnosisReport {
+    /// returns `true` if no inconsistencies were found
+    #[inline]
+    pub fn is_healthy(&self) -> bool {
+        self.state_mismatch.is_empty()
+            && self.missing_files.is_empty()
+            && self.unregistered_files.is_empty()
+            && self.orphaned_orders.is_empty()
+            && self.changed_files.is_empty()
+    }
+}
+
+/// differences between the current config and a saved one, read as what restoring the saved config  
+/// would change, see `Cfg::diff_against` and `ConfigDiff::compare_orders`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    /// mods only registered in the saved config
+    pub added: Vec<String>,
+
+    /// mods only registered in the current config
+    pub removed: Vec<String>,
+
+    /// (`mod_name`, `saved_state`) of mods registered in both with a different state
+    pub state_changed: Vec<(String, bool)>,
+
+    /// mods registered in both with a different set of files
+    pub files_changed: Vec<String>,
+
+    /// (`file_name`, `current_order`, `saved_order`) of `.dll`s with a different load order
+    pub order_changed: Vec<(String, Option<usize>, Option<usize>)>,
+}
+
+impl ConfigDiff {
+    /// returns `true` if both configs are semantically the same
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.state_changed.is_empty()
+            && self.files_changed.is_empty()
+            && self.order_changed.is_empty()
+    }
+
+    /// records each `.dll` that has a different value in section "loadorder" of `current` and `saved`  
+    /// file names are compared ignoring case, values that are not a number are treated as not set
+    pub fn compare_orders(&mut self, current: &Ini, saved: &Ini) {
+        fn order_values(loader: &Ini) -> HashMap<String, (&str, Option<usize>)> {
+            loader
+                .section(LOADER_SECTIONS[1])
+                .into_iter()
+                .flat_map(|section| section.iter())
+                .map(|(k, v)| (k.to_lowercase(), (k, v.trim().parse().ok())))
+                .collect()
+        }
+        let current = order_values(current);
+        let saved = order_values(saved);
+        let mut keys = current.keys().chain(saved.keys()).collect::<Vec<_>>();
+        keys.sort();
+        keys.dedup();
+        self.order_changed = keys
+            .into_iter()
+            .filter_map(|key| {
+                let (current_name, current_order) = current.get(key).copied().unwrap_or_default();
+                let (saved_name, saved_order) = saved.get(key).copied().unwrap_or_default();
+                (current_order != saved_order).then(|| {
+                    let name = if current_name.is_empty() { saved_name } else { current_name };
+                    (name.to_string(), current_order, saved_order)
+                })
+            })
+            .collect();
+    }
+}
+
+/// the parts of a registered mod compared by `Cfg::diff_against`
+struct SavedMod<'a> {
+    name: &'a str,
+    state: Option<bool>,
+    /// short paths with the off_state removed, as lowercase
+    files: BTreeSet<String>,
+}
+
+impl Cfg {
+    /// returns all registered mods keyed by their `canonical_key`
+    fn saved_mods(&self) -> BTreeMap<String, SavedMod<'_>> {
+        let states = self.data().section(INI_SECTIONS[2]);
+        self.iter_file_entries()
+            .map(|(name, files)| {
+                let saved_mod = SavedMod {
+                    name,
+                    state: states
+                        .and_then(|states| states.get(name))
+                        .and_then(|state| parse_bool(state).ok()),
+                    files: files
+                        .into_iter()
+                        .map(|file| omit_off_state(file).to_lowercase())
+                        .collect(),
+                };
+                (canonical_key(name), saved_mod)
+            })
+            .collect()
+    }
+
+    /// compares the mods registered in `self` with the mods registered in `saved`  
+    /// mods are matched by `canonical_key`, files are compared ignoring order, case and off_state  
+    /// load order is stored in a different file, use `ConfigDiff::compare_orders` to include it
+    #[instrument(level = "trace", skip_all)]
+    pub fn diff_against(&self, saved: &Cfg) -> ConfigDiff {
+        let current_mods = self.saved_mods();
+        let saved_mods = saved.saved_mods();
+        let mut diff = ConfigDiff::default();
+        for (key, saved_mod) in saved_mods.iter() {
+            let Some(current_mod) = current_mods.get(key) else {
+                diff.added.push(saved_mod.name.to_string());
+                continue;
+            };
+            if let Some(state) = saved_mod.state {
+                if current_mod.state != Some(state) {
+                    diff.state_changed.push((saved_mod.name.to_string(), state));
+                }
+            }
+            if saved_mod.files != current_mod.files {
+                diff.files_changed.push(saved_mod.name.to_string());
+            }
+        }
+        diff.removed = current_mods
+            .iter()
+            .filter(|(key, _)| !saved_mods.contains_key(*key))
+            .map(|(_, current_mod)| current_mod.name.to_string())
+            .collect();
+        diff
+    }
+}
+
+/// groups warnings of the same kind found while collecting mods so the user is shown  
+/// a single message per kind, per mod detail is logged as each warning is found  
+#[derive(Default)]
+struct CollectedWarnings {
+    /// (`mod_name`, `err`) for mods removed because their files could not be found
+    removed_mods: Vec<(String, std::io::Error)>,
+    /// (`file_name`, `err`) for files removed from a registered mod because they could not be found
+    removed_files: Vec<(String, std::io::Error)>,
+    other: Vec<std::io::Error>,
+}
+
+impl CollectedWarnings {
+    /// merges all collected warnings into one `io::Error`, if a kind only has one  
+    /// warning the original error is kept
+    fn into_warning(self) -> Option<std::io::Error> {
+        let summarize = |mut group: Vec<(String, std::io::Error)>, msg: &str| {
+            if group.len() == 1 {
+                return group.pop().expect("len == 1").1;
+            }
+            let names = group.into_iter().map(|(name, _)| name).collect::<Vec<_>>();
+            std::io::Error::new(
+                ErrorKind::NotFound,
+                format!("{} {msg}: {}", names.len(), DisplayVec(&names)),
+            )
+        };
+        let mut warnings = Vec::with_capacity(self.other.len() + 2);
+        if !self.removed_mods.is_empty() {
+            warnings.push(summarize(
+                self.removed_mods,
+                "mods removed because their files were missing",
+            ));
+        }
+        if !self.removed_files.is_empty() {
+            warnings.push(summarize(
+                self.removed_files,
+                "files removed from registered mods because they were missing",
+            ));
+        }
+        warnings.extend(self.other);
+        match warnings.len() {
+            0 => None,
+            1 => warnings.pop(),
+            _ => Some(warnings.merge(true)),
+        }
+    }
+}
+
 /// (`HashMap<key, bool_str`>, `HashMap<key, Vec<short_paths>`)
 type CollectedMaps<'a> = (HashMap<&'a str, &'a str>, HashMap<&'a str, Vec<&'a str>>);
 
@@ -739,7 +1402,7 @@ trait Combine {
         self,
         parsed_order_val: Option<&OrderMap>,
         game_dir: &Path,
-        ini_dir: &Path,
+        ini: &Cfg,
     ) -> CollectedMods;
 }
 
@@ -749,24 +1412,22 @@ impl<'a> Combine for CollectedMaps<'a> {
         self,
         parsed_order_val: Option<&OrderMap>,
         game_dir: &Path,
-        ini_dir: &Path,
+        ini: &Cfg,
     ) -> CollectedMods {
-        let mut count = 0_usize;
-        let mut warnings = Vec::new();
+        let ini_dir = ini.path();
+        let mut warnings = CollectedWarnings::default();
         let mut mod_data = self
             .0
             .iter()
             .filter_map(|(&key, &state_str)| {
                 self.1.get(&key).map(|file_strs| {
-                    let split_files =
-                        SplitFiles::from(file_strs.iter().map(PathBuf::from).collect::<Vec<_>>());
+                    let split_files = SplitFiles::from(
+                        file_strs.iter().map(|f| path_from_ini_value(f)).collect::<Vec<_>>(),
+                    );
                     let load_order = match parsed_order_val {
                         Some(data) => LoadOrder::from(&split_files.dll, data),
                         None => LoadOrder::default(),
                     };
-                    if load_order.set {
-                        count += 1
-                    }
                     (
                         key,
                         parse_bool(state_str).unwrap_or_else(|err| {
@@ -785,19 +1446,48 @@ impl<'a> Combine for CollectedMaps<'a> {
         // if this fails `sync_keys()` did not do its job
         debug_assert_eq!(self.1.len(), mod_data.len());
 
-        mod_data.sort_by_key(|(_, _, _, l)| if l.set { l.at } else { usize::MAX });
-        mod_data[count..].sort_by_key(|(key, _, _, _)| *key);
+        let order_val = |l: &LoadOrder| if l.set { l.at } else { usize::MAX };
+        mod_data.sort_by(|(a_key, _, _, a_order), (b_key, _, _, b_order)| {
+            order_val(a_order)
+                .cmp(&order_val(b_order))
+                .then_with(|| cmp_mod_names(a_key, b_key))
+        });
         CollectedMods {
             mods: mod_data
                 .drain(..)
                 .filter_map(|mod_data| {
                     let mut curr = RegMod::from(mod_data);
+                    curr.toggle_configs = ini.toggles_configs(&curr.name);
+                    // checked before `verify_state` so the loader is never toggled as if it were a mod
+                    if let Some(hook) = curr.files.dll.iter().find(|dll| is_loader_hook(dll)) {
+                        let err = std::io::Error::new(
+                            ErrorKind::InvalidData,
+                            format!(
+                                "{} was removed, its file: '{}' is the mod loader's dll hook. \
+                                Toggle the loader from settings instead",
+                                DisplayName(&curr.name),
+                                hook.display()
+                            ),
+                        );
+                        warn!("{err}");
+                        warnings.other.push(err);
+                        if let Err(err) = curr.remove_from_file(ini_dir) {
+                            error!("{err}");
+                            warnings.other.push(err);
+                        };
+                        return None;
+                    }
                     if let Err(err) = curr.verify_state(game_dir, ini_dir) {
                         error!("{err}");
-                        warnings.push(err);
+                        if err.kind() == ErrorKind::NotFound {
+                            let name = DisplayName(&curr.name).to_string();
+                            warnings.removed_mods.push((name, err));
+                        } else {
+                            warnings.other.push(err);
+                        }
                         if let Err(err) = curr.remove_from_file(ini_dir) {
                             error!("{err}");
-                            warnings.push(err);
+                            warnings.other.push(err);
                         };
                         return None;
                     }
@@ -807,11 +1497,11 @@ impl<'a> Combine for CollectedMaps<'a> {
                             let Some(file) = curr.files.remove(&err.error_paths[i]) else {
                                 err.errors.into_iter().for_each(|err| {
                                     error!("{err}");
-                                    warnings.push(err);
+                                    warnings.other.push(err);
                                 });
                                 if let Err(err) = curr.remove_from_file(ini_dir) {
                                     error!("{err}");
-                                    warnings.push(err);
+                                    warnings.other.push(err);
                                 };
                                 return None;
                             };
@@ -824,29 +1514,98 @@ impl<'a> Combine for CollectedMaps<'a> {
                                 false,
                             );
                             warn!("{}", err.errors[i]);
-                            warnings.push(err.errors.pop().expect("valid range"))
+                            warnings.removed_files.push((
+                                file.display().to_string(),
+                                err.errors.pop().expect("valid range"),
+                            ));
                         }
                         if let Err(err) = curr.write_to_file(ini_dir, was_array) {
                             error!("{err}");
-                            warnings.push(err);
+                            warnings.other.push(err);
                             return None;
                         }
                     }
                     Some(curr)
                 })
                 .collect(),
-            warnings: if warnings.is_empty() {
-                None
-            } else if warnings.len() == 1 {
-                Some(warnings.remove(0))
-            } else {
-                Some(warnings.merge(true))
-            },
+            warnings: warnings.into_warning(),
         }
     }
 }
 
 impl Cfg {
+    /// returns an iterator over (`mod_name`, `short_paths`) of each entry saved in section "mod-files"  
+    /// see `PropertyArray` for how entries saved as an array are read
+    pub fn iter_file_entries(&self) -> impl Iterator<Item = (&str, Vec<&str>)> {
+        self.data()
+            .section(INI_SECTIONS[3])
+            .into_iter()
+            .flat_map(PropertyArray)
+    }
+
+    /// returns (`mod_name`, `full_path`) for every file of every registered mod, sorted by path  
+    /// mods saved with a single file and mods saved as an array are both read with `iter_file_entries`  
+    /// only section "mod-files" is read, the state saved in section "registered-mods" is not considered
+    pub fn all_registered_files(&self, game_dir: &Path) -> Vec<(String, PathBuf)> {
+        let mut all_files = self
+            .iter_file_entries()
+            .flat_map(|(name, files)| {
+                files
+                    .into_iter()
+                    .map(move |file| (name.to_string(), game_dir.join(path_from_ini_value(file))))
+            })
+            .collect::<Vec<_>>();
+        all_files.sort_by(|(_, a), (_, b)| a.cmp(b));
+        all_files
+    }
+
+    /// returns the key of the registered mod that `short_path` is saved to, an absolute path is first made  
+    /// relative to the saved "game_dir", files within a directory saved in "tracked-dirs" are also found  
+    /// paths are compared ignoring case and off state so a disabled file resolves to the same owner
+    pub fn owner_of(&self, short_path: &Path) -> Option<String> {
+        let file_key = |file: &str| omit_off_state(file).replace('/', "\\").to_lowercase();
+        let short_path = if short_path.is_absolute() {
+            let game_dir = self.data().get_from(INI_SECTIONS[1], INI_KEYS[2])?;
+            short_path.strip_prefix(game_dir).ok()?
+        } else {
+            short_path
+        };
+        let target = file_key(&short_path.to_string_lossy());
+        self.iter_file_entries()
+            .find(|(_, files)| files.iter().any(|&file| file_key(file) == target))
+            .map(|(name, _)| name.to_string())
+            .or_else(|| {
+                self.data()
+                    .section(TRACKED_DIRS_SECTION)?
+                    .iter()
+                    .find(|(_, dir)| target.starts_with(&format!("{}\\", file_key(dir))))
+                    .map(|(name, _)| name.to_string())
+            })
+    }
+
+    /// returns the hashes saved in section "file-hashes" for each of `files` that has one
+    pub fn saved_hashes(&self, files: &SplitFiles) -> FileHashes {
+        let Some(saved) = self.data().section(HASHES_SECTION) else {
+            return FileHashes::new();
+        };
+        files
+            .chain_all()
+            .filter_map(|file| {
+                let key = hash_key(file);
+                let hash = saved.get(&key)?.to_string();
+                Some((key, hash))
+            })
+            .collect()
+    }
+
+    /// returns `FileSet::Dir` if a directory is saved for `key` in section "tracked-dirs"
+    pub fn file_set(&self, key: &str) -> FileSet {
+        match self.data().get_from(TRACKED_DIRS_SECTION, key) {
+            Some(dir) => FileSet::Dir(normalize_separators(path_from_ini_value(dir))),
+            None => FileSet::Explicit,
+        }
+    }
+
     /// returns only valid mod data, if data was found to be invalid a message  
     /// is given to inform the user of why a mod was not included  
     ///
@@ -855,6 +1614,9 @@ impl Cfg {
     /// - `self.files.dll` are valid to exist on disk check `self.verify_state()` for how it can recover  
     /// - `self.files.other_file_refs()` are valid to exist on disk  
     ///   - files not found are removed and user can re-add them  
+    /// - mods with no `.dll` files are kept and are always active  
+    ///
+    /// returns early without reading section "mod-files" if no mods are registered
     #[instrument(level = "trace", skip(self, game_dir, include_load_order))]
     pub fn collect_mods<P: AsRef<Path>>(
         &self,
@@ -862,6 +1624,11 @@ impl Cfg {
         include_load_order: Option<&OrderMap>,
         skip_validation: bool,
     ) -> CollectedMods {
+        let _timer = OpTimer::start("collect_mods");
+        if self.mods_is_empty() {
+            trace!("no mods registered");
+            return CollectedMods::default();
+        }
         if skip_validation {
             let collect_data_unchecked = || -> Vec<(&str, &str, Vec<&str>)> {
                 let mod_state_data = self
@@ -874,9 +1641,12 @@ impl Cfg {
                     .expect("Validated by Ini::is_setup on startup");
                 PropertyArray(file_data)
                     .into_iter()
-                    .map(|(k, v)| {
-                        let s = mod_state_data.get(k).expect("key exists");
-                        (k, s, v)
+                    .filter_map(|(k, v)| match mod_state_data.get(k) {
+                        Some(s) => Some((k, s, v)),
+                        None => {
+                            trace!(key = k, "no state saved, skipped");
+                            None
+                        }
                     })
                     .collect()
             };
@@ -884,24 +1654,63 @@ impl Cfg {
                 mods: collect_data_unchecked()
                     .iter()
                     .map(|(n, s, f)| {
-                        RegMod::new(
+                        let mut reg_mod = RegMod::new(
                             n,
                             parse_bool(s).unwrap_or(true),
-                            f.iter().map(PathBuf::from).collect(),
-                        )
+                            f.iter().map(|f| path_from_ini_value(f)).collect(),
+                        );
+                        reg_mod.hashes = self.saved_hashes(&reg_mod.files);
+                        reg_mod.file_set = self.file_set(&reg_mod.name);
+                        reg_mod.toggle_configs = self.toggles_configs(&reg_mod.name);
+                        reg_mod
                     })
                     .collect(),
                 warnings: None,
             };
         }
 
-        let collected_mods =
+        let mut collected_mods =
             self.sync_keys()
-                .combine_map_data(include_load_order, game_dir.as_ref(), self.path());
+                .combine_map_data(include_load_order, game_dir.as_ref(), self);
+        collected_mods.mods.iter_mut().for_each(|reg_mod| {
+            reg_mod.hashes = self.saved_hashes(&reg_mod.files);
+            reg_mod.file_set = self.file_set(&reg_mod.name);
+        });
         trace!("collected {} mods", collected_mods.mods.len());
         collected_mods
     }
 
+    /// runs `RegMod::verify_state` on every registered mod, unlike `collect_mods` a mod that fails to  
+    /// verify is not removed and does not stop the remaining mods from being verified  
+    /// only errors if the ini can not be read back into memory after all mods were verified
+    #[instrument(level = "trace", skip_all)]
+    pub fn verify_all(&mut self, game_dir: &Path) -> std::io::Result<VerifySummary> {
+        // `keys` syncs the state and file sections so the unchecked collect can not fail
+        self.keys();
+        let mut summary = VerifySummary::default();
+        for mut reg_mod in self.collect_mods(game_dir, None, true).mods {
+            let (prev_state, prev_dll) = (reg_mod.state, reg_mod.files.dll.clone());
+            match reg_mod.verify_state(game_dir, self.path()) {
+                Ok(()) if reg_mod.state != prev_state || reg_mod.files.dll != prev_dll => {
+                    summary.fixed.push(reg_mod.name)
+                }
+                Ok(()) => summary.unchanged.push(reg_mod.name),
+                Err(err) => {
+                    error!("{err}");
+                    summary.failed.push((reg_mod.name, err));
+                }
+            }
+        }
+        self.update()?;
+        info!(
+            fixed = summary.fixed.len(),
+            failed = summary.failed.len(),
+            unchanged = summary.unchanged.len(),
+            "Verified all mods"
+        );
+        Ok(summary)
+    }
+
     /// parses the data associated with a given key into a `RegMod` if found  
     #[instrument(level = "trace", skip_all)]
     pub fn get_mod(
@@ -910,14 +1719,20 @@ impl Cfg {
         game_dir: &Path,
         order_map: Option<&OrderMap>,
     ) -> std::io::Result<RegMod> {
-        let key = name.replace(' ', "_");
-        let split_files = if self.data().get_from(INI_SECTIONS[3], &key).ok_or_else(|| {
-            std::io::Error::new(
-                ErrorKind::InvalidInput,
-                format!("{key} not found in section: {}", INI_SECTIONS[3].unwrap()),
-            )
-        })? == ARRAY_VALUE
-        {
+        let key = self
+            .find_key(name)
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    ErrorKind::InvalidInput,
+                    format!(
+                        "{} not found in section: {}",
+                        format_key(name),
+                        INI_SECTIONS[3].unwrap()
+                    ),
+                )
+            })?
+            .to_string();
+        let split_files = if self.data().get_from(INI_SECTIONS[3], &key) == Some(ARRAY_VALUE) {
             SplitFiles::from(
                 IniProperty::<Vec<PathBuf>>::read(
                     self.data(),
@@ -930,14 +1745,8 @@ impl Cfg {
             )
         } else {
             SplitFiles::from(vec![
-                IniProperty::<PathBuf>::read(
-                    self.data(),
-                    INI_SECTIONS[3],
-                    &key,
-                    Some(game_dir),
-                    false,
-                )?
-                .value,
+                IniProperty::<PathBuf>::read_mod_file_path(self.data(), &key, game_dir, false)?
+                    .value,
             ])
         };
         Ok(RegMod {
@@ -947,11 +1756,221 @@ impl Cfg {
                 LoadOrder::default()
             },
             state: IniProperty::<bool>::read(self.data(), INI_SECTIONS[2], &key)?.value,
+            hashes: self.saved_hashes(&split_files),
+            file_set: self.file_set(&key),
+            toggle_configs: self.toggles_configs(&key),
             files: split_files,
             name: key,
         })
     }
 
+    /// registers `short_paths`, files already installed within `game_dir`, as a new mod named `name`  
+    /// this is the headless equivalent of adding files that are already installed from the app  
+    ///
+    /// the files must exist and not be registered to another mod, the load order is read from  
+    /// `loader_dir`, the state of the new mod is verified then saved to file and `self` is updated  
+    /// files within `track_dir` are tracked by directory, see `RegMod::track_by_dir`
+    #[instrument(level = "trace", skip(self, short_paths, game_dir, loader_dir))]
+    pub fn register_existing(
+        &mut self,
+        name: &str,
+        short_paths: Vec<PathBuf>,
+        game_dir: &Path,
+        loader_dir: &Path,
+        track_dir: Option<PathBuf>,
+    ) -> std::io::Result<RegMod> {
+        let key = format_key(name);
+        if key.is_empty() {
+            return new_io_error!(ErrorKind::InvalidInput, "Mod name can not be empty");
+        }
+        if short_paths.is_empty() {
+            return new_io_error!(ErrorKind::InvalidInput, "No files were given to register");
+        }
+        if self.keys().contains(&canonical_key(&key)) {
+            return new_io_error!(
+                ErrorKind::AlreadyExists,
+                format!(
+                    "There is already a registered mod with the name: {}",
+                    DisplayName(&key)
+                )
+            );
+        }
+        if let Some(absolute) = short_paths.iter().find(|file| file.is_absolute()) {
+            return new_io_error!(
+                ErrorKind::InvalidInput,
+                format!(
+                    "'{}', must be a short path with game_dir truncated",
+                    absolute.display()
+                )
+            );
+        }
+        if let Err(err) = short_paths.validate(Some(game_dir)) {
+            return Err(err.errors.merge(true));
+        }
+        let registered = self.files();
+        if let Some(file) = short_paths
+            .iter()
+            .find(|file| registered.contains(file.to_str().unwrap_or_default()))
+        {
+            return new_io_error!(
+                ErrorKind::AlreadyExists,
+                format!("'{}', is already registered to a mod", file.display())
+            );
+        }
+
+        let order_map = ModLoaderCfg::read(loader_dir)?.parse_into_map();
+        let mut reg_mod = RegMod::with_load_order(&key, true, short_paths, &order_map);
+        if !reg_mod.files.dll.is_empty() && reg_mod.files.dll.iter().all(FileData::is_disabled) {
+            reg_mod.state = false;
+        }
+        reg_mod.verify_state(game_dir, self.path())?;
+        if let Some(dir) = track_dir {
+            let dropped = reg_mod.track_by_dir(dir);
+            info!(dropped, "{} files tracked by directory", DisplayName(&key));
+        }
+        if let Err(err) = reg_mod.write_to_file(self.path(), false) {
+            if let Err(err) = reg_mod.remove_from_file(self.path()) {
+                error!("{err}");
+            }
+            return Err(err);
+        }
+        if self.get_hash_files() {
+            if let Err(err) = reg_mod.record_hashes(game_dir, self.path()) {
+                error!("Failed to record hashes for: {}, {err}", DisplayName(&key));
+            }
+        }
+        self.update()?;
+        info!(
+            files = reg_mod.files.len(),
+            state = %DisplayState(reg_mod.state),
+            order = %reg_mod.order,
+            "{} registered with",
+            DisplayName(&key)
+        );
+        Ok(reg_mod)
+    }
+
+    /// registers a copy of the mod `key` as `new_name`, all files of the mod are copied into  
+    /// "game_dir\mods\new_name" keeping their layout relative to the deepest directory they share  
+    /// dlls are renamed after `new_name` since load order is saved by file name  
+    /// the copy keeps the state, tracked directory and toggle configs flag of the original and  
+    /// starts without a set load order, the copy is saved to file and `self` is updated  
+    ///
+    /// errors if `new_name` is already registered or "mods\new_name" already exists
+    #[instrument(level = "trace", skip(self, game_dir))]
+    pub fn duplicate_mod(
+        &mut self,
+        key: &str,
+        new_name: &str,
+        game_dir: &Path,
+    ) -> std::io::Result<RegMod> {
+        let new_key = format_key(new_name);
+        if new_key.is_empty() {
+            return new_io_error!(ErrorKind::InvalidInput, "Mod name can not be empty");
+        }
+        if self.keys().contains(&canonical_key(&new_key)) {
+            return new_io_error!(
+                ErrorKind::AlreadyExists,
+                format!(
+                    "There is already a registered mod with the name: {}",
+                    DisplayName(&new_key)
+                )
+            );
+        }
+        let source = self.get_mod(&key.into(), game_dir, None)?;
+        let copy_dir = Path::new("mods").join(&new_key);
+        if game_dir.join(&copy_dir).try_exists()? {
+            return new_io_error!(
+                ErrorKind::AlreadyExists,
+                format!("'{}', already exists", copy_dir.display())
+            );
+        }
+
+        let from_paths = source.all_files(game_dir)?;
+        let short_paths = from_paths
+            .iter()
+            .map(|path| path.strip_prefix(game_dir).map(Path::to_path_buf))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))?;
+        let base_dir = short_paths
+            .iter()
+            .fold(None, |base: Option<&Path>, path| {
+                let base = base.or_else(|| path.parent())?;
+                base.ancestors().find(|dir| path.starts_with(dir))
+            })
+            .unwrap_or(Path::new(""))
+            .to_path_buf();
+        let remap = |short_path: &PathBuf| -> std::io::Result<PathBuf> {
+            match short_path.strip_prefix(&base_dir) {
+                Ok(relative) => Ok(copy_dir.join(relative)),
+                Err(err) => Err(std::io::Error::new(ErrorKind::InvalidData, err)),
+            }
+        };
+        let renamed_dlls = source
+            .files
+            .dll
+            .iter()
+            .enumerate()
+            .map(|(i, dll)| {
+                let mut file_name = match i {
+                    0 => format!("{new_key}.dll"),
+                    i => format!("{new_key}_{}.dll", i + 1),
+                };
+                if FileData::is_disabled(dll) {
+                    file_name.push_str(OFF_STATE);
+                }
+                Ok((dll, remap(dll)?.with_file_name(file_name)))
+            })
+            .collect::<std::io::Result<HashMap<_, _>>>()?;
+        let copy_path = |short_path: &PathBuf| match renamed_dlls.get(short_path) {
+            Some(renamed) => Ok(renamed.clone()),
+            None => remap(short_path),
+        };
+        let remap_all =
+            |files: &[PathBuf]| files.iter().map(copy_path).collect::<Result<Vec<_>, _>>();
+
+        let zip = from_paths
+            .iter()
+            .zip(short_paths.iter())
+            .map(|(from_path, short_path)| {
+                Ok((from_path.clone(), game_dir.join(copy_path(short_path)?)))
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        // progress is not reported for a duplicate, the receiver is dropped
+        let (progress, _) = std::sync::mpsc::channel();
+        copy_with_progress(&zip, false, &progress, &AtomicBool::new(false))?;
+
+        let files = SplitFiles {
+            dll: remap_all(&source.files.dll)?,
+            config: remap_all(&source.files.config)?,
+            other: remap_all(&source.files.other)?,
+        };
+        let mut reg_mod = RegMod::from_parts(&new_key, source.state, files, LoadOrder::default());
+        if let FileSet::Dir(ref dir) = source.file_set {
+            reg_mod.file_set = FileSet::Dir(remap(dir)?);
+        }
+        reg_mod.toggle_configs = source.toggle_configs;
+        if let Err(err) = reg_mod.write_to_file(self.path(), false) {
+            if let Err(err) = reg_mod.remove_from_file(self.path()) {
+                error!("{err}");
+            }
+            return Err(err);
+        }
+        if self.get_hash_files() {
+            if let Err(err) = reg_mod.record_hashes(game_dir, self.path()) {
+                error!("Failed to record hashes for: {}, {err}", DisplayName(&new_key));
+            }
+        }
+        self.update()?;
+        info!(
+            files = zip.len(),
+            "{} duplicated as: {}",
+            DisplayName(&source.name),
+            DisplayName(&new_key)
+        );
+        Ok(reg_mod)
+    }
+
     /// ensures that _all_ keys have matching keys in Sections: "registered-mods" and "mod-files"  
     /// returns CollectedMaps - `(state_map, mod_file_map)`
     #[instrument(level = "trace", skip_all)]
@@ -1009,7 +2028,20 @@ impl Cfg {
         (state_data, file_data)
     }
 
-    /// returns all the keys (as_lowercase) collected into a `Set`
+    /// returns the saved key that matches `name`, keys are matched by their `canonical_key`  
+    /// so differences in case, spacing and unicode normalization are ignored
+    pub fn find_key(&self, name: &str) -> Option<&str> {
+        let canonical = canonical_key(name);
+        self.data()
+            .section(INI_SECTIONS[3])
+            .expect("Validated by Ini::is_setup on startup")
+            .iter()
+            .map(|(k, _)| k)
+            .filter(|&k| k != ARRAY_KEY)
+            .find(|k| canonical_key(k) == canonical)
+    }
+
+    /// returns all the keys (as `canonical_key`) collected into a `Set`  
     /// this also calls sync keys if invalid keys are found
     #[instrument(level = "trace", skip_all)]
     pub fn keys(&mut self) -> HashSet<String> {
@@ -1019,14 +2051,14 @@ impl Cfg {
                 .section(INI_SECTIONS[2])
                 .expect("Validated by is_setup")
                 .iter()
-                .map(|(k, _)| k.to_lowercase())
+                .map(|(k, _)| canonical_key(k))
                 .collect::<HashSet<_>>();
             self.data()
                 .section(INI_SECTIONS[3])
                 .expect("Validated by is_setup")
                 .iter()
                 .filter_map(|(k, _)| if k != ARRAY_KEY { Some(k) } else { None })
-                .all(|mod_file_key| state_keys.contains(&mod_file_key.to_lowercase()))
+                .all(|mod_file_key| state_keys.contains(&canonical_key(mod_file_key)))
                 .then_some(state_keys)
         };
 
@@ -1036,7 +2068,7 @@ impl Cfg {
         }
         let registered_mods = {
             let (mods_map, _) = self.sync_keys();
-            mods_map.keys().map(|k| k.to_lowercase()).collect::<HashSet<_>>()
+            mods_map.keys().map(|k| canonical_key(k)).collect::<HashSet<_>>()
         };
         self.update().expect("already exists in an accessable directory");
         registered_mods
@@ -1097,10 +2129,208 @@ impl Cfg {
             order_removed,
         )
     }
+
+    /// returns the short paths of all files in "game_dir\mods" not registered to any mod, sorted  
+    /// files are compared ignoring case and state, restricted files, dlls in `unknown_keys` and  
+    /// files within a directory tracked by a mod are never included  
+    /// returns an empty `Vec` if the "mods" folder does not exist
+    #[instrument(level = "trace", skip_all)]
+    pub fn unregistered_files(
+        &self,
+        game_dir: &Path,
+        unknown_keys: &HashSet<String>,
+    ) -> std::io::Result<Vec<PathBuf>> {
+        let mods_dir = game_dir.join("mods");
+        if !mods_dir.try_exists()? {
+            return Ok(Vec::new());
+        }
+        let registered = self
+            .iter_file_entries()
+            .flat_map(|(_, files)| files)
+            .map(|file| omit_off_state(file).to_lowercase())
+            .collect::<HashSet<_>>();
+        let tracked_dirs = self
+            .data()
+            .section(TRACKED_DIRS_SECTION)
+            .map(|dirs| {
+                dirs.iter()
+                    .map(|(_, dir)| normalize_separators(PathBuf::from(dir.to_lowercase())))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let unknown_keys = unknown_keys
+            .iter()
+            .map(|key| key.to_lowercase())
+            .collect::<HashSet<_>>();
+        let mut unregistered = files_in_dir_tree(&mods_dir)?
+            .into_iter()
+            .filter_map(|file| {
+                let short_path = file.strip_prefix(game_dir).ok()?;
+                let path_str = short_path.to_string_lossy();
+                let lowercase = omit_off_state(&path_str).to_lowercase();
+                let is_registered = registered.contains(&lowercase)
+                    || tracked_dirs.iter().any(|dir| Path::new(&lowercase).starts_with(dir))
+                    || unknown_keys.contains(file_name_from_str(&lowercase))
+                    || is_restricted_file(short_path);
+                (!is_registered).then(|| short_path.to_path_buf())
+            })
+            .collect::<Vec<_>>();
+        unregistered.sort();
+        Ok(unregistered)
+    }
+
+    /// compares the saved data against the files in `game_dir` and "mod_loader_config.ini"  
+    /// nothing is written to file and no files are toggled, see `DiagnosisReport` for what is checked  
+    /// dlls in `unknown_keys` are not reported as unregistered files
+    #[instrument(level = "trace", skip_all)]
+    pub fn diagnose(&self, game_dir: &Path, unknown_keys: &HashSet<String>) -> DiagnosisReport {
+        let mut report = DiagnosisReport::default();
+        // read without `get_hash_files` so a missing setting is not saved
+        let check_hashes = IniProperty::<bool>::read(self.data(), INI_SECTIONS[0], INI_KEYS[9])
+            .map_or(DEFAULT_HASH_FILES, |setting| setting.value);
+        let state_data = self
+            .data()
+            .section(INI_SECTIONS[2])
+            .expect("Validated by Ini::is_setup on startup");
+        let file_data = PropertyArray(
+            self.data()
+                .section(INI_SECTIONS[3])
+                .expect("Validated by Ini::is_setup on startup"),
+        )
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+        for (name, state) in state_data.iter() {
+            let Some(files) = file_data.get(name) else {
+                report.missing_files.push((name.to_string(), Vec::new()));
+                continue;
+            };
+            let state = parse_bool(state).ok();
+            let mut missing = Vec::new();
+            let mut dlls_on_disk = Vec::new();
+            for file in files {
+                let path = path_from_ini_value(file);
+                let is_dll = FileData::from(&path.to_string_lossy()).extension == ".dll";
+                let on_disk = if matches!(game_dir.join(&path).try_exists(), Ok(true)) {
+                    path
+                } else if is_dll
+                    && matches!(game_dir.join(toggle_path_state(&path)).try_exists(), Ok(true))
+                {
+                    toggle_path_state(&path)
+                } else {
+                    missing.push(path);
+                    continue;
+                };
+                if is_dll {
+                    dlls_on_disk.push(on_disk);
+                }
+            }
+            // matches `RegMod::verify_state`, a mod is enabled if any of its dlls are enabled
+            let any_enabled = dlls_on_disk.iter().any(FileData::is_enabled);
+            if !dlls_on_disk.is_empty() && state.is_some_and(|state| any_enabled != state) {
+                report.state_mismatch.push(name.to_string());
+            }
+            if !missing.is_empty() {
+                report.missing_files.push((name.to_string(), missing));
+            }
+            if check_hashes {
+                let mut reg_mod = RegMod::from_parts(
+                    name,
+                    state.unwrap_or(true),
+                    SplitFiles::from(
+                        files.iter().map(|f| path_from_ini_value(f)).collect::<Vec<_>>(),
+                    ),
+                    LoadOrder::default(),
+                );
+                reg_mod.hashes = self.saved_hashes(&reg_mod.files);
+                let changed = reg_mod.verify_hashes(game_dir);
+                if !changed.is_empty() {
+                    report.changed_files.push((name.to_string(), changed));
+                }
+            }
+        }
+
+        match self.unregistered_files(game_dir, unknown_keys) {
+            Ok(files) => report.unregistered_files = files,
+            Err(err) => warn!("Could not read: '{}', {err}", game_dir.join("mods").display()),
+        }
+
+        let loader_cfg = loader_cfg_path(game_dir);
+        if matches!(loader_cfg.try_exists(), Ok(true)) {
+            match get_cfg(&loader_cfg) {
+                Ok(loader_data) => {
+                    let dll_set = file_data
+                        .values()
+                        .flatten()
+                        .filter(|f| FileData::from(f).extension == ".dll")
+                        .map(|f| omit_off_state(file_name_from_str(f)))
+                        .collect::<DllSet>();
+                    let loader = <ModLoaderCfg as Config>::from(loader_data, &loader_cfg);
+                    report.orphaned_orders = loader
+                        .unregistered_keys(&dll_set)
+                        .into_iter()
+                        .map(|(k, _)| k)
+                        .collect();
+                }
+                Err(err) => warn!("{err}"),
+            }
+        }
+        trace!(healthy = report.is_healthy(), "diagnosis complete");
+        report
+    }
+}
+
+/// returns the `path()` of every file found in the directory tree of `dir`  
+/// can error on fs::read_dir
+fn files_in_dir_tree(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    fn collect_loop(files: &mut Vec<PathBuf>, path: &Path) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(path)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_file() {
+                files.push(entry.path());
+            } else if file_type.is_dir() {
+                collect_loop(files, &entry.path())?;
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    collect_loop(&mut files, dir)?;
+    Ok(files)
 }
 
+/// iterates over a section where each key is saved with either a single value or an array  
+/// an array is saved as `key=ARRAY_VALUE` followed by one `ARRAY_KEY=value` line per entry  
+/// each item is (`key`, `values`), a single value is returned as a `Vec` with one entry
+///
+/// ```
+/// use elden_mod_loader_gui::utils::ini::parser::PropertyArray;
+/// use ini::Ini;
+///
+/// let ini = Ini::load_from_str_noescape(
+///     "[mod-files]\n\
+///     single=mods\\single.dll\n\
+///     multi=array\n\
+///     array[]=mods\\multi.dll\n\
+///     array[]=mods\\multi\\config.ini\n",
+/// )
+/// .unwrap();
+/// let entries = PropertyArray(ini.section(Some("mod-files")).unwrap())
+///     .into_iter()
+///     .collect::<Vec<_>>();
+/// assert_eq!(
+///     entries,
+///     vec![
+///         ("single", vec!["mods\\single.dll"]),
+///         ("multi", vec!["mods\\multi.dll", "mods\\multi\\config.ini"]),
+///     ]
+/// );
+/// ```
 pub struct PropertyArray<'a>(pub &'a ini::Properties);
 
+/// see `PropertyArray`
 pub struct PropertyArrayIter<'a> {
     iter: ini::PropertyIter<'a>,
     next_up_key: &'a str,
@@ -1,18 +1,24 @@
 use ini::{EscapePolicy, Ini, LineSeparator, WriteOption};
-use tracing::{info, instrument, trace};
+use tracing::{info, instrument, trace, warn};
 
 use std::{
+    ffi::OsString,
     fmt::Display,
     fs::{self, read_to_string, write, File},
     io::{Error, ErrorKind, Result, Write},
-    path::Path,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
 };
 
 use crate::{
-    file_name_or_err, get_cfg, new_io_error, omit_off_state, parent_or_err,
-    utils::ini::parser::RegMod, DisplayName, ARRAY_KEY, ARRAY_VALUE, DEFAULT_INI_VALUES,
-    DEFAULT_LOADER_VALUES, INI_KEYS, INI_NAME, INI_SECTIONS, LOADER_FILES, LOADER_KEYS,
-    LOADER_SECTIONS,
+    file_name_or_err, get_cfg, new_io_error, omit_off_state, parent_or_err, rename_with_retry,
+    utils::{
+        ini::parser::{hash_key, FileHashes, RegMod},
+        watcher::mark_self_write,
+    },
+    DisplayName, ARRAY_KEY, ARRAY_VALUE, DEFAULT_BACKUP_CONFIGS, DEFAULT_INI_VALUES,
+    DEFAULT_LOADER_VALUES, HASHES_SECTION, INI_KEYS, INI_NAME, INI_SECTIONS, LOADER_FILES,
+    LOADER_KEYS, LOADER_SECTIONS, SCHEMA_VERSION,
 };
 
 pub const WRITE_OPTIONS: WriteOption = WriteOption {
@@ -37,12 +43,13 @@ pub fn save_paths<P: AsRef<Path>>(
     let mut config: Ini = get_cfg(file_path)?;
     let save_paths = files
         .iter()
-        .map(|path| path.as_ref().to_string_lossy())
+        .map(|path| path.as_ref().to_string_lossy().replace('/', "\\"))
         .collect::<Vec<_>>()
         .join(&format!("\r\n{ARRAY_KEY}="));
     config
         .with_section(section)
         .set(key, format!("{ARRAY_VALUE}\r\n{ARRAY_KEY}={save_paths}"));
+    mark_self_write();
     config.write_to_file_opt(file_path, WRITE_OPTIONS)?;
     trace!("saved paths to file");
     Ok(())
@@ -54,6 +61,7 @@ pub fn save_path(file_path: &Path, section: Option<&str>, key: &str, path: &Path
     config
         .with_section(section)
         .set(key, path.to_string_lossy().to_string());
+    mark_self_write();
     config.write_to_file_opt(file_path, WRITE_OPTIONS)?;
     trace!("saved path to file");
     if let Some(span) = tracing::Span::current().metadata() {
@@ -68,11 +76,22 @@ pub fn save_path(file_path: &Path, section: Option<&str>, key: &str, path: &Path
 pub fn save_bool(file_path: &Path, section: Option<&str>, key: &str, value: bool) -> Result<()> {
     let mut config: Ini = get_cfg(file_path)?;
     config.with_section(section).set(key, value.to_string());
+    mark_self_write();
     config.write_to_file_opt(file_path, WRITE_OPTIONS)?;
     trace!("saved bool to file");
     Ok(())
 }
 
+#[instrument(level = "trace", skip(file_path, section), fields(section = section.unwrap()))]
+pub fn save_value(file_path: &Path, section: Option<&str>, key: &str, value: &str) -> Result<()> {
+    let mut config: Ini = get_cfg(file_path)?;
+    config.with_section(section).set(key, value);
+    mark_self_write();
+    config.write_to_file_opt(file_path, WRITE_OPTIONS)?;
+    trace!("saved value to file");
+    Ok(())
+}
+
 #[instrument(level = "trace", skip(file_path, section), fields(section = section.unwrap()))]
 pub fn save_value_ext(
     file_path: &Path,
@@ -82,11 +101,122 @@ pub fn save_value_ext(
 ) -> Result<()> {
     let mut config: Ini = get_cfg(file_path)?;
     config.with_section(section).set(key, value);
+    mark_self_write();
     config.write_to_file_opt(file_path, EXT_OPTIONS)?;
     trace!("saved value to file");
     Ok(())
 }
 
+/// saves each (`hash_key`, `sha256`) in `hashes` to section "file-hashes", existing keys are replaced
+#[instrument(level = "trace", skip_all)]
+pub fn save_hashes(file_path: &Path, hashes: &FileHashes) -> Result<()> {
+    let mut config: Ini = get_cfg(file_path)?;
+    hashes.iter().for_each(|(key, hash)| {
+        config.with_section(HASHES_SECTION).set(key, hash);
+    });
+    mark_self_write();
+    config.write_to_file_opt(file_path, WRITE_OPTIONS)?;
+    trace!(count = hashes.len(), "saved hashes to file");
+    Ok(())
+}
+
+/// removes the saved hash of each file in `files`, only writes to file if a hash was removed
+#[instrument(level = "trace", skip_all)]
+pub fn remove_hashes<P: AsRef<Path>>(file_path: &Path, files: &[P]) -> Result<()> {
+    let mut config: Ini = get_cfg(file_path)?;
+    let removed = files
+        .iter()
+        .filter(|file| {
+            config
+                .delete_from(HASHES_SECTION, &hash_key(file.as_ref()))
+                .is_some()
+        })
+        .count();
+    if removed == 0 {
+        return Ok(());
+    }
+    mark_self_write();
+    config.write_to_file_opt(file_path, WRITE_OPTIONS)?;
+    trace!(removed, "removed hashes from file");
+    Ok(())
+}
+
+static BACKUP_CONFIGS: AtomicBool = AtomicBool::new(DEFAULT_BACKUP_CONFIGS);
+
+/// enables or disables `backup_file`, kept in sync with the app-setting "backup_configs"
+pub fn set_backup_configs(state: bool) {
+    BACKUP_CONFIGS.store(state, Ordering::Relaxed);
+}
+
+/// returns the path of the single rolling backup kept for `file_path`: "<file_name>.bak"
+pub fn backup_path(file_path: &Path) -> PathBuf {
+    let mut backup = OsString::from(file_path.as_os_str());
+    backup.push(".bak");
+    PathBuf::from(backup)
+}
+
+/// returns the path `write_atomic` writes to before replacing `file_path`: "<file_name>.tmp"
+pub fn temp_path(file_path: &Path) -> PathBuf {
+    let mut temp = OsString::from(file_path.as_os_str());
+    temp.push(".tmp");
+    PathBuf::from(temp)
+}
+
+/// writes `data` to `temp_path(file_path)` then renames it over `file_path`, so an interrupted or  
+/// failed write never leaves `file_path` partially written. on windows `fs::rename` replaces an  
+/// existing file, a rename blocked by another process holding `file_path` open is retried  
+/// on failure the temp file is removed and `file_path` is left untouched
+#[instrument(level = "trace", skip(data, options), fields(path = %file_path.display()))]
+pub fn write_atomic(data: &Ini, file_path: &Path, options: WriteOption) -> Result<()> {
+    let temp = temp_path(file_path);
+    let result = File::create(&temp)
+        .and_then(|mut file| {
+            data.write_to_opt(&mut file, options)?;
+            file.sync_all()
+        })
+        .and_then(|_| rename_with_retry(&temp, file_path));
+    if result.is_err() && matches!(temp.try_exists(), Ok(true)) {
+        if let Err(err) = fs::remove_file(&temp) {
+            warn!("Failed to remove: '{}', {err}", temp.display());
+        }
+    }
+    result
+}
+
+/// copies `file_path` to `backup_path(file_path)` if "backup_configs" is enabled  
+/// a failed copy is only logged so the write that follows is never stopped by the backup
+pub fn backup_file(file_path: &Path) {
+    if !BACKUP_CONFIGS.load(Ordering::Relaxed) || !matches!(file_path.try_exists(), Ok(true)) {
+        return;
+    }
+    match fs::copy(file_path, backup_path(file_path)) {
+        Ok(_) => trace!(file = ?file_path.file_name(), "backup saved"),
+        Err(err) => warn!("Failed to back up: '{}', {err}", file_path.display()),
+    }
+}
+
+/// swaps the contents of `file_path` with its backup made by `backup_file`  
+/// restoring a second time returns `file_path` to what it was before the first restore
+#[instrument(level = "trace", skip_all, fields(path = %file_path.display()))]
+pub fn restore_backup(file_path: &Path) -> Result<()> {
+    let backup_dir = backup_path(file_path);
+    if !matches!(backup_dir.try_exists(), Ok(true)) {
+        return new_io_error!(
+            ErrorKind::NotFound,
+            format!("No backup found for: {:?}", file_name_or_err(file_path)?)
+        );
+    }
+    let backup = fs::read(&backup_dir)?;
+    let current = fs::read(file_path).ok();
+    mark_self_write();
+    write(file_path, backup)?;
+    if let Some(current) = current {
+        write(&backup_dir, current)?;
+    }
+    info!("Restored backup of: {:?}", file_name_or_err(file_path)?);
+    Ok(())
+}
+
 fn init_default_values<K, V>(
     writer: &mut File,
     sections: &[Option<&str>],
@@ -119,17 +249,18 @@ pub fn new_cfg(path: &Path) -> Result<Ini> {
     let parent = parent_or_err(path)?;
 
     fs::create_dir_all(parent)?;
+    mark_self_write();
     let mut new_ini = File::create(path)?;
 
     match file_name {
         f_name if f_name == INI_NAME => {
-            init_default_values(
-                &mut new_ini,
-                &INI_SECTIONS,
-                &INI_KEYS,
-                &DEFAULT_INI_VALUES,
-                &WRITE_OPTIONS,
-            )?;
+            let keys = [INI_KEYS[0], INI_KEYS[1], INI_KEYS[11]];
+            let values = [
+                DEFAULT_INI_VALUES[0].to_string(),
+                DEFAULT_INI_VALUES[1].to_string(),
+                SCHEMA_VERSION.to_string(),
+            ];
+            init_default_values(&mut new_ini, &INI_SECTIONS, &keys, &values, &WRITE_OPTIONS)?;
             info!("Created new ini: {}", INI_NAME);
         }
         f_name if f_name == LOADER_FILES[3] => {
@@ -168,6 +299,7 @@ pub fn remove_array(file_path: &Path, key: &str) -> Result<()> {
 
     let lines = content.lines().filter(|&line| filter_lines(line)).collect::<Vec<_>>();
 
+    mark_self_write();
     write(file_path, lines.join("\r\n"))?;
     trace!("removed paths from file");
     Ok(())
@@ -182,11 +314,75 @@ pub fn remove_entry(file_path: &Path, section: Option<&str>, key: &str) -> Resul
             &section.expect("Passed in section should be valid")
         ))
     })?;
+    mark_self_write();
     config.write_to_file_opt(file_path, WRITE_OPTIONS)?;
     trace!("removed entry from file");
     Ok(())
 }
 
+/// sets or removes (`value` is `None`) the entry `key` in `section` by only editing the line it is on  
+/// a new entry is added after the last entry of `section`, all other lines including comments are  
+/// left unchanged
+#[instrument(level = "trace", skip(file_path, write_options), fields(section = section.unwrap()))]
+pub fn write_entry_in_place(
+    file_path: &Path,
+    section: Option<&str>,
+    key: &str,
+    value: Option<&str>,
+    write_options: &WriteOption,
+) -> Result<()> {
+    let section = section.expect("Passed in section should be valid");
+    let content = read_to_string(file_path)?;
+    let line_separator = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let new_line = value.map(|value| format!("{key}{}{value}", write_options.kv_separator));
+
+    let mut lines = content.lines().map(str::to_string).collect::<Vec<_>>();
+    let (mut in_section, mut key_line, mut last_entry) = (false, None, None);
+    for (i, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        if line.starts_with('[') && line.ends_with(']') {
+            in_section = line[1..line.len() - 1].trim() == section;
+            if in_section {
+                last_entry = Some(i);
+            }
+            continue;
+        }
+        if !in_section || line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+        last_entry = Some(i);
+        if line.split_once('=').is_some_and(|(k, _)| k.trim() == key) {
+            key_line = Some(i);
+            break;
+        }
+    }
+    match (key_line, new_line) {
+        (Some(i), Some(new_line)) => lines[i] = new_line,
+        (Some(i), None) => {
+            lines.remove(i);
+        }
+        (None, Some(new_line)) => match last_entry {
+            Some(i) => lines.insert(i + 1, new_line),
+            None => lines.extend([format!("[{section}]"), new_line]),
+        },
+        (None, None) => {
+            return new_io_error!(
+                ErrorKind::NotFound,
+                format!("Could not delete: {key}, from Section: {section}")
+            );
+        }
+    }
+    let mut new_content = lines.join(line_separator);
+    if content.ends_with('\n') {
+        new_content.push_str(line_separator);
+    }
+    backup_file(file_path);
+    mark_self_write();
+    write(file_path, new_content)?;
+    trace!("wrote entry in place");
+    Ok(())
+}
+
 #[instrument(level = "trace", skip(loader_dir), fields(mod_name = entry.name))]
 pub fn remove_order_entry(entry: &RegMod, loader_dir: &Path) -> Result<()> {
     if !entry.order.set {
@@ -195,7 +391,13 @@ pub fn remove_order_entry(entry: &RegMod, loader_dir: &Path) -> Result<()> {
             format!("{} has no order data", DisplayName(&entry.name))
         );
     }
-    let file_name = file_name_or_err(&entry.files.dll[entry.order.i])?;
+    let Some(order_i) = entry.validated_order_index() else {
+        return new_io_error!(
+            ErrorKind::InvalidData,
+            format!("{} load order index is out of range", DisplayName(&entry.name))
+        );
+    };
+    let file_name = file_name_or_err(&entry.files.dll[order_i])?;
     let file_name = file_name.to_str().ok_or_else(|| {
         Error::new(
             ErrorKind::InvalidData,
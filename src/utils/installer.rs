@@ -1,14 +1,29 @@
 use std::{
     collections::HashSet,
+    ffi::OsString,
     io::ErrorKind,
-    path::{Path, PathBuf},
+    path::{Component, Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::Sender,
+    },
 };
 use tracing::{error, info, instrument, trace};
 
 use crate::{
-    does_dir_contain, file_name_from_str, file_name_or_err, new_io_error, parent_or_err,
-    utils::ini::{parser::RegMod, writer::remove_order_entry},
-    FileData,
+    canonical_key, does_dir_contain, file_name_from_str, is_restricted_file, new_io_error,
+    omit_off_state, parent_or_err,
+    utils::{
+        error::EmlError,
+        ini::{
+            common::{Cfg, Config},
+            parser::RegMod,
+            writer::remove_order_entry,
+        },
+        subscriber::OpTimer,
+        watcher::mark_self_write,
+    },
+    FileData, LOCK_VIOLATION, MAX_PATTERN_MATCHES, SHARING_VIOLATION,
 };
 
 /// returns the deepest occurance of a directory that contains at least 1 file  
@@ -97,14 +112,14 @@ fn items_in_directory(path: &Path, f_type: FileType) -> std::io::Result<usize> {
 }
 
 /// returns `Ok(num)` of files in a dir_tree,  
-/// returns `Err(InvalidData)` if _any_ symlink is found or fs::read_dir err
+/// returns `EmlError::SymlinkFound` naming the first symlink found or fs::read_dir err
 fn files_in_directory_tree(directory: &Path) -> std::io::Result<usize> {
     fn count_loop(count: &mut usize, path: &Path) -> std::io::Result<()> {
         for entry in std::fs::read_dir(path)? {
             let entry = entry?;
             let metadata = entry.metadata()?;
             if metadata.is_symlink() {
-                return new_io_error!(ErrorKind::InvalidData, "Unsuported file type");
+                return Err(EmlError::SymlinkFound(entry.path()).into());
             } else if metadata.is_file() {
                 *count += 1;
             } else if metadata.is_dir() {
@@ -120,14 +135,14 @@ fn files_in_directory_tree(directory: &Path) -> std::io::Result<usize> {
 }
 
 /// returns `Ok(true)` if dir_tree contains no files, note directories are not counted as files  
-/// returns `Err(InvalidData)` if _any_ symlink is found or fs::read_dir err
+/// returns `EmlError::SymlinkFound` naming the first symlink found or fs::read_dir err
 fn directory_tree_is_empty(directory: &Path) -> std::io::Result<bool> {
     fn lookup_loop(path: &Path) -> std::io::Result<bool> {
         for entry in std::fs::read_dir(path)? {
             let entry = entry?;
             let metadata = entry.metadata()?;
             if metadata.is_symlink() {
-                return new_io_error!(ErrorKind::InvalidData, "Unsuported file type");
+                return Err(EmlError::SymlinkFound(entry.path()).into());
             } else if metadata.is_file() || (metadata.is_dir() && !lookup_loop(&entry.path())?) {
                 return Ok(false);
             }
@@ -214,6 +229,17 @@ struct CutoffData {
     counter: usize,
 }
 
+/// what `InstallData::update_fields_with_new_dir` changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AddDirSummary {
+    /// number of files imported from the selected directory
+    pub files_added: usize,
+    /// the selected directory contained the original files, so they were re-read from it
+    pub reconstructed: bool,
+    /// the selected directory contained only new files, so it is installed as a whole
+    pub moved_whole_folder: bool,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct InstallData {
     pub name: String,
@@ -241,31 +267,23 @@ impl InstallData {
         Ok(data)
     }
 
+    /// creates a new `InstallData` with no files selected, use `update_fields_with_new_dir` to add the  
+    /// contents of a directory
+    pub fn empty(name: &str, game_dir: &Path) -> Self {
+        InstallData {
+            name: String::from(name),
+            install_dir: game_dir.join("mods"),
+            ..Default::default()
+        }
+    }
+
     /// creates a new `InstallData` from a previously installed `RegMod` and amends a new collection of files  
     pub fn amend(
         amend_to: &RegMod,
         file_paths: Vec<PathBuf>,
         game_dir: &Path,
     ) -> std::io::Result<Self> {
-        let dll_names = amend_to.files.dll.iter().try_fold(
-            Vec::with_capacity(amend_to.files.len()),
-            |mut acc, file| {
-                let file_name = file_name_or_err(file)?.to_str().ok_or_else(|| {
-                    std::io::Error::new(ErrorKind::InvalidData, "File name is not valid unicode")
-                })?;
-                acc.push(FileData::from(file_name).name);
-                Ok::<Vec<&str>, std::io::Error>(acc)
-            },
-        )?;
-        let mut install_dir = game_dir.join("mods");
-        if dll_names.len() == 1 {
-            install_dir = install_dir.join(dll_names[0]);
-        } else {
-            return new_io_error!(
-                ErrorKind::InvalidInput,
-                "Could not determine the proper file structure for installing files"
-            );
-        }
+        let install_dir = amend_to.install_dir(game_dir)?;
         let parent_dir = parent_dir_from_vec(&file_paths)?;
         let mut data = InstallData {
             name: String::from(&amend_to.name),
@@ -341,6 +359,13 @@ impl InstallData {
                 "collect_to_paths either failed or was not ran"
             );
         }
+        let game_dir = self.game_dir();
+        for to_path in self.to_paths.iter() {
+            if let Err(err) = check_path_within(game_dir, to_path) {
+                error!(to_path = %to_path.display(), "{err}");
+                return Err(err);
+            }
+        }
         Ok(self
             .from_paths
             .iter()
@@ -349,6 +374,16 @@ impl InstallData {
             .collect::<Vec<_>>())
     }
 
+    /// returns the game directory `self.install_dir` is located in  
+    /// `install_dir` is always "game_dir\mods" or a sub-directory of "mods"
+    fn game_dir(&self) -> &Path {
+        self.install_dir
+            .ancestors()
+            .find(|path| path.ends_with("mods"))
+            .and_then(Path::parent)
+            .unwrap_or(&self.install_dir)
+    }
+
     /// use `update_fields_with_new_dir` when installing a mod from outside the game_dir  
     /// this function is for internal use only and contians no saftey checks
     #[instrument(level = "trace", skip(self, directory), fields(valid_dir = %directory.display()))]
@@ -423,17 +458,17 @@ impl InstallData {
         Ok(())
     }
 
-    /// adds a directories contents to a `InstallData::new()`  
+    /// adds a directories contents to a `InstallData::new()` and returns a summary of what was added  
     /// **Note:** subsequent runs of this funciton is not tested and not expected to work
     #[instrument(level = "trace", skip_all, fields(in_dir = %new_directory.display()))]
     pub async fn update_fields_with_new_dir(
         &mut self,
         new_directory: &Path,
         cutoff: DisplayItems,
-    ) -> std::io::Result<()> {
+    ) -> std::io::Result<AddDirSummary> {
         let mut self_clone = self.clone();
         let valid_dir = check_dir_contains_files(new_directory)?;
-        let jh = std::thread::spawn(move || -> std::io::Result<InstallData> {
+        let jh = std::thread::spawn(move || -> std::io::Result<(InstallData, AddDirSummary)> {
             let game_dir = self_clone.install_dir.parent().expect("has parent");
             if valid_dir.starts_with(game_dir) {
                 return new_io_error!(ErrorKind::InvalidInput, "Files are already installed");
@@ -444,9 +479,12 @@ impl InstallData {
                 return new_io_error!(ErrorKind::InvalidData, "Invalid file structure");
             }
 
+            let mut summary = AddDirSummary::default();
+            let selected_files = self_clone.from_paths.clone();
             if self_clone.parent_dir.starts_with(&valid_dir) {
                 trace!("Selected directory contains the original files, reconstructing data");
                 self_clone.reconstruct(&valid_dir);
+                summary.reconstructed = true;
             } else if valid_dir.ends_with("mods")
                 && items_in_directory(parent_or_err(&valid_dir)?, FileType::File)? > 0
             {
@@ -454,20 +492,33 @@ impl InstallData {
             } else {
                 trace!("Selected directory contains unique files, entire folder will be moved");
                 self_clone.parent_dir = parent_or_err(&valid_dir)?.to_path_buf();
+                summary.moved_whole_folder = true;
             }
 
             self_clone.import_files_from_dir(&valid_dir, cutoff)?;
+            // when reconstructing the original selection is read again, it is not counted as added
+            summary.files_added = self_clone
+                .from_paths
+                .iter()
+                .filter(|file| !selected_files.contains(file))
+                .count();
+            if let Some(file) = self_clone.from_paths.iter().find(|f| is_restricted_file(f)) {
+                return new_io_error!(
+                    ErrorKind::InvalidData,
+                    format!("Tried to add a restricted file: '{}'", file.display())
+                );
+            }
 
             if self_clone.to_paths.len() != self_clone.from_paths.len() {
                 self_clone.collect_to_paths();
             }
-            Ok(self_clone)
+            Ok((self_clone, summary))
         });
         match jh.join() {
             Ok(result) => match result {
-                Ok(mut data) => {
+                Ok((mut data, summary)) => {
                     std::mem::swap(&mut data, self);
-                    Ok(())
+                    Ok(summary)
                 }
                 Err(err) => Err(err),
             },
@@ -479,16 +530,405 @@ impl InstallData {
     }
 }
 
+/// lexically normalizes `path` by removing `.` components and resolving `..` components  
+/// unlike `canonicalize` this does not require `path` to exist and does not resolve symlinks
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => {
+                if matches!(normalized.components().next_back(), Some(Component::Normal(_))) {
+                    normalized.pop();
+                } else {
+                    normalized.push(component);
+                }
+            }
+            Component::Prefix(_) | Component::RootDir | Component::Normal(_) => {
+                normalized.push(component)
+            }
+        }
+    }
+    normalized
+}
+
+/// returns the normalized `path` if it resolves to a location within `base`  
+/// errors with `ErrorKind::InvalidInput` if `path` escapes `base`
+pub fn check_path_within(base: &Path, path: &Path) -> std::io::Result<PathBuf> {
+    let normalized = normalize_path(path);
+    if !normalized.starts_with(normalize_path(base)) {
+        return new_io_error!(
+            ErrorKind::InvalidInput,
+            format!(
+                "Install path: '{}', resolves outside of: '{}'",
+                path.display(),
+                base.display()
+            )
+        );
+    }
+    Ok(normalized)
+}
+
+/// expands the glob `pattern` relative to `game_dir` into the short paths of the files it matches  
+/// case is ignored, errors if `pattern` resolves outside of `game_dir`, matches a restricted file, matches no files  
+/// or matches more than `MAX_PATTERN_MATCHES` files
+#[instrument(level = "trace", skip(game_dir))]
+pub fn expand_file_pattern(game_dir: &Path, pattern: &str) -> std::io::Result<Vec<PathBuf>> {
+    let pattern = pattern.trim();
+    let relative = Path::new(pattern);
+    if relative.has_root()
+        || !normalize_path(&game_dir.join(relative)).starts_with(normalize_path(game_dir))
+    {
+        return new_io_error!(
+            ErrorKind::InvalidInput,
+            format!("Pattern: '{pattern}', must be relative to the game directory")
+        );
+    }
+    let root = glob::Pattern::escape(&game_dir.to_string_lossy());
+    let full_pattern = Path::new(&root).join(relative);
+    let options = glob::MatchOptions {
+        case_sensitive: false,
+        ..Default::default()
+    };
+    let entries = glob::glob_with(&full_pattern.to_string_lossy(), options).map_err(|err| {
+        std::io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("Invalid pattern: '{pattern}', {err}"),
+        )
+    })?;
+
+    let mut short_paths = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(glob::GlobError::into_error)?;
+        if !path.is_file() {
+            continue;
+        }
+        if is_restricted_file(&path) {
+            return new_io_error!(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Pattern: '{pattern}', matches restricted file: '{}'",
+                    path.display()
+                )
+            );
+        }
+        if short_paths.len() == MAX_PATTERN_MATCHES {
+            return new_io_error!(
+                ErrorKind::InvalidInput,
+                format!("Pattern: '{pattern}', matches more than {MAX_PATTERN_MATCHES} files")
+            );
+        }
+        let short_path = path.strip_prefix(game_dir).map_err(|err| {
+            std::io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("{err}: '{}'", path.display()),
+            )
+        })?;
+        short_paths.push(short_path.to_path_buf());
+    }
+    if short_paths.is_empty() {
+        return new_io_error!(
+            ErrorKind::NotFound,
+            format!("Pattern: '{pattern}', did not match any files")
+        );
+    }
+    trace!(matched = short_paths.len(), "pattern expanded");
+    Ok(short_paths)
+}
+
+/// progress of a copy operation sent by `copy_with_progress`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CopyProgress {
+    pub files_done: usize,
+    pub files_total: usize,
+    pub bytes_done: u64,
+}
+
+/// how a file was placed at its destination by `copy_with_progress`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transfer {
+    Copied,
+    /// the file was renamed into place, the source no longer exists
+    Moved,
+    /// a move across volumes, the file was copied and the source is removed once all files are placed
+    CopiedForMove,
+}
+
+/// places `from_path` at `to_path`, returns how it was placed and the size of the file  
+/// if `move_files` the file is renamed with `rename`, falling back to a copy if `to_path` is on another volume
+pub fn transfer_file<R>(
+    from_path: &Path,
+    to_path: &Path,
+    move_files: bool,
+    rename: R,
+) -> std::io::Result<(Transfer, u64)>
+where
+    R: Fn(&Path, &Path) -> std::io::Result<()>,
+{
+    if !move_files {
+        return Ok((Transfer::Copied, std::fs::copy(from_path, to_path)?));
+    }
+    match rename(from_path, to_path) {
+        Ok(()) => Ok((Transfer::Moved, to_path.metadata()?.len())),
+        Err(err) if err.kind() == ErrorKind::CrossesDevices => {
+            trace!(file = %from_path.display(), "can not rename across volumes, copying instead");
+            Ok((Transfer::CopiedForMove, std::fs::copy(from_path, to_path)?))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// returns the path a file that is about to be overwritten is kept at until an install finishes:  
+/// "<file_name>.replaced"
+pub fn replaced_path(file_path: &Path) -> PathBuf {
+    let mut replaced = OsString::from(file_path.as_os_str());
+    replaced.push(".replaced");
+    PathBuf::from(replaced)
+}
+
+/// a file placed by `copy_with_progress`
+struct Placed<'a> {
+    from_path: &'a Path,
+    to_path: &'a Path,
+    transfer: Transfer,
+    /// where the file previously at `to_path` was moved, see `replaced_path`
+    replaced: Option<PathBuf>,
+}
+
+/// copies each `(from_path, to_path)` creating any missing parent directories, a `CopyProgress` is sent  
+/// through `progress` after each file is copied, copying stops before the next file if `cancel` is set  
+/// if `move_files` each file is moved instead, sources are only removed once every file is in place  
+/// files that already exist at `to_path` are set aside and only removed once every file is in place  
+/// on error or cancellation all files and directories created by this call are removed, moved  
+/// files are returned to their original location and overwritten files are restored
+pub fn copy_with_progress(
+    zip: &[(PathBuf, PathBuf)],
+    move_files: bool,
+    progress: &Sender<CopyProgress>,
+    cancel: &AtomicBool,
+) -> std::io::Result<()> {
+    copy_with_progress_using(zip, move_files, progress, cancel, |from, to| {
+        std::fs::rename(from, to)
+    })
+}
+
+/// `copy_with_progress` where files are moved with `rename`
+#[instrument(level = "trace", skip(zip, progress, cancel, rename), fields(files = zip.len()))]
+pub fn copy_with_progress_using<R>(
+    zip: &[(PathBuf, PathBuf)],
+    move_files: bool,
+    progress: &Sender<CopyProgress>,
+    cancel: &AtomicBool,
+    rename: R,
+) -> std::io::Result<()>
+where
+    R: Fn(&Path, &Path) -> std::io::Result<()>,
+{
+    let mut placed = Vec::with_capacity(zip.len());
+    let mut created_dirs = Vec::new();
+    let mut status = CopyProgress {
+        files_total: zip.len(),
+        ..Default::default()
+    };
+    let result = zip.iter().try_for_each(|(from_path, to_path)| {
+        if cancel.load(Ordering::Acquire) {
+            return new_io_error!(ErrorKind::ConnectionAborted, "Mod install canceled");
+        }
+        mark_self_write();
+        let parent = parent_or_err(to_path)?;
+        if let Some(new_dir) = parent
+            .ancestors()
+            .take_while(|dir| matches!(dir.try_exists(), Ok(false)))
+            .last()
+        {
+            std::fs::create_dir_all(parent)?;
+            created_dirs.push(new_dir.to_path_buf());
+        }
+        let replaced = if matches!(to_path.try_exists(), Ok(true)) {
+            let replaced = replaced_path(to_path);
+            std::fs::rename(to_path, &replaced)?;
+            Some(replaced)
+        } else {
+            None
+        };
+        let (transfer, bytes) = match transfer_file(from_path, to_path, move_files, &rename) {
+            Ok(transferred) => transferred,
+            Err(err) => {
+                // a copy can fail after `to_path` is created, anything at `to_path` is now partial
+                if matches!(to_path.try_exists(), Ok(true)) {
+                    if let Err(err) = std::fs::remove_file(to_path) {
+                        error!("Failed to remove: '{}', {err}", to_path.display());
+                    }
+                }
+                if let Some(ref replaced) = replaced {
+                    restore_replaced(replaced, to_path);
+                }
+                return Err(err);
+            }
+        };
+        placed.push(Placed {
+            from_path,
+            to_path,
+            transfer,
+            replaced,
+        });
+        status.bytes_done += bytes;
+        status.files_done += 1;
+        // receiver is allowed to hang up, progress is only used for display
+        let _ = progress.send(status);
+        Ok(())
+    });
+    mark_self_write();
+    if let Err(err) = result {
+        // moved files must be returned before `created_dirs` are removed
+        placed
+            .iter()
+            .filter(|file| file.transfer == Transfer::Moved)
+            .for_each(|file| {
+                if let Err(err) = std::fs::rename(file.to_path, file.from_path) {
+                    error!("Failed to return: '{}', {err}", file.to_path.display());
+                }
+            });
+        placed
+            .iter()
+            .filter(|file| {
+                file.transfer != Transfer::Moved
+                    && !created_dirs.iter().any(|dir| file.to_path.starts_with(dir))
+            })
+            .for_each(|file| {
+                if let Err(err) = std::fs::remove_file(file.to_path) {
+                    error!("Failed to remove: '{}', {err}", file.to_path.display());
+                }
+            });
+        placed.iter().for_each(|file| {
+            if let Some(ref replaced) = file.replaced {
+                restore_replaced(replaced, file.to_path);
+            }
+        });
+        created_dirs.iter().for_each(|dir| {
+            if let Err(err) = std::fs::remove_dir_all(dir) {
+                error!("Failed to remove: '{}', {err}", dir.display());
+            }
+        });
+        info!(
+            files = placed.len(),
+            dirs = created_dirs.len(),
+            "removed files placed before install stopped"
+        );
+        return Err(err);
+    }
+    placed.iter().for_each(|file| {
+        if file.transfer == Transfer::CopiedForMove {
+            if let Err(err) = std::fs::remove_file(file.from_path) {
+                error!(
+                    "Installed a copy of: '{}', but failed to remove it, {err}",
+                    file.from_path.display()
+                );
+            }
+        }
+        if let Some(ref replaced) = file.replaced {
+            if let Err(err) = std::fs::remove_file(replaced) {
+                error!("Failed to remove: '{}', {err}", replaced.display());
+            }
+        }
+    });
+    trace!(bytes = status.bytes_done, "placed all files");
+    Ok(())
+}
+
+/// moves a file set aside by `copy_with_progress` back to `to_path`
+fn restore_replaced(replaced: &Path, to_path: &Path) {
+    if let Err(err) = std::fs::rename(replaced, to_path) {
+        error!(
+            "Failed to restore: '{}', the previous file is saved at: '{}', {err}",
+            to_path.display(),
+            replaced.display()
+        );
+    }
+}
+
+/// attempts to open each file associated with `reg_mod` with exclusive write access  
+/// returns an error naming the first file that is currently open in another program  
+/// only sharing and lock violations are reported as in use, any other error is returned as is  
+/// files that do not exist are skipped
+#[instrument(level = "trace", skip_all, fields(reg_mod = reg_mod.name))]
+pub fn check_files_unlocked(game_dir: &Path, reg_mod: &RegMod) -> std::io::Result<()> {
+    check_paths_unlocked(&reg_mod.all_files(game_dir)?)
+}
+
+fn check_paths_unlocked(paths: &[PathBuf]) -> std::io::Result<()> {
+    for path in paths {
+        if !matches!(path.try_exists(), Ok(true)) {
+            continue;
+        }
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true);
+        #[cfg(target_os = "windows")]
+        std::os::windows::fs::OpenOptionsExt::share_mode(&mut options, 0);
+        if let Err(err) = options.open(path) {
+            if matches!(err.raw_os_error(), Some(SHARING_VIOLATION | LOCK_VIOLATION)) {
+                return new_io_error!(
+                    ErrorKind::PermissionDenied,
+                    format!(
+                        "File: '{}', is currently in use. Please close Elden Ring or any program that has the file open and try again",
+                        path.display()
+                    )
+                );
+            }
+            return Err(err);
+        }
+    }
+    trace!("no files are locked");
+    Ok(())
+}
+
 /// removes mod files safely by avoiding any call to `remove_dir_all()`  
-/// will remove all associated fiales with a `RegMod` then clean up any empty directories
+/// will remove all associated fiales with a `RegMod` then clean up any empty directories  
+/// no files are removed if any are currently in use, see: `check_files_unlocked()`
 #[instrument(level = "trace", skip_all, fields(reg_mod = reg_mod.name))]
 pub fn remove_mod_files(
     game_dir: &Path,
     loader_dir: &Path,
     reg_mod: &RegMod,
 ) -> std::io::Result<()> {
-    let mut remove_files = reg_mod.files.full_paths(game_dir);
+    check_files_unlocked(game_dir, reg_mod)?;
+    remove_files_and_empty_dirs(game_dir, reg_mod.all_files(game_dir)?)?;
+
+    if reg_mod.order.set {
+        remove_order_entry(reg_mod, loader_dir)?;
+    }
+    Ok(())
+}
 
+/// the files and directories that would be removed with a mod, see `RegMod::removal_preview`
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RemovalPreview {
+    /// full paths of the files that exist
+    pub files: Vec<PathBuf>,
+    /// full paths of the directories left empty once `files` are removed, deepest first
+    pub dirs: Vec<PathBuf>,
+}
+
+impl RemovalPreview {
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.files.len() + self.dirs.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.dirs.is_empty()
+    }
+}
+
+/// computes what `remove_files_and_empty_dirs` removes without modifying anything  
+/// each of `remove_files` that exists is kept, then every directory between them and  
+/// "game_dir\mods" that only contains kept files and other pruned directories  
+/// "mods" and `game_dir` are never included
+pub fn plan_removal(
+    game_dir: &Path,
+    mut remove_files: Vec<PathBuf>,
+) -> std::io::Result<RemovalPreview> {
     for i in (0..remove_files.len()).rev() {
         match remove_files[i].try_exists() {
             Ok(true) => (),
@@ -529,33 +969,175 @@ pub fn remove_mod_files(
     }
 
     let mut parent_dirs = parent_dirs.into_iter().collect::<Vec<_>>();
-    parent_dirs.sort_by_key(|path| path.components().count());
+    parent_dirs.sort_by(|a, b| {
+        b.components()
+            .count()
+            .cmp(&a.components().count())
+            .then_with(|| a.cmp(b))
+    });
 
-    remove_files.iter().try_for_each(std::fs::remove_file)?;
+    let mut dirs = Vec::new();
+    for dir in parent_dirs {
+        let mut left_empty = true;
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if !remove_files.contains(&path) && !dirs.contains(&path) {
+                left_empty = false;
+                break;
+            }
+        }
+        if left_empty {
+            dirs.push(dir.to_path_buf());
+        }
+    }
+    Ok(RemovalPreview {
+        files: remove_files,
+        dirs,
+    })
+}
+
+/// removes each of `remove_files` that exists then removes any directory between them and  
+/// "game_dir\mods" that was left empty, "mods" and `game_dir` themselves are never removed  
+/// what is removed is calculated by `plan_removal` so it always matches `RegMod::removal_preview`
+fn remove_files_and_empty_dirs(game_dir: &Path, remove_files: Vec<PathBuf>) -> std::io::Result<()> {
+    let RemovalPreview { files, dirs } = plan_removal(game_dir, remove_files)?;
 
-    parent_dirs.iter().rev().try_for_each(|dir| {
+    mark_self_write();
+    files.iter().try_for_each(std::fs::remove_file)?;
+
+    dirs.iter().try_for_each(|dir| {
         if items_in_directory(dir, FileType::Any)? == 0 {
             std::fs::remove_dir(dir)
         } else {
             Ok(())
         }
-    })?;
+    })
+}
 
-    if reg_mod.order.set {
-        remove_order_entry(reg_mod, loader_dir)?;
+/// returns the short paths of files in "game_dir\mods" that are not registered to any mod  
+/// dlls in `unknown_keys` are not included, see `Cfg::unregistered_files`  
+/// this is the dry-run of `clean_orphaned_files`, nothing is removed
+#[instrument(level = "trace", skip_all)]
+pub fn orphaned_files(
+    game_dir: &Path,
+    ini_dir: &Path,
+    unknown_keys: &HashSet<String>,
+) -> std::io::Result<Vec<PathBuf>> {
+    let orphaned = Cfg::read(ini_dir)?.unregistered_files(game_dir, unknown_keys)?;
+    trace!(orphaned = orphaned.len());
+    Ok(orphaned)
+}
+
+/// removes every file found by `orphaned_files` then cleans up any directories left empty  
+/// restricted files, dlls in `unknown_keys` and files registered to a mod are never removed  
+/// no files are removed if any are currently in use, returns the short paths of removed files
+#[instrument(level = "trace", skip_all)]
+pub fn clean_orphaned_files(
+    game_dir: &Path,
+    ini_dir: &Path,
+    unknown_keys: &HashSet<String>,
+) -> std::io::Result<Vec<PathBuf>> {
+    let orphaned = orphaned_files(game_dir, ini_dir, unknown_keys)?;
+    if orphaned.is_empty() {
+        return Ok(orphaned);
     }
-    Ok(())
+    let full_paths = orphaned
+        .iter()
+        .map(|file| game_dir.join(file))
+        .collect::<Vec<_>>();
+    check_paths_unlocked(&full_paths)?;
+    remove_files_and_empty_dirs(game_dir, full_paths)?;
+    info!(removed = orphaned.len(), "Removed orphaned files");
+    Ok(orphaned)
+}
+
+/// everything found by `discover_mods` in the top level of the "mods" folder
+#[derive(Debug, Default)]
+pub struct ScanResult {
+    /// one mod for each ".dll" found
+    pub mods: Vec<RegMod>,
+    pub dirs_seen: usize,
+    pub non_dll_files: usize,
+}
+
+impl ScanResult {
+    #[inline]
+    pub fn dlls_found(&self) -> usize {
+        self.mods.len()
+    }
+
+    /// returns `true` if the "mods" folder contained no files or directories
+    #[inline]
+    pub fn folder_is_empty(&self) -> bool {
+        self.mods.is_empty() && self.dirs_seen == 0 && self.non_dll_files == 0
+    }
+
+    /// removes each mod that has a `.dll` already registered in `ini` or a name that is already  
+    /// registered, returns the number of mods removed
+    pub fn retain_unregistered(&mut self, ini: &Cfg) -> usize {
+        let file_key = |file: &str| omit_off_state(file).to_lowercase();
+        let mut registered_names = HashSet::new();
+        let mut registered_files = HashSet::new();
+        ini.iter_file_entries().for_each(|(name, files)| {
+            registered_names.insert(canonical_key(name));
+            registered_files.extend(files.into_iter().map(file_key));
+        });
+        let found = self.mods.len();
+        self.mods.retain(|reg_mod| {
+            !registered_names.contains(&canonical_key(&reg_mod.name))
+                && !reg_mod
+                    .files
+                    .dll
+                    .iter()
+                    .any(|dll| registered_files.contains(&file_key(&dll.to_string_lossy())))
+        });
+        trace!(skipped = found - self.mods.len(), "removed mods that are already registered");
+        found - self.mods.len()
+    }
+}
+
+/// how a scan of the "mods" folder treats mods that are already registered
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanMode {
+    /// every mod found is registered, existing registrations are expected to be cleared first
+    Reset,
+    /// only mods that are not registered yet are added, existing registrations and their notes,  
+    /// categories and load order are left untouched
+    Merge,
+}
+
+/// returns "game_dir\mods", the directory is created if it does not exist yet  
+/// a fresh install of the mod loader does not always include a "mods" folder
+#[instrument(level = "trace", skip_all)]
+pub fn ensure_mods_dir(game_dir: &Path) -> std::io::Result<PathBuf> {
+    let mods_dir = game_dir.join("mods");
+    if mods_dir.try_exists()? {
+        if !mods_dir.is_dir() {
+            return new_io_error!(
+                ErrorKind::InvalidData,
+                format!("'{}' is not a directory", mods_dir.display())
+            );
+        }
+        return Ok(mods_dir);
+    }
+    std::fs::create_dir(&mods_dir)?;
+    info!("Created \"mods\" folder in: '{}'", game_dir.display());
+    Ok(mods_dir)
 }
 
 /// scans the "mods" folder for ".dll"s | if the ".dll" has the same name as a directory the contentents  
-/// of that directory are included in that mod
+/// of that directory are included in that mod  
+/// nothing is written to file, use `commit_scanned_mods` to register the discovered mods
 #[instrument(level = "trace", skip_all)]
-pub fn scan_for_mods(game_dir: &Path, ini_dir: &Path) -> std::io::Result<usize> {
+pub fn discover_mods(game_dir: &Path) -> std::io::Result<ScanResult> {
     let scan_dir = game_dir.join("mods");
     if !matches!(scan_dir.try_exists(), Ok(true)) {
         return new_io_error!(
-            ErrorKind::BrokenPipe,
-            format!("\"mods\" folder does not exist in '{}'", game_dir.display())
+            ErrorKind::NotFound,
+            format!(
+                "No \"mods\" folder found in '{}' yet, add a mod to create it",
+                game_dir.display()
+            )
         );
     };
     let num_files = items_in_directory(&scan_dir, FileType::File)?;
@@ -577,30 +1159,61 @@ pub fn scan_for_mods(game_dir: &Path, ini_dir: &Path) -> std::io::Result<usize>
         if file_data.extension != ".dll" {
             continue;
         };
-        if let Some(dir) = dirs.iter().find(|d| d.file_name().expect("is dir") == file_data.name) {
+        let mut reg_mod = RegMod::new(
+            file_data.name,
+            file_data.enabled,
+            vec![file.strip_prefix(game_dir).expect("file found here").to_path_buf()],
+        );
+        let install_dir = reg_mod.install_dir(game_dir)?;
+        if dirs.contains(&install_dir) {
             let mut data = InstallData::new(file_data.name, vec![file.to_owned()], game_dir)?;
-            data.import_files_from_dir(dir, DisplayItems::None)?;
-            file_sets.push(RegMod::new(
+            data.import_files_from_dir(&install_dir, DisplayItems::None)?;
+            reg_mod = RegMod::new(
                 &data.name,
                 file_data.enabled,
                 data.from_paths
                     .into_iter()
                     .map(|p| p.strip_prefix(game_dir).expect("file found here").to_path_buf())
                     .collect(),
-            ));
-        } else {
-            file_sets.push(RegMod::new(
-                file_data.name,
-                file_data.enabled,
-                vec![file.strip_prefix(game_dir).expect("file found here").to_path_buf()],
-            ));
+            );
         }
+        file_sets.push(reg_mod);
     }
-    for mod_data in file_sets.iter_mut() {
+    let non_dll_files = files.len() - file_sets.len();
+    trace!(mods_found = file_sets.len(), dirs = dirs.len(), non_dll_files, "discovered mods");
+    Ok(ScanResult {
+        mods: file_sets,
+        dirs_seen: dirs.len(),
+        non_dll_files,
+    })
+}
+
+/// registers each mod returned from `discover_mods` to the ini at `ini_dir` and verifies their state  
+/// returns the number of mods registered
+#[instrument(level = "trace", skip_all)]
+pub fn commit_scanned_mods(
+    mods: &mut [RegMod],
+    game_dir: &Path,
+    ini_dir: &Path,
+) -> std::io::Result<usize> {
+    for mod_data in mods.iter_mut() {
         mod_data.write_to_file(ini_dir, false)?;
         mod_data.verify_state(game_dir, ini_dir)?;
     }
-    let mods_found = file_sets.len();
+    let mods_found = mods.len();
     info!(mods_found, "Scanned for mods");
     Ok(mods_found)
 }
+
+/// scans the "mods" folder and registers all mods found, see `discover_mods` for how mods are found  
+/// with `ScanMode::Merge` mods already registered in the ini at `ini_dir` are skipped  
+/// returns the number of mods registered
+#[instrument(level = "trace", skip(game_dir, ini_dir))]
+pub fn scan_for_mods(game_dir: &Path, ini_dir: &Path, mode: ScanMode) -> std::io::Result<usize> {
+    let _timer = OpTimer::start("scan_for_mods");
+    let mut scan = discover_mods(game_dir)?;
+    if mode == ScanMode::Merge {
+        scan.retain_unregistered(&Cfg::read(ini_dir)?);
+    }
+    commit_scanned_mods(&mut scan.mods, game_dir, ini_dir)
+}
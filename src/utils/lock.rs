@@ -0,0 +1,63 @@
+use std::sync::{PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError};
+
+/// `RwLock` for globals that are only accessed from the ui thread  
+/// guards must not be held across an `.await`, so a request that conflicts with a guard that is  
+/// already held can only come from the same thread and would block forever  
+/// debug builds panic naming the lock instead of hanging, release builds log and fall back to blocking
+#[derive(Debug)]
+pub struct UiRwLock<T> {
+    name: &'static str,
+    lock: RwLock<T>,
+}
+
+impl<T> UiRwLock<T> {
+    pub const fn new(name: &'static str, value: T) -> Self {
+        UiRwLock {
+            name,
+            lock: RwLock::new(value),
+        }
+    }
+
+    /// returns `None` if a write guard is currently held
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        match self.lock.try_read() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(err)) => Some(err.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+
+    /// returns `None` if any guard is currently held
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        match self.lock.try_write() {
+            Ok(guard) => Some(guard),
+            Err(TryLockError::Poisoned(err)) => Some(err.into_inner()),
+            Err(TryLockError::WouldBlock) => None,
+        }
+    }
+
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.try_read().unwrap_or_else(|| {
+            self.conflict("read");
+            self.lock.read().unwrap_or_else(PoisonError::into_inner)
+        })
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+        self.try_write().unwrap_or_else(|| {
+            self.conflict("write");
+            self.lock.write().unwrap_or_else(PoisonError::into_inner)
+        })
+    }
+
+    fn conflict(&self, access: &str) {
+        let msg = format!(
+            "{access} of: {}, requested while a conflicting guard is held, this would deadlock",
+            self.name
+        );
+        #[cfg(debug_assertions)]
+        panic!("{msg}");
+        #[cfg(not(debug_assertions))]
+        tracing::error!("{msg}");
+    }
+}
@@ -0,0 +1,178 @@
+use std::{
+    fs::{create_dir_all, remove_dir_all, File},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    file_name_from_str, ini_cfg_path, loader_cfg_path, omit_off_state,
+    utils::ini::{
+        parser::RegMod,
+        writer::{new_cfg, save_path, save_value_ext},
+    },
+    FileData, INI_KEYS, INI_SECTIONS, LOADER_FILES, LOADER_SECTIONS, OFF_STATE, REQUIRED_GAME_FILES,
+};
+
+/// creates a new ini at `path` that only contains the given empty `sections`
+pub fn new_cfg_with_sections(path: &Path, sections: &[Option<&str>]) -> std::io::Result<()> {
+    let parent = path.parent().unwrap();
+
+    create_dir_all(parent)?;
+    let mut new_ini = File::create(path)?;
+
+    for section in sections.iter() {
+        writeln!(new_ini, "[{}]", section.unwrap())?;
+    }
+    Ok(())
+}
+
+/// a mod to be created by `MockGameDirBuilder`
+struct MockMod {
+    name: String,
+    files: Vec<PathBuf>,
+    state: bool,
+    order: Option<usize>,
+}
+
+/// creates a fake game directory with every registered file on disk and a consistent  
+/// `INI_NAME` and `LOADER_FILES[3]`, start with `MockGameDir::builder`
+pub struct MockGameDirBuilder {
+    root: PathBuf,
+    mods: Vec<MockMod>,
+}
+
+impl MockGameDirBuilder {
+    /// adds an enabled mod with no set load order, `files` are short paths eg. "mods\\a.dll"
+    pub fn add_mod(mut self, name: &str, files: &[&str]) -> Self {
+        self.mods.push(MockMod {
+            name: name.to_string(),
+            files: files.iter().map(PathBuf::from).collect(),
+            state: true,
+            order: None,
+        });
+        self
+    }
+
+    /// sets the state of the last added mod, `.dll` files of disabled mods are created in the off state
+    pub fn enabled(mut self, state: bool) -> Self {
+        self.last_mod().state = state;
+        self
+    }
+
+    /// sets the load order of the first `.dll` of the last added mod
+    pub fn order(mut self, order: usize) -> Self {
+        self.last_mod().order = Some(order);
+        self
+    }
+
+    fn last_mod(&mut self) -> &mut MockMod {
+        self.mods
+            .last_mut()
+            .expect("`add_mod` must be called before setting mod properties")
+    }
+
+    /// writes everything to disk, any previous contents of the mock directory are removed first
+    pub fn build(self) -> std::io::Result<MockGameDir> {
+        let game_dir = self.root.join("Game");
+        // construct the guard first so a failed build is still cleaned up
+        let mock = MockGameDir {
+            ini_dir: ini_cfg_path(&self.root),
+            loader_dir: loader_cfg_path(&game_dir),
+            game_dir,
+            root: self.root,
+        };
+        if mock.root.exists() {
+            remove_dir_all(&mock.root)?;
+        }
+        create_dir_all(&mock.game_dir)?;
+        for file in REQUIRED_GAME_FILES.iter().chain(std::iter::once(&LOADER_FILES[1])) {
+            File::create(mock.game_dir.join(file))?;
+        }
+
+        new_cfg_with_sections(&mock.ini_dir, &INI_SECTIONS)?;
+        save_path(&mock.ini_dir, INI_SECTIONS[1], INI_KEYS[2], &mock.game_dir)?;
+        new_cfg(&mock.loader_dir)?;
+
+        for mock_mod in self.mods {
+            let files = mock_mod
+                .files
+                .into_iter()
+                .map(|file| {
+                    let is_dll = FileData::from(&file.to_string_lossy()).extension == ".dll";
+                    if is_dll && !mock_mod.state {
+                        PathBuf::from(format!("{}{OFF_STATE}", file.display()))
+                    } else {
+                        file
+                    }
+                })
+                .collect::<Vec<_>>();
+            for file in files.iter() {
+                let path = mock.game_dir.join(file);
+                create_dir_all(path.parent().expect("short paths have a parent"))?;
+                File::create(path)?;
+            }
+            let reg_mod = RegMod::new(&mock_mod.name, mock_mod.state, files);
+            reg_mod.write_to_file(&mock.ini_dir, false)?;
+            if let Some(order) = mock_mod.order {
+                let Some(dll) = reg_mod.files.dll.first() else {
+                    return Err(std::io::Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("{} has no .dll to set a load order for", reg_mod.name),
+                    ));
+                };
+                let dll_str = dll.to_string_lossy();
+                save_value_ext(
+                    &mock.loader_dir,
+                    LOADER_SECTIONS[1],
+                    omit_off_state(file_name_from_str(&dll_str)),
+                    &order.to_string(),
+                )?;
+            }
+        }
+        Ok(mock)
+    }
+}
+
+/// paths into a fake game directory made by `MockGameDirBuilder::build`  
+/// the directory and everything in it is removed when this is dropped
+pub struct MockGameDir {
+    root: PathBuf,
+    game_dir: PathBuf,
+    ini_dir: PathBuf,
+    loader_dir: PathBuf,
+}
+
+impl MockGameDir {
+    /// `name` is the directory created in "temp\\" it must be unique to each test
+    pub fn builder(name: &str) -> MockGameDirBuilder {
+        MockGameDir::builder_at(Path::new("temp").join(name))
+    }
+
+    /// same as `builder` but everything is created in `root`, `root` is removed when dropped
+    pub fn builder_at(root: PathBuf) -> MockGameDirBuilder {
+        MockGameDirBuilder {
+            root,
+            mods: Vec::new(),
+        }
+    }
+
+    pub fn game_dir(&self) -> &Path {
+        &self.game_dir
+    }
+
+    /// path to the mock `INI_NAME`
+    pub fn ini_dir(&self) -> &Path {
+        &self.ini_dir
+    }
+
+    /// path to the mock `LOADER_FILES[3]`
+    pub fn loader_dir(&self) -> &Path {
+        &self.loader_dir
+    }
+}
+
+impl Drop for MockGameDir {
+    fn drop(&mut self) {
+        let _ = remove_dir_all(&self.root);
+    }
+}
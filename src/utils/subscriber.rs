@@ -1,30 +1,72 @@
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt};
-
-#[cfg(not(debug_assertions))]
-use tracing::{Event, Level, Subscriber};
-
-#[cfg(not(debug_assertions))]
+use std::{
+    fs::{File, OpenOptions},
+    path::Path,
+    sync::OnceLock,
+    time::Instant,
+};
+use tracing::{debug, Event, Level, Subscriber};
+use tracing_appender::non_blocking::WorkerGuard;
 use tracing_subscriber::{
+    filter::{EnvFilter, LevelFilter},
     fmt::{
+        self,
         format::{FormatEvent, FormatFields, PrettyFields, Writer},
         FmtContext,
     },
+    layer::SubscriberExt,
     registry::LookupSpan,
+    reload,
+    util::SubscriberInitExt,
+    Layer, Registry,
 };
 
+use crate::{ini_cfg_path, new_io_error, utils::ini::parser::Setup, Cfg, Config, INI_SECTIONS};
+
+#[cfg(not(debug_assertions))]
+use std::sync::Mutex;
+
+#[cfg(not(debug_assertions))]
+use tracing_subscriber::layer::Layered;
+
+static LOG_FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
 #[cfg(not(debug_assertions))]
+type FilteredRegistry = Layered<reload::Layer<EnvFilter, Registry>, Registry>;
+
+/// a boxed layer that writes logs to file for a subscriber of type `S`
+pub type FileLayer<S> = Box<dyn Layer<S> + Send + Sync>;
+
+#[cfg(not(debug_assertions))]
+static FILE_LAYER_HANDLE: OnceLock<
+    reload::Handle<Option<FileLayer<FilteredRegistry>>, FilteredRegistry>,
+> = OnceLock::new();
+
+#[cfg(not(debug_assertions))]
+static LOG_WRITER_GUARD: Mutex<Option<WorkerGuard>> = Mutex::new(None);
+
+/// flushes and closes the log file when dropped, hold onto this for the lifetime of the app
+#[cfg(not(debug_assertions))]
+pub struct LogGuard;
+
+#[cfg(not(debug_assertions))]
+impl Drop for LogGuard {
+    fn drop(&mut self) {
+        if let Ok(mut guard) = LOG_WRITER_GUARD.lock() {
+            guard.take();
+        }
+    }
+}
+
 struct CustomFormatter<E> {
     inner: E,
 }
 
-#[cfg(not(debug_assertions))]
 impl<E> CustomFormatter<E> {
     fn new(inner: E) -> Self {
         Self { inner }
     }
 }
 
-#[cfg(not(debug_assertions))]
 impl<S, N, E> FormatEvent<S, N> for CustomFormatter<E>
 where
     S: Subscriber + for<'a> LookupSpan<'a>,
@@ -47,53 +89,198 @@ where
     }
 }
 
+fn new_file_layer<S>(log_dir: &Path, append: bool) -> std::io::Result<(FileLayer<S>, WorkerGuard)>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    let log_file = if append {
+        OpenOptions::new().create(true).append(true).open(log_dir)?
+    } else {
+        File::create(log_dir)?
+    };
+    let (non_blocking, guard) = tracing_appender::non_blocking(log_file);
+    let layer = fmt::layer()
+        .event_format(CustomFormatter::new(
+            fmt::format().with_target(false).with_ansi(false).without_time(),
+        ))
+        .fmt_fields(PrettyFields::new())
+        .with_writer(non_blocking)
+        .boxed();
+    Ok((layer, guard))
+}
+
 #[cfg(not(debug_assertions))]
-pub fn init_subscriber() -> std::io::Result<Option<tracing_appender::non_blocking::WorkerGuard>> {
-    use crate::{utils::ini::parser::Setup, Cfg, Config, INI_NAME, INI_SECTIONS, LOG_NAME};
+pub fn init_subscriber() -> std::io::Result<Option<LogGuard>> {
+    use crate::LOG_NAME;
 
-    let env_dir = std::env::current_dir()?;
-    let log_dir = env_dir.join(LOG_NAME);
-    let ini_dir = env_dir.join(INI_NAME);
+    let log_dir = std::env::current_dir()?.join(LOG_NAME);
+    let cfg = saved_settings();
+    let save_logs = cfg
+        .as_ref()
+        .map(|cfg| cfg.get_save_log())
+        .unwrap_or(true);
 
-    let save_logs = if let Ok(ini) = ini_dir.is_setup(&INI_SECTIONS) {
-        let cfg: Cfg = Config::from(ini, &ini_dir);
-        cfg.get_save_log().unwrap_or(true)
+    let (file_layer, writer_guard) = if save_logs {
+        let (layer, guard) = new_file_layer(&log_dir, false)?;
+        (Some(layer), Some(guard))
     } else {
-        true
-    };
-
-    if !save_logs {
         if matches!(log_dir.try_exists(), Ok(true)) {
             std::fs::remove_file(log_dir)?;
         }
-        return Ok(None);
+        (None, None)
+    };
+    let (filter, filter_handle) = reload::Layer::new(new_filter(saved_log_level(cfg.as_ref())));
+    let (file_layer, handle) = reload::Layer::new(file_layer);
+    tracing_subscriber::registry().with(filter).with(file_layer).init();
+    set_log_filter_handle(filter_handle);
+    FILE_LAYER_HANDLE
+        .set(handle)
+        .unwrap_or_else(|_| panic!("subscriber is only initialized once"));
+    *LOG_WRITER_GUARD
+        .lock()
+        .map_err(|_| std::io::Error::other("log writer lock poisoned"))? = writer_guard;
+    Ok(Some(LogGuard))
+}
+
+/// enables or disables writing logs to file without restarting the app  
+/// when re-enabled new logs are appended to the existing log file
+#[cfg(not(debug_assertions))]
+pub fn set_save_log(state: bool) -> std::io::Result<()> {
+    use crate::LOG_NAME;
+
+    let Some(handle) = FILE_LAYER_HANDLE.get() else {
+        return new_io_error!(
+            std::io::ErrorKind::NotFound,
+            "Log subscriber was not initialized"
+        );
+    };
+    let mut writer_guard = LOG_WRITER_GUARD
+        .lock()
+        .map_err(|_| std::io::Error::other("log writer lock poisoned"))?;
+    let log_dir = std::env::current_dir()?.join(LOG_NAME);
+    reload_file_layer(handle, &mut writer_guard, &log_dir, state)
+}
+
+/// turns the file layer behind `handle` on or off, `writer_guard` holds the log writer while on  
+/// when turned on new logs are appended to `log_dir`, turning it off flushes any remaining logs
+pub fn reload_file_layer<S>(
+    handle: &reload::Handle<Option<FileLayer<S>>, S>,
+    writer_guard: &mut Option<WorkerGuard>,
+    log_dir: &Path,
+    state: bool,
+) -> std::io::Result<()>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    if state == writer_guard.is_some() {
+        return Ok(());
     }
-    let log_file = std::fs::File::create(log_dir)?;
-    let (non_blocking, guard) = tracing_appender::non_blocking(log_file);
-    tracing_subscriber::registry()
-        .with(
-            fmt::layer()
-                .event_format(CustomFormatter::new(
-                    fmt::format().with_target(false).with_ansi(false).without_time(),
-                ))
-                .fmt_fields(PrettyFields::new())
-                .with_writer(non_blocking),
-        )
-        .init();
-    Ok(Some(guard))
+    if state {
+        let (layer, guard) = new_file_layer(log_dir, true)?;
+        handle.reload(Some(layer)).map_err(std::io::Error::other)?;
+        *writer_guard = Some(guard);
+    } else {
+        handle.reload(None).map_err(std::io::Error::other)?;
+        // dropping the guard flushes any remaining logs to file
+        writer_guard.take();
+    }
+    Ok(())
+}
+
+/// returns `true` if logs are currently being written to file
+#[cfg(not(debug_assertions))]
+pub fn save_log_enabled() -> bool {
+    LOG_WRITER_GUARD.lock().is_ok_and(|guard| guard.is_some())
 }
 
 #[cfg(debug_assertions)]
 pub fn init_subscriber() -> std::io::Result<Option<()>> {
-    use tracing_subscriber::filter::{EnvFilter, LevelFilter};
+    use std::io::IsTerminal;
 
+    let (filter, filter_handle) =
+        reload::Layer::new(new_filter(saved_log_level(saved_settings().as_ref())));
     tracing_subscriber::registry()
-        .with(fmt::layer().with_target(false).pretty())
+        .with(filter)
         .with(
-            EnvFilter::builder()
-                .with_default_directive(LevelFilter::INFO.into())
-                .from_env_lossy(),
+            fmt::layer()
+                .with_target(false)
+                .with_ansi(std::io::stdout().is_terminal())
+                .pretty(),
         )
         .init();
+    set_log_filter_handle(filter_handle);
     Ok(None)
 }
+
+/// debug builds log to the terminal only, this is a no-op
+#[cfg(debug_assertions)]
+pub fn set_save_log(_state: bool) -> std::io::Result<()> {
+    Ok(())
+}
+
+/// debug builds log to the terminal only
+#[cfg(debug_assertions)]
+pub fn save_log_enabled() -> bool {
+    false
+}
+
+/// logs how long an operation took at `debug` level once dropped, bind to `_timer` at the start of the  
+/// operation so it lives until the operation returns. nothing is timed while `debug` is disabled
+pub struct OpTimer {
+    operation: &'static str,
+    start: Option<Instant>,
+}
+
+impl OpTimer {
+    #[inline]
+    pub fn start(operation: &'static str) -> Self {
+        OpTimer {
+            operation,
+            start: tracing::enabled!(Level::DEBUG).then(Instant::now),
+        }
+    }
+}
+
+impl Drop for OpTimer {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            debug!(operation = self.operation, elapsed = ?start.elapsed(), "operation timed");
+        }
+    }
+}
+
+/// reads the app settings if the ini exists and is set up, nothing is written to file
+fn saved_settings() -> Option<Cfg> {
+    let ini_dir = ini_cfg_path(&std::env::current_dir().ok()?);
+    let ini = ini_dir.is_setup(&INI_SECTIONS).ok()?;
+    Some(Config::from(ini, &ini_dir))
+}
+
+fn saved_log_level(cfg: Option<&Cfg>) -> Level {
+    cfg.and_then(|cfg| cfg.get_log_level().ok())
+        .unwrap_or(crate::DEFAULT_LOG_LEVEL)
+}
+
+/// directives set with the env var `RUST_LOG` take priority over `level`
+fn new_filter(level: Level) -> EnvFilter {
+    EnvFilter::builder()
+        .with_default_directive(LevelFilter::from_level(level).into())
+        .from_env_lossy()
+}
+
+fn set_log_filter_handle(handle: reload::Handle<EnvFilter, Registry>) {
+    LOG_FILTER_HANDLE
+        .set(handle)
+        .unwrap_or_else(|_| panic!("subscriber is only initialized once"));
+}
+
+/// changes the max level of logs that are recorded without restarting the app
+pub fn set_log_level(level: Level) -> std::io::Result<()> {
+    let Some(handle) = LOG_FILTER_HANDLE.get() else {
+        return new_io_error!(
+            std::io::ErrorKind::NotFound,
+            "Log subscriber was not initialized"
+        );
+    };
+    handle.reload(new_filter(level)).map_err(std::io::Error::other)
+}
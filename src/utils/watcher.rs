@@ -0,0 +1,87 @@
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    path::Path,
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+use tracing::{error, trace};
+
+/// events received this soon after a call to `mark_self_write` are assumed to be caused by the app
+pub const SELF_WRITE_WINDOW: Duration = Duration::from_millis(1000);
+
+/// a burst of events is only reported once no new event has been received for this long
+pub const DEBOUNCE_DELAY: Duration = Duration::from_millis(500);
+
+static LAST_SELF_WRITE: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// call before the app changes any file that is watched so the resulting events are ignored
+pub fn mark_self_write() {
+    *LAST_SELF_WRITE.lock().unwrap() = Some(Instant::now());
+}
+
+/// returns `true` if `mark_self_write` was called within the last `SELF_WRITE_WINDOW`
+pub fn is_self_write() -> bool {
+    LAST_SELF_WRITE
+        .lock()
+        .unwrap()
+        .is_some_and(|time| time.elapsed() < SELF_WRITE_WINDOW)
+}
+
+/// starts watching the ini at `ini_dir` and all files within `mods_dir`  
+/// `on_change` is called from a background thread once per burst of changes not caused by the app  
+/// watching stops once the returned `RecommendedWatcher` is dropped
+pub fn watch_files<F>(
+    ini_dir: &Path,
+    mods_dir: &Path,
+    on_change: F,
+) -> notify::Result<RecommendedWatcher>
+where
+    F: Fn() + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel::<notify::Result<Event>>();
+    let mut watcher = notify::recommended_watcher(sender)?;
+    watcher.watch(ini_dir, RecursiveMode::NonRecursive)?;
+    if mods_dir.is_dir() {
+        watcher.watch(mods_dir, RecursiveMode::Recursive)?;
+    }
+
+    // ends once `watcher` is dropped and hangs up the channel
+    std::thread::spawn(move || debounce_events(receiver, on_change));
+    Ok(watcher)
+}
+
+/// calls `on_change` once for each burst of events received that were not caused by the app  
+/// a burst ends once no event has been received for `DEBOUNCE_DELAY`  
+/// blocks until every sender of `receiver` is dropped, a burst that is cut off is not reported
+pub fn debounce_events<F>(receiver: Receiver<notify::Result<Event>>, on_change: F)
+where
+    F: Fn(),
+{
+    while let Ok(event) = receiver.recv() {
+        if !is_external_change(event) {
+            continue;
+        }
+        loop {
+            match receiver.recv_timeout(DEBOUNCE_DELAY) {
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+        trace!("external change detected");
+        on_change();
+    }
+}
+
+fn is_external_change(event: notify::Result<Event>) -> bool {
+    match event {
+        Ok(event) => !matches!(event.kind, EventKind::Access(_)) && !is_self_write(),
+        Err(err) => {
+            error!("File watcher error: {err}");
+            false
+        }
+    }
+}
@@ -0,0 +1,36 @@
+pub use elden_mod_loader_gui::utils::mock::{new_cfg_with_sections, MockGameDir};
+use std::{
+    fs::metadata,
+    future::Future,
+    path::Path,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+};
+
+pub const GAME_DIR: &str = "C:\\Program Files (x86)\\Steam\\steamapps\\common\\ELDEN RING\\Game";
+
+/// drives `future` to completion on the current thread, the app's async fns only need to be polled
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    struct NoopWaker;
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        std::thread::yield_now();
+    }
+}
+
+pub fn file_exists(file_path: &Path) -> bool {
+    if let Ok(metadata) = metadata(file_path) {
+        metadata.is_file()
+    } else {
+        false
+    }
+}
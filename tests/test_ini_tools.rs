@@ -9,16 +9,28 @@ mod tests {
     };
 
     use elden_mod_loader_gui::{
-        get_cfg,
-        utils::ini::{
-            common::*,
-            parser::{IniProperty, RegMod, Setup},
-            writer::*,
+        canonical_key, get_cfg,
+        utils::{
+            error::EmlError,
+            ini::{
+                common::*,
+                mod_loader::{order_line_key, order_placement, OrderValue, RegModsExt},
+                parser::{IniProperty, LoadOrder, RegMod, Setup, SplitFiles},
+                writer::*,
+            },
+            subscriber::{reload_file_layer, FileLayer},
+            watcher::is_self_write,
         },
-        INI_KEYS, INI_SECTIONS, LOADER_FILES, LOADER_SECTIONS, OFF_STATE,
+        ARRAY_KEY, ConfirmLevel, DEFAULT_CONFIRM_LEVEL, DEFAULT_INSTALL_PREVIEW_LIMIT,
+        DEFAULT_LAUNCH_METHOD, DEFAULT_LOG_LEVEL, DEFAULT_WATCH_FILES, INI_KEYS, INI_SECTIONS,
+        LOADER_EXAMPLE, LOADER_FILES, LOADER_KEYS, LOADER_SECTIONS, LOAD_DELAY_RANGE, LaunchMethod,
+        MAX_FAVORITES, NOTES_SECTION, OFF_STATE, RECENT_DIRS_SECTION, REQUIRED_GAME_FILES,
+        SCHEMA_VERSION,
     };
 
-    use crate::common::{new_cfg_with_sections, GAME_DIR};
+    use tracing_subscriber::{layer::SubscriberExt, reload, Registry};
+
+    use crate::common::{new_cfg_with_sections, MockGameDir, GAME_DIR};
 
     #[test]
     fn does_u32_parse() {
@@ -53,8 +65,12 @@ mod tests {
 
     #[test]
     fn does_bool_parse() {
-        let test_bools: [&str; 6] = [" True ", "false", "faLSe", "0 ", "0", "1"];
-        let bool_results: [bool; 6] = [true, false, false, false, false, true];
+        let test_bools: [&str; 10] = [
+            " True ", "false", "faLSe", "0 ", "0", "1", "Yes", " no", "ON", "off",
+        ];
+        let bool_results: [bool; 10] = [
+            true, false, false, false, false, true, true, false, true, false,
+        ];
         let test_file = Path::new("temp\\test_bools.ini");
         let test_section = [Some("bools")];
 
@@ -112,6 +128,93 @@ mod tests {
         remove_file(test_file).unwrap();
     }
 
+    #[test]
+    fn path_prefix_misuse_is_an_error() {
+        let mock = MockGameDir::builder("path_prefix")
+            .add_mod("a_mod", &["mods\\a_mod.dll"])
+            .build()
+            .unwrap();
+        let config = get_cfg(mock.ini_dir()).unwrap();
+        let game_dir = mock.game_dir();
+
+        let prefix_given = IniProperty::<PathBuf>::read(
+            &config,
+            INI_SECTIONS[1],
+            INI_KEYS[2],
+            Some(game_dir),
+            false,
+        );
+        assert_eq!(
+            prefix_given.unwrap_err().kind(),
+            std::io::ErrorKind::InvalidInput
+        );
+        let prefix_missing =
+            IniProperty::<PathBuf>::read(&config, INI_SECTIONS[3], "a_mod", None, false);
+        assert_eq!(
+            prefix_missing.unwrap_err().kind(),
+            std::io::ErrorKind::InvalidInput
+        );
+
+        let full_path =
+            IniProperty::<PathBuf>::read_full_path(&config, INI_SECTIONS[1], INI_KEYS[2], false)
+                .unwrap()
+                .value;
+        assert_eq!(full_path, game_dir);
+        let short_path =
+            IniProperty::<PathBuf>::read_mod_file_path(&config, "a_mod", game_dir, false)
+                .unwrap()
+                .value;
+        assert_eq!(short_path, Path::new("mods\\a_mod.dll"));
+    }
+
+    #[test]
+    fn path_values_are_trimmed_and_unquoted() {
+        let mock = MockGameDir::builder("path_value_normalize")
+            .add_mod("a_mod", &["mods\\a_mod.dll"])
+            .build()
+            .unwrap();
+        let game_dir = mock.game_dir();
+        // values are set in memory so they are kept exactly as a user could have typed them
+        let mut config = get_cfg(mock.ini_dir()).unwrap();
+        config
+            .with_section(INI_SECTIONS[1])
+            .set(INI_KEYS[2], format!(" \"{}\" ", game_dir.display()));
+        config
+            .with_section(INI_SECTIONS[3])
+            .set("a_mod", "' mods\\a_mod.dll ' ");
+
+        let full_path =
+            IniProperty::<PathBuf>::read_full_path(&config, INI_SECTIONS[1], INI_KEYS[2], false)
+                .unwrap()
+                .value;
+        assert_eq!(full_path, game_dir);
+        let short_path =
+            IniProperty::<PathBuf>::read_mod_file_path(&config, "a_mod", game_dir, false)
+                .unwrap()
+                .value;
+        assert_eq!(short_path, Path::new("mods\\a_mod.dll"));
+    }
+
+    #[test]
+    fn all_registered_files_are_sorted_by_path() {
+        let mock = MockGameDir::builder("all_registered_files")
+            .add_mod("a_mod", &["mods\\a_mod.dll", "mods\\c_mod.ini"])
+            .add_mod("b_mod", &["mods\\b_mod.dll"])
+            .build()
+            .unwrap();
+        let game_dir = mock.game_dir();
+        let ini = Cfg::read(mock.ini_dir()).unwrap();
+
+        assert_eq!(
+            ini.all_registered_files(game_dir),
+            [
+                (String::from("a_mod"), game_dir.join("mods\\a_mod.dll")),
+                (String::from("b_mod"), game_dir.join("mods\\b_mod.dll")),
+                (String::from("a_mod"), game_dir.join("mods\\c_mod.ini")),
+            ]
+        );
+    }
+
     #[test]
     fn test_sort_by_order() {
         let test_keys = [
@@ -167,10 +270,13 @@ mod tests {
 
         let (dlls, order_count, _) = ini.dll_set_order_count(loader.mut_section());
         let expected_unknown_key_err = loader.verify_keys(&dlls, order_count).unwrap_err();
-        assert_eq!(
-            expected_unknown_key_err.err.kind(),
-            std::io::ErrorKind::Unsupported
-        );
+        assert!(matches!(
+            expected_unknown_key_err.err,
+            EmlError::UnknownOrderKeys {
+                order_changed: true,
+                ..
+            }
+        ));
 
         let ord_meta_data = loader.update_order_entries(None, &test_unknown_keys);
         assert_eq!(ord_meta_data.max_order, expected_max_ord);
@@ -187,10 +293,265 @@ mod tests {
             assert_eq!(v, sorted_order[i].1)
         });
 
+        remove_file(backup_path(&test_file)).unwrap();
         remove_file(test_file).unwrap();
         remove_file(required_file).unwrap();
     }
 
+    #[test]
+    fn order_placement_rejects_inconsistent_values() {
+        let test_file = Path::new("temp\\order_placement.ini");
+        let no_unknown_keys = HashSet::new();
+
+        new_cfg_with_sections(test_file, &LOADER_SECTIONS).unwrap();
+        for (key, value) in [("a.dll", "2"), ("b.dll", "3"), ("c.dll", "3"), ("d.dll", "4")] {
+            save_value_ext(test_file, LOADER_SECTIONS[1], key, value).unwrap();
+        }
+        let mut loader = ModLoaderCfg::read(test_file).unwrap();
+
+        let err = order_placement(&loader.parse_into_map(), &no_unknown_keys, 4).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        loader.update_order_entries(None, &no_unknown_keys);
+        let order_map = loader.parse_into_map();
+        assert_eq!(order_map.values().min(), Some(&1));
+
+        let err = order_placement(&order_map, &no_unknown_keys, 3).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+
+        let placement = order_placement(&order_map, &no_unknown_keys, 4).unwrap().unwrap();
+        assert_eq!(placement.low_order, 1);
+        assert_eq!(placement.rows, [vec![0], vec![1, 2], vec![3]]);
+
+        let only_unknown = order_map.keys().cloned().collect::<HashSet<_>>();
+        assert!(order_placement(&order_map, &only_unknown, 4).unwrap().is_none());
+
+        remove_file(test_file).unwrap();
+    }
+
+    #[test]
+    fn single_order_change_is_written_in_place() {
+        let test_file = Path::new("temp\\order_in_place.ini");
+        let header = "[modloader]\r\nload_delay = 5000\r\nshow_terminal = 0\r\n[loadorder]\r\n";
+        std::fs::write(
+            test_file,
+            format!("{header}; kept by other tools\r\na.dll = 1\r\nb.dll = 2\r\n"),
+        )
+        .unwrap();
+        let no_unknown_keys = HashSet::new();
+
+        let mut loader = ModLoaderCfg::read(test_file).unwrap();
+        loader.mut_section().insert("c.dll", "3");
+        let ord_meta_data = loader.write_order_change("c.dll", true, &no_unknown_keys).unwrap();
+        assert_eq!(ord_meta_data.max_order, (3, false));
+        assert_eq!(
+            std::fs::read_to_string(test_file).unwrap(),
+            format!("{header}; kept by other tools\r\na.dll = 1\r\nb.dll = 2\r\nc.dll = 3\r\n")
+        );
+
+        loader.mut_section().remove("c.dll");
+        loader.write_order_change("c.dll", false, &no_unknown_keys).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(test_file).unwrap(),
+            format!("{header}; kept by other tools\r\na.dll = 1\r\nb.dll = 2\r\n")
+        );
+
+        // removing the first entry leaves a gap so all entries are renormalized
+        loader.mut_section().remove("a.dll");
+        loader.write_order_change("a.dll", false, &no_unknown_keys).unwrap();
+        let saved = ModLoaderCfg::read(test_file).unwrap();
+        assert_eq!(saved.section().len(), 1);
+        assert_eq!(saved.section().get("b.dll"), Some("1"));
+
+        remove_file(test_file).unwrap();
+        remove_file(backup_path(test_file)).unwrap();
+    }
+
+    #[test]
+    fn unregistered_order_keys_are_listed() {
+        let mock = MockGameDir::builder("unregistered_order_keys")
+            .add_mod("a_mod", &["mods\\a_mod.dll"])
+            .order(1)
+            .add_mod("b_mod", &["mods\\b_mod.dll"])
+            .enabled(false)
+            .order(2)
+            .add_mod("c_mod", &["mods\\c_mod.dll", "mods\\c_mod\\config.ini"])
+            .build()
+            .unwrap();
+        for (key, value) in [
+            ("manual.dll", "3"),
+            (LOADER_EXAMPLE, "0"),
+            ("broken.dll", "not a number"),
+        ] {
+            save_value_ext(mock.loader_dir(), LOADER_SECTIONS[1], key, value).unwrap();
+        }
+
+        let mut loader = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        let ini = Cfg::read(mock.ini_dir()).unwrap();
+        let (dlls, _, _) = ini.dll_set_order_count(loader.mut_section());
+
+        assert_eq!(
+            loader.unregistered_keys(&dlls),
+            vec![
+                (String::from("manual.dll"), 3),
+                (String::from("broken.dll"), usize::MAX),
+            ]
+        );
+    }
+
+    #[test]
+    fn load_order_is_imported_from_lines() {
+        let mock = MockGameDir::builder("import_load_order")
+            .add_mod("a_mod", &["mods\\a_mod.dll"])
+            .order(1)
+            .add_mod("b_mod", &["mods\\b_mod.dll"])
+            .enabled(false)
+            .order(2)
+            .add_mod("c_mod", &["mods\\c_mod.dll"])
+            .order(3)
+            .add_mod("d_mod", &["mods\\d_mod.dll"])
+            .build()
+            .unwrap();
+        save_value_ext(mock.loader_dir(), LOADER_SECTIONS[1], "manual.dll", "4").unwrap();
+        let lines = [
+            "# exported by another mod manager",
+            "*c_mod.dll",
+            "unknown_mod.dll",
+            "",
+            "mods\\A_MOD.dll",
+            "c_mod.dll",
+        ];
+
+        let mut loader = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        let ini = Cfg::read(mock.ini_dir()).unwrap();
+        let (dlls, _, _) = ini.dll_set_order_count(loader.mut_section());
+
+        assert_eq!(order_line_key("unknown_mod.dll", &dlls), None);
+        assert_eq!(order_line_key("b_mod.dll.disabled", &dlls), Some("b_mod.dll"));
+
+        let ord_meta_data = loader.import_order_from_lines(&lines, &dlls);
+        assert_eq!(ord_meta_data.max_order, (3, false));
+        assert!(ord_meta_data.missing_vals.is_none());
+        assert_eq!(
+            loader.iter().collect::<Vec<_>>(),
+            [
+                ("c_mod.dll", "1"),
+                ("a_mod.dll", "2"),
+                ("b_mod.dll", "3"),
+                ("manual.dll", "4"),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_load_order_section_reads_as_empty() {
+        let mut loader = ModLoaderCfg::default(Path::new("temp\\missing_loadorder.ini"));
+        assert!(loader.section().is_empty());
+        assert_eq!(loader.iter().count(), 0);
+        assert!(loader.mods_is_empty());
+        assert_eq!(loader.mods_registered(), 0);
+        assert!(ModLoaderCfg::empty().parse_into_map().is_empty());
+
+        loader.mut_section().insert("a_mod.dll", "1");
+        assert_eq!(loader.iter().collect::<Vec<_>>(), [("a_mod.dll", "1")]);
+        assert_eq!(loader.mods_registered(), 1);
+    }
+
+    #[test]
+    fn gapped_load_order_is_normalized() {
+        let mock = MockGameDir::builder("normalize_load_order")
+            .add_mod("a_mod", &["mods\\a_mod.dll"])
+            .order(1)
+            .add_mod("b_mod", &["mods\\b_mod.dll"])
+            .order(4)
+            .add_mod("c_mod", &["mods\\c_mod.dll"])
+            .order(7)
+            .build()
+            .unwrap();
+        save_value_ext(mock.loader_dir(), LOADER_SECTIONS[1], "manual.dll", "5").unwrap();
+        let unknown_keys = HashSet::from([String::from("manual.dll")]);
+
+        let mut loader = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        let (ord_meta_data, moved) = loader.normalize_order(&unknown_keys);
+        assert_eq!(moved, 3);
+        assert_eq!(ord_meta_data.max_order, (4, false));
+        loader.write_to_file().unwrap();
+
+        // unknown key keeps its place between "b_mod" and "c_mod"
+        let mut loader = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        let mut entries = loader.iter().collect::<Vec<_>>();
+        entries.sort_by_key(|(_, v)| v.parse::<usize>().unwrap());
+        assert_eq!(
+            entries,
+            [("a_mod.dll", "1"), ("b_mod.dll", "2"), ("manual.dll", "3"), ("c_mod.dll", "4")]
+        );
+
+        let (_, moved) = loader.normalize_order(&unknown_keys);
+        assert_eq!(moved, 0);
+    }
+
+    #[test]
+    fn suspended_load_order_round_trips() {
+        let mock = MockGameDir::builder("suspend_load_order")
+            .add_mod("a_mod", &["mods\\a_mod.dll"])
+            .order(1)
+            .add_mod("b_mod", &["mods\\b_mod.dll"])
+            .order(3)
+            .build()
+            .unwrap();
+        let no_unknown_keys = HashSet::new();
+
+        let mut loader = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        assert!(!loader.order_suspended());
+        assert_eq!(loader.resume_order().unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+        loader.suspend_order().unwrap();
+        assert_eq!(loader.suspend_order().unwrap_err().kind(), std::io::ErrorKind::InvalidInput);
+
+        // stashed values are left out of order, only section "loadorder" is renormalized
+        let mut saved = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        assert!(saved.order_suspended());
+        assert!(saved.mods_is_empty());
+        assert!(saved.parse_section(&no_unknown_keys).unwrap().is_empty());
+        let stashed = saved.suspended_order_map();
+        assert_eq!(stashed.get("a_mod.dll"), Some(&1));
+        assert_eq!(stashed.get("b_mod.dll"), Some(&3));
+
+        saved.resume_order().unwrap();
+        let resumed = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        assert!(!resumed.order_suspended());
+        assert_eq!(
+            resumed.iter().collect::<Vec<_>>(),
+            [("a_mod.dll", "1"), ("b_mod.dll", "3")]
+        );
+    }
+
+    #[test]
+    fn equal_load_orders_sort_by_name() {
+        let mock = MockGameDir::builder("equal_load_orders")
+            .add_mod("charlie", &["mods\\charlie.dll"])
+            .order(1)
+            .add_mod("zulu", &["mods\\zulu.dll"])
+            .add_mod("alpha", &["mods\\alpha.dll"])
+            .order(1)
+            .add_mod("delta", &["mods\\delta.dll"])
+            .order(0)
+            .add_mod("echo", &["mods\\echo.dll"])
+            .add_mod("bravo", &["mods\\bravo.dll"])
+            .order(1)
+            .build()
+            .unwrap();
+        let order_map = ModLoaderCfg::read(mock.loader_dir())
+            .unwrap()
+            .parse_into_map();
+        let expected = ["delta", "alpha", "bravo", "charlie", "echo", "zulu"];
+
+        for _ in 0..3 {
+            let cfg = Cfg::read(mock.ini_dir()).unwrap();
+            let mods = cfg.collect_mods(mock.game_dir(), Some(&order_map), false).mods;
+            assert_eq!(mods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(), expected);
+        }
+    }
+
     #[test]
     #[allow(unused_variables)]
     fn type_check() {
@@ -239,8 +600,16 @@ mod tests {
 
     #[test]
     fn read_write_delete_from_ini() {
-        let test_file = Path::new("temp\\test_collect_mod_data.ini");
-        let game_path = Path::new(GAME_DIR);
+        let mock_game = MockGameDir::builder("read_write_delete")
+            .add_mod(
+                "Unlock The Fps  ",
+                &["mods\\UnlockTheFps.dll", "mods\\UnlockTheFps\\config.ini"],
+            )
+            .add_mod(" Skip The Intro", &["mods\\SkipTheIntro.dll"])
+            .build()
+            .unwrap();
+        let test_file = mock_game.ini_dir();
+        let game_path = mock_game.game_dir();
 
         let mod_1_files = vec![
             PathBuf::from("mods\\UnlockTheFps.dll"),
@@ -248,13 +617,11 @@ mod tests {
         ];
         let mod_2_file = PathBuf::from("mods\\SkipTheIntro.dll");
 
-        // test_mod_2 state is set incorrectly
         let test_mod_1 = RegMod::new("Unlock The Fps  ", true, mod_1_files);
         let mut test_mod_2 = RegMod::new(" Skip The Intro", false, vec![mod_2_file]);
 
         {
-            // Test if new_cfg will write all Sections to the file with .is_setup()
-            new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+            // Test if the mock ini was written with all Sections
             assert!(test_file.is_setup(&INI_SECTIONS).is_ok());
 
             let invalid_format_1 = vec![
@@ -263,31 +630,7 @@ mod tests {
             ];
             let invalid_format_2 = PathBuf::from("mods\\SkipTheIntro.dll");
 
-            // We must save a working game_dir in the ini before we can parse entries in Section("mod-files")
-            // -----------------------parser is set up to only parse valid entries---------------------------
-            // ----use case for entries in Section("mod-files") is to keep track of files within game_dir----
-
-            save_paths(
-                test_file,
-                INI_SECTIONS[3],
-                &test_mod_1.name,
-                &test_mod_1.files.file_refs(),
-            )
-            .unwrap();
-            save_bool(
-                test_file,
-                INI_SECTIONS[2],
-                &test_mod_1.name,
-                test_mod_1.state,
-            )
-            .unwrap();
-            save_path(
-                test_file,
-                INI_SECTIONS[3],
-                &test_mod_2.name,
-                &test_mod_2.files.dll[0],
-            )
-            .unwrap();
+            // test_mod_2 state is set incorrectly, its files on disk are enabled
             save_bool(
                 test_file,
                 INI_SECTIONS[2],
@@ -310,8 +653,6 @@ mod tests {
             )
             .unwrap();
             save_bool(test_file, INI_SECTIONS[2], "no_matching_path", true).unwrap();
-
-            save_path(test_file, INI_SECTIONS[1], INI_KEYS[2], game_path).unwrap();
         }
 
         // -------------------------------------sync_keys() runs from inside Cfg.collect_mods()------------------------------------------------
@@ -367,7 +708,1297 @@ mod tests {
         // Tests if bool was parsed correctly
         assert_eq!(test_mod_1.state, reg_mods[mod_1].state);
         assert!(test_mod_2.state);
+    }
+
+    #[test]
+    fn dll_less_mod_is_always_active() {
+        let test_file = Path::new("temp\\test_dll_less_mod.ini");
+        let config_file = PathBuf::from("temp\\dll_less_config.ini");
+
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+        File::create(&config_file).unwrap();
+
+        // saved state is incorrect, mods without a .dll can not be disabled
+        let mut test_mod = RegMod::new("Config Only", false, vec![config_file.clone()]);
+        test_mod.write_to_file(test_file, false).unwrap();
+        assert!(test_mod.is_always_active());
+
+        test_mod.verify_state(Path::new(""), test_file).unwrap();
+        assert!(test_mod.state);
+        assert!(
+            IniProperty::<bool>::read(
+                &get_cfg(test_file).unwrap(),
+                INI_SECTIONS[2],
+                &test_mod.name
+            )
+            .unwrap()
+            .value
+        );
+
+        remove_file(test_file).unwrap();
+        remove_file(config_file).unwrap();
+    }
+
+    #[test]
+    fn rename_registered_mod() {
+        let test_file = Path::new("temp\\test_rename_mod.ini");
+        let test_files = vec![
+            PathBuf::from("mods\\RenameMe.dll"),
+            PathBuf::from("mods\\RenameMe\\config.ini"),
+        ];
+        let test_mod = RegMod::new("Renme Me", true, test_files.clone());
+        let other_mod = RegMod::new("Taken", false, vec![PathBuf::from("mods\\Taken.dll")]);
+
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+        test_mod.write_to_file(test_file, false).unwrap();
+        other_mod.write_to_file(test_file, false).unwrap();
+
+        let mut cfg = Cfg::read(test_file).unwrap();
+        let duplicate_err = cfg
+            .rename_mod(&test_mod.name, "taken", Path::new(""))
+            .unwrap_err();
+        assert_eq!(duplicate_err.kind(), std::io::ErrorKind::AlreadyExists);
+
+        cfg.rename_mod(&test_mod.name, "Rename Me", Path::new(""))
+            .unwrap();
+
+        let config = get_cfg(test_file).unwrap();
+        assert!(config.get_from(INI_SECTIONS[2], &test_mod.name).is_none());
+        assert!(config.get_from(INI_SECTIONS[3], &test_mod.name).is_none());
+        assert!(
+            IniProperty::<bool>::read(&config, INI_SECTIONS[2], "Rename_Me")
+                .unwrap()
+                .value
+        );
+        assert_eq!(
+            IniProperty::<Vec<PathBuf>>::read(&config, INI_SECTIONS[3], "Rename_Me", "", true)
+                .unwrap()
+                .value,
+            test_files
+        );
+
+        // order of registered mods is preserved
+        let keys = config
+            .section(INI_SECTIONS[2])
+            .unwrap()
+            .iter()
+            .map(|(k, _)| k)
+            .collect::<Vec<_>>();
+        assert_eq!(keys, ["Rename_Me", "Taken"]);
+
+        remove_file(test_file).unwrap();
+        remove_file(backup_path(test_file)).unwrap();
+    }
+
+    #[test]
+    fn max_order_from_parts() {
+        let new_mod = |name: &str, order: LoadOrder| {
+            let files = SplitFiles::from(vec![PathBuf::from(format!("mods\\{name}.dll"))]);
+            RegMod::from_parts(name, true, files, order)
+        };
+
+        let unique_high = [
+            new_mod("a_mod", LoadOrder::new(true, 0, 1)),
+            new_mod("b_mod", LoadOrder::new(true, 0, 3)),
+            new_mod("c_mod", LoadOrder::default()),
+            new_mod("d_mod", LoadOrder::new(true, 0, 2)),
+        ];
+        assert_eq!(unique_high.max_order(), (3, false));
+
+        let duplicate_high = [
+            new_mod("a_mod", LoadOrder::new(true, 0, 1)),
+            new_mod("b_mod", LoadOrder::new(true, 0, 2)),
+            new_mod("c_mod", LoadOrder::new(true, 0, 2)),
+        ];
+        assert_eq!(duplicate_high.max_order(), (3, true));
+
+        let one_set = [
+            new_mod("a_mod", LoadOrder::new(true, 0, 5)),
+            new_mod("b_mod", LoadOrder::default()),
+        ];
+        assert_eq!(one_set.max_order(), (1, false));
+    }
+
+    #[test]
+    fn invalid_order_value_is_reset() {
+        let test_file = Path::new("temp\\test_invalid_order.ini");
+        let test_entries = [("a_mod.dll", "1"), ("b_mod.dll", "abc"), ("c_mod.dll", "2")];
+
+        new_cfg_with_sections(test_file, &LOADER_SECTIONS).unwrap();
+        for (k, v) in test_entries {
+            save_value_ext(test_file, LOADER_SECTIONS[1], k, v).unwrap();
+        }
+
+        let mut loader = ModLoaderCfg::read(test_file).unwrap();
+        assert_eq!(
+            OrderValue::parse(loader.section().get("b_mod.dll").unwrap(), false),
+            OrderValue::Invalid
+        );
+
+        let order_map = loader.parse_section(&HashSet::new()).unwrap();
+        assert_eq!(order_map.len(), test_entries.len());
+        assert_eq!(order_map.get("b_mod.dll"), Some(&3));
+
+        let mut values = order_map.values().copied().collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, [1, 2, 3]);
+
+        // invalid value was written back to file as a valid entry
+        let loader = ModLoaderCfg::read(test_file).unwrap();
+        assert_eq!(loader.section().get("b_mod.dll"), Some("3"));
+
+        remove_file(test_file).unwrap();
+        remove_file(backup_path(test_file)).unwrap();
+    }
+
+    #[test]
+    fn deduplicate_shared_files() {
+        let test_file = Path::new("temp\\test_deduplicate_files.ini");
+        let shared_file = PathBuf::from("mods\\shared.dll");
+        let first_owner = RegMod::new("First Owner", true, vec![shared_file.clone()]);
+        let second_owner = RegMod::new(
+            "Second Owner",
+            true,
+            vec![shared_file.clone(), PathBuf::from("mods\\second.dll")],
+        );
+
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+        first_owner.write_to_file(test_file, false).unwrap();
+        second_owner.write_to_file(test_file, false).unwrap();
+
+        let mut cfg = Cfg::read(test_file).unwrap();
+        let messages = cfg.validate_entries().unwrap_err();
+        assert_eq!(messages.len(), 1);
+        cfg.write_to_file().unwrap();
+
+        let config = get_cfg(test_file).unwrap();
+        assert_eq!(
+            config.get_from(INI_SECTIONS[3], &first_owner.name),
+            Some("mods\\shared.dll")
+        );
+        // array with one file left is collapsed to a single entry
+        assert_eq!(
+            config.get_from(INI_SECTIONS[3], &second_owner.name),
+            Some("mods\\second.dll")
+        );
+
+        let mut cfg = Cfg::read(test_file).unwrap();
+        assert!(cfg.deduplicate_files().is_empty());
+
+        remove_file(test_file).unwrap();
+        remove_file(backup_path(test_file)).unwrap();
+    }
+
+    #[test]
+    fn deduplicate_ignores_off_state_and_removes_empty_mods() {
+        let test_file = Path::new("temp\\test_deduplicate_off_state.ini");
+        let owner = RegMod::new("Owner", true, vec![PathBuf::from("mods\\shared.dll")]);
+        let disabled_copy = RegMod::new(
+            "Disabled Copy",
+            false,
+            vec![PathBuf::from("mods\\Shared.dll.disabled")],
+        );
+
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+        owner.write_to_file(test_file, false).unwrap();
+        disabled_copy.write_to_file(test_file, false).unwrap();
+
+        let mut cfg = Cfg::read(test_file).unwrap();
+        let conflicts = cfg.deduplicate_files();
+        assert_eq!(
+            conflicts,
+            vec![(
+                owner.name.clone(),
+                disabled_copy.name.clone(),
+                PathBuf::from("mods\\Shared.dll.disabled")
+            )]
+        );
+        cfg.write_to_file().unwrap();
+
+        let config = get_cfg(test_file).unwrap();
+        assert_eq!(
+            config.get_from(INI_SECTIONS[3], &owner.name),
+            Some("mods\\shared.dll")
+        );
+        assert!(config.get_from(INI_SECTIONS[3], &disabled_copy.name).is_none());
+        assert!(config.get_from(INI_SECTIONS[2], &disabled_copy.name).is_none());
+        assert!(config.get_from(INI_SECTIONS[2], &owner.name).is_some());
+
+        remove_file(test_file).unwrap();
+        remove_file(backup_path(test_file)).unwrap();
+    }
+
+    #[test]
+    fn diagnose_reports_without_writing() {
+        let game_dir = Path::new("temp\\diagnose_game");
+        let test_file = Path::new("temp\\test_diagnose.ini");
+        let mods_dir = game_dir.join("mods");
+        std::fs::create_dir_all(&mods_dir).unwrap();
+        for file in ["wrong_state.dll.disabled", "unregistered.dll"] {
+            File::create(mods_dir.join(file)).unwrap();
+        }
+        std::fs::write(
+            game_dir.join(LOADER_FILES[3]),
+            "[modloader]\r\n[loadorder]\r\nwrong_state.dll=0\r\nremoved.dll=1\r\nExample.dll=2\r\n",
+        )
+        .unwrap();
+        let wrong_state = RegMod::new(
+            "Wrong State",
+            true,
+            vec![PathBuf::from("mods\\wrong_state.dll")],
+        );
+        let missing = RegMod::new("Missing", true, vec![PathBuf::from("mods\\missing.dll")]);
+
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+        wrong_state.write_to_file(test_file, false).unwrap();
+        missing.write_to_file(test_file, false).unwrap();
+        let before = std::fs::read_to_string(test_file).unwrap();
+
+        let cfg = Cfg::read(test_file).unwrap();
+        let report = cfg.diagnose(game_dir, &HashSet::new());
+        assert!(!report.is_healthy());
+        assert_eq!(report.state_mismatch, vec![wrong_state.name.clone()]);
+        assert_eq!(report.missing_files.len(), 1);
+        assert_eq!(report.missing_files[0].0, missing.name);
+        assert_eq!(
+            report.unregistered_files,
+            vec![PathBuf::from("mods\\unregistered.dll")]
+        );
+        assert_eq!(report.orphaned_orders, vec![String::from("removed.dll")]);
+
+        assert_eq!(std::fs::read_to_string(test_file).unwrap(), before);
+        assert!(mods_dir.join("wrong_state.dll.disabled").exists());
+
+        remove_file(test_file).unwrap();
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[test]
+    fn verify_all_continues_past_failures() {
+        let game_dir = Path::new("temp\\verify_all_game");
+        let test_file = Path::new("temp\\test_verify_all.ini");
+        let mods_dir = game_dir.join("mods");
+        std::fs::create_dir_all(&mods_dir).unwrap();
+        for file in ["correct.dll", "wrong_state.dll"] {
+            File::create(mods_dir.join(file)).unwrap();
+        }
+        let test_mods = [
+            RegMod::new("Correct", true, vec![PathBuf::from("mods\\correct.dll")]),
+            RegMod::new("Wrong State", false, vec![PathBuf::from("mods\\wrong_state.dll")]),
+            RegMod::new("Missing", true, vec![PathBuf::from("mods\\missing.dll")]),
+        ];
+
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+        test_mods
+            .iter()
+            .for_each(|reg_mod| reg_mod.write_to_file(test_file, false).unwrap());
+
+        let mut cfg = Cfg::read(test_file).unwrap();
+        let summary = cfg.verify_all(game_dir).unwrap();
+        assert_eq!(summary.unchanged, vec![test_mods[0].name.clone()]);
+        assert_eq!(summary.fixed, vec![test_mods[1].name.clone()]);
+        assert_eq!(summary.failed.len(), 1);
+        assert_eq!(summary.failed[0].0, test_mods[2].name);
+        assert!(mods_dir.join(format!("wrong_state.dll{OFF_STATE}")).exists());
+
+        remove_file(test_file).unwrap();
+        std::fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[test]
+    fn eac_warning_shown_by_default() {
+        let test_file = Path::new("temp\\test_eac_warning.ini");
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+
+        let cfg = Cfg::read(test_file).unwrap();
+        assert!(!cfg.get_suppress_eac_warning());
+
+        save_bool(test_file, INI_SECTIONS[0], INI_KEYS[4], true).unwrap();
+        let cfg = Cfg::read(test_file).unwrap();
+        assert!(cfg.get_suppress_eac_warning());
+
+        remove_file(test_file).unwrap();
+    }
+
+    #[test]
+    fn invalid_launch_method_resets_to_default() {
+        let test_file = Path::new("temp\\test_launch_method.ini");
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+
+        let cfg = Cfg::read(test_file).unwrap();
+        assert_eq!(cfg.get_launch_method().unwrap(), DEFAULT_LAUNCH_METHOD);
+
+        save_value(test_file, INI_SECTIONS[0], INI_KEYS[12], " Direct ").unwrap();
+        let cfg = Cfg::read(test_file).unwrap();
+        assert_eq!(cfg.get_launch_method().unwrap(), LaunchMethod::Direct);
+
+        save_value(test_file, INI_SECTIONS[0], INI_KEYS[12], "epic").unwrap();
+        let cfg = Cfg::read(test_file).unwrap();
+        assert_eq!(
+            cfg.get_launch_method().unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+        let cfg = Cfg::read(test_file).unwrap();
+        assert_eq!(
+            cfg.data().get_from(INI_SECTIONS[0], INI_KEYS[12]),
+            Some(DEFAULT_LAUNCH_METHOD.as_str())
+        );
+
+        remove_file(test_file).unwrap();
+    }
+
+    #[test]
+    fn confirm_level_gates_only_non_destructive_prompts() {
+        let test_file = Path::new("temp\\test_confirm_level.ini");
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+
+        let cfg = Cfg::read(test_file).unwrap();
+        let level = cfg.get_confirm_level().unwrap();
+        assert_eq!(level, DEFAULT_CONFIRM_LEVEL);
+        assert!(level.confirm_routine() && level.confirm_summary());
+
+        save_value(test_file, INI_SECTIONS[0], INI_KEYS[13], "Destructive_Only").unwrap();
+        let cfg = Cfg::read(test_file).unwrap();
+        let level = cfg.get_confirm_level().unwrap();
+        assert_eq!(level, ConfirmLevel::DestructiveOnly);
+        assert!(!level.confirm_routine() && level.confirm_summary());
+
+        let level = "minimal".parse::<ConfirmLevel>().unwrap();
+        assert!(!level.confirm_routine() && !level.confirm_summary());
+
+        save_value(test_file, INI_SECTIONS[0], INI_KEYS[13], "none").unwrap();
+        let cfg = Cfg::read(test_file).unwrap();
+        assert_eq!(
+            cfg.get_confirm_level().unwrap_err().kind(),
+            std::io::ErrorKind::InvalidData
+        );
+        let cfg = Cfg::read(test_file).unwrap();
+        assert_eq!(
+            cfg.data().get_from(INI_SECTIONS[0], INI_KEYS[13]),
+            Some(DEFAULT_CONFIRM_LEVEL.as_str())
+        );
+
+        remove_file(test_file).unwrap();
+    }
+
+    #[test]
+    fn watch_files_off_by_default() {
+        let test_file = Path::new("temp\\test_watch_files.ini");
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+
+        let cfg = Cfg::read(test_file).unwrap();
+        assert!(!cfg.get_watch_files());
+
+        save_bool(test_file, INI_SECTIONS[0], INI_KEYS[6], true).unwrap();
+        assert!(is_self_write());
+        let cfg = Cfg::read(test_file).unwrap();
+        assert!(cfg.get_watch_files());
+
+        remove_file(test_file).unwrap();
+    }
+
+    #[test]
+    fn invalid_log_level_resets_to_default() {
+        let test_file = Path::new("temp\\test_log_level.ini");
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+
+        let cfg = Cfg::read(test_file).unwrap();
+        assert_eq!(cfg.get_log_level().unwrap(), DEFAULT_LOG_LEVEL);
+
+        save_value(test_file, INI_SECTIONS[0], INI_KEYS[7], " WARN ").unwrap();
+        let cfg = Cfg::read(test_file).unwrap();
+        assert_eq!(cfg.get_log_level().unwrap(), tracing::Level::WARN);
+
+        // `Level` also parses numbers, only the names in `LOG_LEVELS` are accepted
+        for invalid in ["loud", "3"] {
+            save_value(test_file, INI_SECTIONS[0], INI_KEYS[7], invalid).unwrap();
+            let cfg = Cfg::read(test_file).unwrap();
+            assert_eq!(
+                cfg.get_log_level().unwrap_err().kind(),
+                std::io::ErrorKind::InvalidData
+            );
+            let cfg = Cfg::read(test_file).unwrap();
+            assert_eq!(cfg.data().get_from(INI_SECTIONS[0], INI_KEYS[7]), Some("info"));
+        }
+
+        remove_file(test_file).unwrap();
+    }
+
+    #[test]
+    fn mod_note_round_trips() {
+        let test_file = Path::new("temp\\test_mod_notes.ini");
+        let notes = [
+            ("mod_a", "line one\nline \"two\" = ; # \\ 'x'"),
+            ("mod_b", "\"quoted\" \\n is not a newline\r\n"),
+            ("mod_c", "[not-a-section] key=value"),
+        ];
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+
+        let mut cfg = Cfg::read(test_file).unwrap();
+        notes.iter().for_each(|(key, note)| cfg.set_mod_note(key, note));
+        cfg.write_to_file().unwrap();
+
+        let mut cfg = Cfg::read(test_file).unwrap();
+        for (key, note) in notes {
+            assert_eq!(cfg.get_mod_note(key).as_deref(), Some(note.trim()));
+        }
+        assert_eq!(cfg.data().section(NOTES_SECTION).unwrap().len(), notes.len());
+
+        cfg.set_mod_note("mod_a", "  ");
+        assert!(cfg.get_mod_note("mod_a").is_none());
+        assert!(cfg.get_mod_note("mod_d").is_none());
+
+        remove_file(test_file).unwrap();
+        remove_file(backup_path(test_file)).unwrap();
+    }
+
+    #[test]
+    fn pinned_mods_follow_rename_and_removal() {
+        let mock = MockGameDir::builder("pinned_mods")
+            .add_mod("pinned", &["mods\\pinned.dll"])
+            .add_mod("other", &["mods\\other.dll"])
+            .build()
+            .unwrap();
+
+        let mut cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert!(!cfg.is_pinned("pinned"));
+        cfg.set_pinned("pinned", true);
+        cfg.write_to_file().unwrap();
+
+        let mut cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert!(cfg.is_pinned("pinned"));
+        assert!(!cfg.is_pinned("other"));
+
+        cfg.rename_mod("pinned", "Still Pinned", mock.loader_dir()).unwrap();
+        let cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert!(!cfg.is_pinned("pinned"));
+        assert!(cfg.is_pinned("Still Pinned"));
+        assert!(cfg.is_pinned(" Still  Pinned "));
+
+        let reg_mod = cfg.get_mod(&"Still Pinned".into(), mock.game_dir(), None).unwrap();
+        reg_mod.remove_from_file(mock.ini_dir()).unwrap();
+        let cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert!(!cfg.is_pinned("Still Pinned"));
+    }
+
+    #[test]
+    fn favorites_are_limited_and_pruned() {
+        let mock = MockGameDir::builder("favorite_mods")
+            .add_mod("favorite", &["mods\\favorite.dll"])
+            .add_mod("other", &["mods\\other.dll"])
+            .build()
+            .unwrap();
+
+        let mut cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert!(cfg.favorites().is_empty());
+        assert!(cfg.toggle_favorite("favorite").unwrap());
+        assert!(cfg.toggle_favorite("other").unwrap());
+        assert!(!cfg.toggle_favorite("other").unwrap());
+        cfg.write_to_file().unwrap();
+
+        let mut cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert_eq!(cfg.favorites(), vec![String::from("favorite")]);
+        assert!(cfg.is_favorite("favorite"));
+        assert!(!cfg.is_favorite("other"));
+
+        // favorites that are not registered count towards the limit until they are pruned
+        (1..MAX_FAVORITES).for_each(|i| {
+            assert!(cfg.toggle_favorite(&format!("removed {i}")).unwrap());
+        });
+        let limit_err = cfg.toggle_favorite("other").unwrap_err();
+        assert_eq!(limit_err.kind(), std::io::ErrorKind::InvalidInput);
+        assert_eq!(cfg.favorites().len(), MAX_FAVORITES);
+        assert!(cfg.favorites().contains(&String::from("removed_1")));
+
+        assert_eq!(cfg.validate_entries().unwrap_err().len(), MAX_FAVORITES - 1);
+        assert_eq!(cfg.favorites(), vec![String::from("favorite")]);
+        assert!(cfg.toggle_favorite("other").unwrap());
+        cfg.write_to_file().unwrap();
+
+        cfg.rename_mod("favorite", "Still Favorite", mock.loader_dir()).unwrap();
+        let cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert_eq!(
+            cfg.favorites(),
+            vec![String::from("other"), String::from("Still_Favorite")]
+        );
+
+        let reg_mod = cfg.get_mod(&"Still Favorite".into(), mock.game_dir(), None).unwrap();
+        reg_mod.remove_from_file(mock.ini_dir()).unwrap();
+        let cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert_eq!(cfg.favorites(), vec![String::from("other")]);
+    }
+
+    #[test]
+    fn unicode_mod_names_are_found_by_canonical_key() {
+        let mock = MockGameDir::builder("test_unicode_names")
+            .add_mod("Über Mod", &["mods\\uber.dll"])
+            .add_mod("İstanbul", &["mods\\istanbul.dll"])
+            .build()
+            .unwrap();
+
+        let mut cfg = Cfg::read(mock.ini_dir()).unwrap();
+        let keys = cfg.keys();
+        assert!(keys.contains(&canonical_key("über mod")));
+        assert!(keys.contains(&canonical_key("İSTANBUL")));
+
+        // decomposed, differently cased and extra spaced names all resolve to the saved key
+        for name in ["U\u{308}ber Mod", "über mod", " Über   Mod ", "ÜBER_MOD"] {
+            assert_eq!(cfg.find_key(name), Some("Über_Mod"), "{name:?}");
+            let reg_mod = cfg.get_mod(&name.into(), mock.game_dir(), None).unwrap();
+            assert_eq!(reg_mod.name, "Über_Mod");
+        }
+        assert_eq!(cfg.find_key("İstanbul "), Some("İstanbul"));
+        assert!(cfg.find_key("Uber Mod").is_none());
+
+        let duplicate_err = cfg
+            .rename_mod("İstanbul", "U\u{308}BER mod", mock.loader_dir())
+            .unwrap_err();
+        assert_eq!(duplicate_err.kind(), std::io::ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn rename_mod_matches_key_ignoring_case() {
+        let mock = MockGameDir::builder("test_rename_case")
+            .add_mod("Case Mod", &["mods\\case.dll"])
+            .add_mod("other", &["mods\\other.dll"])
+            .build()
+            .unwrap();
+
+        let mut cfg = Cfg::read(mock.ini_dir()).unwrap();
+        cfg.set_pinned("Case_Mod", true);
+        cfg.write_to_file().unwrap();
+
+        let mut cfg = Cfg::read(mock.ini_dir()).unwrap();
+        cfg.rename_mod("case_mod", "Renamed Mod", mock.loader_dir()).unwrap();
+
+        let mut cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert!(cfg.find_key("Case Mod").is_none());
+        assert_eq!(cfg.find_key("renamed mod"), Some("Renamed_Mod"));
+        assert!(cfg.is_pinned("Renamed_Mod"));
+        assert_eq!(cfg.keys().len(), 2);
+
+        let reg_mod = cfg.get_mod(&"Renamed Mod".into(), mock.game_dir(), None).unwrap();
+        assert_eq!(reg_mod.files.dll, vec![PathBuf::from("mods\\case.dll")]);
+    }
+
+    #[test]
+    fn install_preview_limit_falls_back_to_default() {
+        let test_file = Path::new("temp\\test_install_preview_limit.ini");
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+
+        let cfg = Cfg::read(test_file).unwrap();
+        assert_eq!(cfg.get_install_preview_limit(), DEFAULT_INSTALL_PREVIEW_LIMIT);
+
+        for (value, expected) in [
+            ("1", 1),
+            (" 42 ", 42),
+            ("100", 100),
+            ("0", DEFAULT_INSTALL_PREVIEW_LIMIT),
+            ("101", DEFAULT_INSTALL_PREVIEW_LIMIT),
+            ("-3", DEFAULT_INSTALL_PREVIEW_LIMIT),
+            ("nine", DEFAULT_INSTALL_PREVIEW_LIMIT),
+        ] {
+            save_value_ext(test_file, INI_SECTIONS[0], INI_KEYS[5], value).unwrap();
+            let cfg = Cfg::read(test_file).unwrap();
+            assert_eq!(cfg.get_install_preview_limit(), expected, "value: '{value}'");
+        }
+
+        remove_file(test_file).unwrap();
+    }
+
+    #[test]
+    fn config_diff_reports_each_category() {
+        let current_file = Path::new("temp\\test_diff_current.ini");
+        let saved_file = Path::new("temp\\test_diff_saved.ini");
+        let new_mod = |name: &str, state: bool, files: &[&str]| {
+            RegMod::new(name, state, files.iter().map(PathBuf::from).collect())
+        };
+        let current = [
+            new_mod("same", true, &["mods\\same.dll", "mods\\same\\config.ini"]),
+            new_mod("toggled", true, &["mods\\toggled.dll"]),
+            new_mod("grown", true, &["mods\\grown.dll"]),
+            new_mod("only_current", true, &["mods\\only_current.dll"]),
+        ];
+        // file order, case and off_state are ignored
+        let saved = [
+            new_mod("Same", true, &["mods\\same\\CONFIG.ini", "mods\\same.dll"]),
+            new_mod("toggled", false, &["mods\\toggled.dll.disabled"]),
+            new_mod("grown", true, &["mods\\grown.dll", "mods\\grown\\new.ini"]),
+            new_mod("only_saved", true, &["mods\\only_saved.dll"]),
+        ];
+        for (file, mods) in [(current_file, current), (saved_file, saved)] {
+            new_cfg_with_sections(file, &INI_SECTIONS).unwrap();
+            mods.iter().for_each(|m| m.write_to_file(file, false).unwrap());
+        }
+
+        let current = Cfg::read(current_file).unwrap();
+        let saved = Cfg::read_snapshot(saved_file).unwrap();
+        assert!(current.diff_against(&current).is_empty());
+        let mut diff = current.diff_against(&saved);
+        assert_eq!(diff.added, ["only_saved"]);
+        assert_eq!(diff.removed, ["only_current"]);
+        assert_eq!(diff.state_changed, [(String::from("toggled"), false)]);
+        assert_eq!(diff.files_changed, ["grown"]);
+        assert!(diff.order_changed.is_empty());
+        assert!(diff.to_string().contains("toggled will be disabled"));
+
+        let mut current_loader = ini::Ini::new();
+        current_loader
+            .with_section(LOADER_SECTIONS[1])
+            .set("a.dll", "1")
+            .set("b.dll", "2")
+            .set("d.dll", "not a number");
+        let mut saved_loader = ini::Ini::new();
+        saved_loader
+            .with_section(LOADER_SECTIONS[1])
+            .set("A.DLL", "1")
+            .set("b.dll", "3")
+            .set("c.dll", "1");
+        diff.compare_orders(&current_loader, &saved_loader);
+        assert_eq!(
+            diff.order_changed,
+            [
+                (String::from("b.dll"), Some(2), Some(3)),
+                (String::from("c.dll"), None, Some(1))
+            ]
+        );
+
+        remove_file(current_file).unwrap();
+        remove_file(saved_file).unwrap();
+    }
+
+    #[test]
+    fn missing_or_invalid_bool_setting_saves_default() {
+        let test_file = Path::new("temp\\test_bool_setting.ini");
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+        save_value(test_file, INI_SECTIONS[0], INI_KEYS[0], "maybe").unwrap();
+
+        // reads fall back to the default and save it to file
+        let cfg = Cfg::read(test_file).unwrap();
+        assert!(!cfg.get_bool_setting(INI_KEYS[6], DEFAULT_WATCH_FILES));
+        assert!(cfg.get_dark_mode());
+        let saved = get_cfg(test_file).unwrap();
+        assert_eq!(saved.get_from(INI_SECTIONS[0], INI_KEYS[6]), Some("false"));
+        assert_eq!(saved.get_from(INI_SECTIONS[0], INI_KEYS[0]), Some("true"));
+
+        // `validate_entries` resets the remaining settings in memory
+        save_value(test_file, INI_SECTIONS[0], INI_KEYS[9], "maybe").unwrap();
+        let mut cfg = Cfg::read(test_file).unwrap();
+        let messages = cfg.reset_bool_settings();
+        assert_eq!(messages.len(), 1);
+        assert!(messages[0].contains(INI_KEYS[9]));
+        cfg.write_to_file().unwrap();
+
+        let saved = get_cfg(test_file).unwrap();
+        assert_eq!(saved.get_from(INI_SECTIONS[0], INI_KEYS[9]), Some("false"));
+        assert_eq!(saved.get_from(INI_SECTIONS[0], INI_KEYS[10]), Some("false"));
+        assert!(Cfg::read(test_file).unwrap().reset_bool_settings().is_empty());
+
+        remove_file(test_file).unwrap();
+        remove_file(backup_path(test_file)).unwrap();
+    }
+
+    #[test]
+    fn save_log_round_trips() {
+        let test_file = Path::new("temp\\test_save_log.ini");
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+
+        for state in [false, true] {
+            save_bool(test_file, INI_SECTIONS[0], INI_KEYS[1], state).unwrap();
+            let cfg = Cfg::read(test_file).unwrap();
+            assert_eq!(cfg.get_save_log(), state);
+        }
+
+        remove_file(test_file).unwrap();
+
+        let log_file = Path::new("temp\\test_save_log.log");
+        let _ = remove_file(log_file);
+        let (file_layer, handle) = reload::Layer::new(None::<FileLayer<Registry>>);
+        let mut writer_guard = None;
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(file_layer), || {
+            reload_file_layer(&handle, &mut writer_guard, log_file, true).unwrap();
+            assert!(handle.with_current(Option::is_some).unwrap());
+            tracing::info!("logged while saving");
+
+            reload_file_layer(&handle, &mut writer_guard, log_file, false).unwrap();
+            assert!(handle.with_current(Option::is_none).unwrap());
+            tracing::info!("logged while not saving");
+
+            reload_file_layer(&handle, &mut writer_guard, log_file, true).unwrap();
+            tracing::info!("logged after re-enabling");
+            reload_file_layer(&handle, &mut writer_guard, log_file, false).unwrap();
+        });
+
+        let logs = std::fs::read_to_string(log_file).unwrap();
+        assert!(logs.contains("logged while saving"));
+        assert!(!logs.contains("logged while not saving"));
+        assert!(logs.contains("logged after re-enabling"));
+
+        remove_file(log_file).unwrap();
+    }
+
+    #[test]
+    fn repair_malformed_arrays() {
+        let test_file = Path::new("temp\\test_repair_arrays.ini");
+        let malformed = "[app-settings]\r\n\
+            [paths]\r\n\
+            [registered-mods]\r\n\
+            plain=true\r\n\
+            empty_array=true\r\n\
+            single_array=false\r\n\
+            [mod-files]\r\n\
+            plain=mods\\plain.dll\r\n\
+            array[]=mods\\stray.dll\r\n\
+            empty_array=array\r\n\
+            single_array=array\r\n\
+            array[]=mods\\single_array.dll.disabled\r\n";
+        std::fs::create_dir_all("temp").unwrap();
+        std::fs::write(test_file, malformed).unwrap();
+
+        let mut cfg = Cfg::read(test_file).unwrap();
+        // one message for the stray `array[]` row and one for the `array` key with no files
+        assert_eq!(cfg.validate_entries().unwrap_err().len(), 2);
+        cfg.write_to_file().unwrap();
+
+        let config = get_cfg(test_file).unwrap();
+        assert_eq!(
+            config.get_from(INI_SECTIONS[3], "single_array"),
+            Some("mods\\single_array.dll.disabled")
+        );
+        assert!(config.get_from(INI_SECTIONS[3], "empty_array").is_none());
+        assert!(config.get_from(INI_SECTIONS[2], "empty_array").is_none());
+        assert!(config
+            .section(INI_SECTIONS[3])
+            .unwrap()
+            .get(ARRAY_KEY)
+            .is_none());
+
+        let cfg = Cfg::read(test_file).unwrap();
+        let mut mods = cfg.collect_mods(Path::new(""), None, true).mods;
+        mods.sort_by(|a, b| a.name.cmp(&b.name));
+        let expected = [
+            RegMod::new("plain", true, vec![PathBuf::from("mods\\plain.dll")]),
+            RegMod::new(
+                "single_array",
+                false,
+                vec![PathBuf::from("mods\\single_array.dll.disabled")],
+            ),
+        ];
+        assert_eq!(mods.len(), expected.len());
+        for (reg_mod, expected) in mods.iter().zip(expected.iter()) {
+            assert_eq!(reg_mod.name, expected.name);
+            assert_eq!(reg_mod.state, expected.state);
+            assert_eq!(reg_mod.files.dll, expected.files.dll);
+        }
+
+        remove_file(test_file).unwrap();
+        remove_file(backup_path(test_file)).unwrap();
+    }
+
+    #[test]
+    fn install_dir_is_consistent() {
+        let game_dir = Path::new(GAME_DIR);
+        let mods_dir = game_dir.join("mods");
+
+        let single_dll = RegMod::new("single", true, vec![PathBuf::from("mods\\single.dll")]);
+        assert_eq!(single_dll.install_dir(game_dir).unwrap(), mods_dir.join("single"));
+
+        let disabled_with_config = RegMod::new(
+            "with config",
+            false,
+            vec![
+                PathBuf::from("mods\\with_config.dll.disabled"),
+                PathBuf::from("mods\\with_config\\config.ini"),
+            ],
+        );
+        assert_eq!(
+            disabled_with_config.install_dir(game_dir).unwrap(),
+            mods_dir.join("with_config")
+        );
+
+        let in_mods_dir = RegMod::new(
+            "loose files",
+            true,
+            vec![
+                PathBuf::from("mods\\first.dll"),
+                PathBuf::from("mods\\second.dll"),
+                PathBuf::from("mods\\loose\\readme.txt"),
+            ],
+        );
+        assert_eq!(in_mods_dir.install_dir(game_dir).unwrap(), mods_dir);
+
+        assert!(RegMod::new("empty", true, Vec::new()).install_dir(game_dir).is_err());
+    }
+
+    #[test]
+    fn disk_state_reports_files_on_disk() {
+        let mock_game = MockGameDir::builder("disk_state")
+            .add_mod(
+                "enabled",
+                &[
+                    "mods\\enabled_1.dll",
+                    "mods\\enabled_2.dll",
+                    "mods\\enabled\\config.ini",
+                ],
+            )
+            .add_mod(
+                "disabled",
+                &["mods\\disabled_1.dll", "mods\\disabled_2.dll"],
+            )
+            .enabled(false)
+            .add_mod("mixed", &["mods\\mixed_1.dll", "mods\\mixed_2.dll"])
+            .add_mod("no dlls", &["mods\\no_dlls\\config.ini"])
+            .build()
+            .unwrap();
+        let game_dir = mock_game.game_dir();
+        std::fs::rename(
+            game_dir.join("mods\\mixed_2.dll"),
+            game_dir.join(format!("mods\\mixed_2.dll{OFF_STATE}")),
+        )
+        .unwrap();
+
+        let enabled = RegMod::new(
+            "enabled",
+            true,
+            vec![
+                PathBuf::from("mods\\enabled_1.dll"),
+                PathBuf::from("mods\\enabled_2.dll"),
+                PathBuf::from("mods\\enabled\\config.ini"),
+            ],
+        );
+        assert_eq!(enabled.disk_state(game_dir).unwrap(), Some(true));
+
+        // saved as enabled but the files were disabled outside of the app
+        let disabled = RegMod::new(
+            "disabled",
+            true,
+            vec![
+                PathBuf::from("mods\\disabled_1.dll"),
+                PathBuf::from("mods\\disabled_2.dll"),
+            ],
+        );
+        assert_eq!(disabled.disk_state(game_dir).unwrap(), Some(false));
+
+        let mixed = RegMod::new(
+            "mixed",
+            true,
+            vec![
+                PathBuf::from("mods\\mixed_1.dll"),
+                PathBuf::from("mods\\mixed_2.dll"),
+            ],
+        );
+        assert_eq!(mixed.disk_state(game_dir).unwrap(), None);
+
+        let no_dlls = RegMod::new(
+            "no dlls",
+            true,
+            vec![PathBuf::from("mods\\no_dlls\\config.ini")],
+        );
+        assert_eq!(no_dlls.disk_state(game_dir).unwrap(), Some(true));
+
+        let missing = RegMod::new("missing", true, vec![PathBuf::from("mods\\missing.dll")]);
+        assert_eq!(
+            missing.disk_state(game_dir).unwrap_err().kind(),
+            std::io::ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn relocate_game_dir_keeps_registered_mods() {
+        let mock = MockGameDir::builder("relocate_game_dir")
+            .add_mod("kept", &["mods\\kept.dll", "mods\\kept\\config.ini"])
+            .add_mod("lost", &["mods\\lost.dll"])
+            .build()
+            .unwrap();
+        let new_dir = mock.game_dir().with_file_name("Moved");
+        std::fs::rename(mock.game_dir(), &new_dir).unwrap();
+        remove_file(new_dir.join("mods\\lost.dll")).unwrap();
+
+        let mut cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert_eq!(
+            cfg.relocate_game_dir(mock.game_dir()).unwrap_err().kind(),
+            std::io::ErrorKind::NotFound
+        );
+
+        let missing = cfg.relocate_game_dir(&new_dir).unwrap();
+        assert_eq!(
+            missing,
+            vec![(String::from("lost"), vec![PathBuf::from("mods\\lost.dll")])]
+        );
+
+        let saved = Cfg::read(mock.ini_dir()).unwrap();
+        assert_eq!(
+            saved.data().get_from(INI_SECTIONS[1], INI_KEYS[2]),
+            Some(new_dir.to_string_lossy().as_ref())
+        );
+        assert_eq!(saved.mods_registered(), 2);
+    }
+
+    #[test]
+    fn recent_game_dirs_are_ordered_and_pruned() {
+        let first = MockGameDir::builder("recent_dirs_first").build().unwrap();
+        let second = MockGameDir::builder("recent_dirs_second").build().unwrap();
+
+        let mut cfg = Cfg::read(first.ini_dir()).unwrap();
+        assert!(cfg.recent_game_dirs().is_empty());
+        cfg.add_recent_game_dir(first.game_dir()).unwrap();
+        cfg.add_recent_game_dir(second.game_dir()).unwrap();
+        cfg.add_recent_game_dir(first.game_dir()).unwrap();
+
+        let mut saved = Cfg::read(first.ini_dir()).unwrap();
+        assert_eq!(
+            saved.recent_game_dirs(),
+            vec![first.game_dir().to_path_buf(), second.game_dir().to_path_buf()]
+        );
+
+        remove_file(second.game_dir().join(REQUIRED_GAME_FILES[0])).unwrap();
+        assert_eq!(saved.recent_game_dirs(), vec![first.game_dir().to_path_buf()]);
+
+        saved.prune_recent_game_dirs().unwrap();
+        let pruned = Cfg::read(first.ini_dir()).unwrap();
+        assert_eq!(pruned.data().section(RECENT_DIRS_SECTION).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn verify_hashes_detects_changed_files() {
+        let mock = MockGameDir::builder("verify_hashes")
+            .add_mod("hashed", &["mods\\hashed.dll", "mods\\hashed\\config.ini"])
+            .build()
+            .unwrap();
+        let changed_file = PathBuf::from("mods\\hashed\\config.ini");
+
+        let cfg = Cfg::read(mock.ini_dir()).unwrap();
+        let mut reg_mod = cfg.collect_mods(mock.game_dir(), None, false).mods.pop().unwrap();
+        assert!(reg_mod.hashes.is_empty());
+        reg_mod.record_hashes(mock.game_dir(), mock.ini_dir()).unwrap();
+        assert_eq!(reg_mod.hashes.len(), 2);
+        assert!(reg_mod.verify_hashes(mock.game_dir()).is_empty());
+
+        std::fs::write(mock.game_dir().join(&changed_file), "corrupted").unwrap();
+        save_bool(mock.ini_dir(), INI_SECTIONS[0], INI_KEYS[9], true).unwrap();
+
+        let cfg = Cfg::read(mock.ini_dir()).unwrap();
+        let saved = cfg.collect_mods(mock.game_dir(), None, false).mods.pop().unwrap();
+        assert_eq!(saved.verify_hashes(mock.game_dir()), vec![changed_file.clone()]);
+        assert_eq!(
+            cfg.diagnose(mock.game_dir(), &HashSet::new()).changed_files,
+            vec![(String::from("hashed"), vec![changed_file])]
+        );
+    }
+
+    #[test]
+    fn files_missing_lists_only_absent_files() {
+        let mock_game = MockGameDir::builder("files_missing")
+            .add_mod(
+                "partial",
+                &[
+                    "mods\\partial.dll",
+                    "mods\\partial\\config.ini",
+                    "mods\\partial\\readme.txt",
+                ],
+            )
+            .add_mod("complete", &["mods\\complete.dll"])
+            .enabled(false)
+            .build()
+            .unwrap();
+        let game_dir = mock_game.game_dir();
+        remove_file(game_dir.join("mods\\partial\\config.ini")).unwrap();
+
+        let partial = RegMod::new(
+            "partial",
+            true,
+            vec![
+                PathBuf::from("mods\\partial.dll"),
+                PathBuf::from("mods\\partial\\config.ini"),
+                PathBuf::from("mods\\partial\\readme.txt"),
+                PathBuf::from("mods\\partial\\never_installed.dll"),
+            ],
+        );
+        assert_eq!(
+            partial.files_missing(game_dir),
+            vec![
+                PathBuf::from("mods\\partial\\never_installed.dll"),
+                PathBuf::from("mods\\partial\\config.ini"),
+            ]
+        );
+
+        let complete = RegMod::new(
+            "complete",
+            false,
+            vec![PathBuf::from(format!("mods\\complete.dll{OFF_STATE}"))],
+        );
+        assert!(complete.files_missing(game_dir).is_empty());
+
+        // saved in the wrong state, `files_missing` does not look for the toggled file
+        let wrong_state = RegMod::new("complete", true, vec![PathBuf::from("mods\\complete.dll")]);
+        assert_eq!(
+            wrong_state.files_missing(game_dir),
+            vec![PathBuf::from("mods\\complete.dll")]
+        );
+    }
+
+    #[test]
+    fn load_delay_is_parsed_and_clamped() {
+        assert_eq!(parse_load_delay("2500").unwrap(), 2500);
+        assert_eq!(parse_load_delay(" 300 ms ").unwrap(), 300);
+        assert_eq!(parse_load_delay("0ms").unwrap(), 0);
+        for invalid in ["", "ms", "-5", "1.5", "60001", "soon"] {
+            assert_eq!(
+                parse_load_delay(invalid).unwrap_err().kind(),
+                std::io::ErrorKind::InvalidInput
+            );
+        }
+
+        let mock = MockGameDir::builder("load_delay_clamp").build().unwrap();
+        save_value_ext(mock.loader_dir(), LOADER_SECTIONS[0], LOADER_KEYS[0], "99999999").unwrap();
+        let cfg = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        assert_eq!(cfg.get_load_delay().unwrap(), *LOAD_DELAY_RANGE.end());
+
+        // the clamped value is saved so the stored value is valid from now on
+        let cfg = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        assert_eq!(
+            cfg.data().get_from(LOADER_SECTIONS[0], LOADER_KEYS[0]),
+            Some("60000")
+        );
+    }
+
+    #[test]
+    fn loader_bool_keys_round_trip() {
+        let mock = MockGameDir::builder("loader_bool_keys").build().unwrap();
+        let mut cfg = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        assert!(!cfg.get_bool_key(LOADER_KEYS[1]).unwrap());
+
+        cfg.set_bool_key(LOADER_KEYS[1], true).unwrap();
+        assert!(cfg.get_show_terminal().unwrap());
+        let cfg = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        assert_eq!(cfg.data().get_from(LOADER_SECTIONS[0], LOADER_KEYS[1]), Some("1"));
+
+        // an invalid value is reset to the default of the key
+        save_value_ext(mock.loader_dir(), LOADER_SECTIONS[0], LOADER_KEYS[1], "maybe").unwrap();
+        let mut cfg = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        assert!(cfg.get_bool_key(LOADER_KEYS[1]).is_err());
+        let saved = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        assert_eq!(
+            saved.data().get_from(LOADER_SECTIONS[0], LOADER_KEYS[1]),
+            default_loader_value(LOADER_KEYS[1])
+        );
+
+        assert_eq!(
+            cfg.set_bool_key("not_a_loader_key", true).unwrap_err().kind(),
+            std::io::ErrorKind::InvalidInput
+        );
+    }
+
+    #[test]
+    fn write_to_file_backs_up_previous_contents() {
+        let test_file = Path::new("temp\\test_backup.ini");
+        let backup = backup_path(test_file);
+        let _ = remove_file(&backup);
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+        assert_eq!(
+            restore_backup(test_file).unwrap_err().kind(),
+            std::io::ErrorKind::NotFound
+        );
+        save_bool(test_file, INI_SECTIONS[0], INI_KEYS[0], false).unwrap();
+
+        // the missing setting is saved with its default before the backup is taken
+        let mut cfg = Cfg::read(test_file).unwrap();
+        assert!(cfg.get_backup_configs());
+        let before = std::fs::read_to_string(test_file).unwrap();
+        assert!(before.contains("backup_configs=true"));
+        cfg.set(INI_SECTIONS[0], INI_KEYS[0], "true");
+        cfg.write_to_file().unwrap();
+        let after = std::fs::read_to_string(test_file).unwrap();
+        assert_ne!(after, before);
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), before);
+
+        // restoring swaps the backup with the current contents
+        restore_backup(test_file).unwrap();
+        assert_eq!(std::fs::read_to_string(test_file).unwrap(), before);
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), after);
+
+        remove_file(test_file).unwrap();
+        remove_file(backup).unwrap();
+    }
+
+    #[test]
+    fn failed_write_leaves_original_untouched() {
+        let test_file = Path::new("temp\\test_atomic_write.ini");
+        let temp = temp_path(test_file);
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+        save_bool(test_file, INI_SECTIONS[0], INI_KEYS[0], false).unwrap();
+        let before = std::fs::read_to_string(test_file).unwrap();
+
+        // windows ignores the read-only attribute on directories, a directory in place of the
+        // temp file makes the write fail before `test_file` would be replaced instead
+        std::fs::create_dir_all(&temp).unwrap();
+        let mut cfg = Cfg::read(test_file).unwrap();
+        cfg.set(INI_SECTIONS[0], INI_KEYS[0], "true");
+        assert!(cfg.write_to_file().is_err());
+        assert_eq!(std::fs::read_to_string(test_file).unwrap(), before);
+        std::fs::remove_dir(&temp).unwrap();
+
+        cfg.write_to_file().unwrap();
+        assert!(!temp.exists());
+        assert!(Cfg::read(test_file).unwrap().get_dark_mode());
+
+        remove_file(test_file).unwrap();
+        remove_file(backup_path(test_file)).unwrap();
+    }
+
+    #[test]
+    fn mods_registered_with_the_loader_hook_are_rejected() {
+        let mock = MockGameDir::builder("loader_hook_mod")
+            .add_mod("bogus", &["dinput8.dll"])
+            .add_mod("bogus disabled", &["mods\\dinput8.dll", "mods\\bogus\\config.ini"])
+            .enabled(false)
+            .add_mod("real", &["mods\\real.dll"])
+            .build()
+            .unwrap();
+
+        let cfg = Cfg::read(mock.ini_dir()).unwrap();
+        let collected = cfg.collect_mods(mock.game_dir(), None, false);
+        assert_eq!(
+            collected.mods.iter().map(|m| m.name.as_str()).collect::<Vec<_>>(),
+            vec!["real"]
+        );
+        let warning = collected.warnings.unwrap().to_string();
+        let hook_msg = "bogus was removed, its file: 'dinput8.dll' is the mod loader's dll hook";
+        assert!(warning.contains(hook_msg));
+        assert!(warning.contains("bogus disabled was removed"));
+
+        // the loader itself was not toggled
+        assert!(mock.game_dir().join(LOADER_FILES[1]).is_file());
+        let cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert_eq!(cfg.mods_registered(), 1);
+    }
+
+    #[test]
+    fn collect_mods_from_empty_config() {
+        let test_file = Path::new("temp\\test_collect_empty.ini");
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+
+        let cfg = Cfg::read(test_file).unwrap();
+        assert!(cfg.mods_is_empty());
+        for skip_validation in [true, false] {
+            let collected = cfg.collect_mods(GAME_DIR, None, skip_validation);
+            assert!(collected.mods.is_empty());
+            assert!(collected.warnings.is_none());
+        }
+
+        // missing sections are treated the same as empty sections
+        // `read_snapshot` is used as `read` would set up the missing sections
+        new_cfg_with_sections(test_file, &INI_SECTIONS[..2]).unwrap();
+        let cfg = Cfg::read_snapshot(test_file).unwrap();
+        assert!(cfg.data().section(INI_SECTIONS[2]).is_none());
+        assert!(cfg.mods_is_empty());
+        for skip_validation in [true, false] {
+            let collected = cfg.collect_mods(GAME_DIR, None, skip_validation);
+            assert!(collected.mods.is_empty());
+            assert!(collected.warnings.is_none());
+        }
+
+        remove_file(test_file).unwrap();
+    }
+
+    #[test]
+    fn iter_file_entries_reads_single_and_array_entries() {
+        let test_file = Path::new("temp\\test_iter_file_entries.ini");
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+        let single = RegMod::new("single", true, vec![PathBuf::from("mods\\single.dll")]);
+        let multi = RegMod::new(
+            "multi",
+            true,
+            vec![
+                PathBuf::from("mods\\multi.dll"),
+                PathBuf::from("mods\\multi\\config.ini"),
+            ],
+        );
+        single.write_to_file(test_file, false).unwrap();
+        multi.write_to_file(test_file, false).unwrap();
+
+        let cfg = Cfg::read(test_file).unwrap();
+        assert_eq!(
+            cfg.iter_file_entries().collect::<Vec<_>>(),
+            vec![
+                ("single", vec!["mods\\single.dll"]),
+                ("multi", vec!["mods\\multi.dll", "mods\\multi\\config.ini"]),
+            ]
+        );
+
+        remove_file(test_file).unwrap();
+    }
+
+    #[test]
+    fn owner_of_finds_mod_by_short_or_full_path() {
+        let root = std::env::current_dir().unwrap().join("temp").join("owner_of");
+        let mock = MockGameDir::builder_at(root)
+            .add_mod("single", &["mods\\single.dll"])
+            .add_mod("multi", &["mods\\multi.dll", "mods\\multi\\config.ini"])
+            .enabled(false)
+            .build()
+            .unwrap();
+
+        let cfg = Cfg::read(mock.ini_dir()).unwrap();
+        assert_eq!(
+            cfg.owner_of(Path::new("mods\\single.dll")).as_deref(),
+            Some("single")
+        );
+        assert_eq!(
+            cfg.owner_of(Path::new("mods/Multi/CONFIG.ini")).as_deref(),
+            Some("multi")
+        );
+
+        // "multi" is disabled so its dll is saved in the off state
+        let multi_dll = mock.game_dir().join(format!("mods\\multi.dll{OFF_STATE}"));
+        assert!(cfg
+            .iter_file_entries()
+            .any(|(_, files)| files.contains(&"mods\\multi.dll.disabled")));
+        assert_eq!(cfg.owner_of(&multi_dll).as_deref(), Some("multi"));
+        assert_eq!(
+            cfg.owner_of(&mock.game_dir().join("mods\\multi.dll")).as_deref(),
+            Some("multi")
+        );
+
+        assert_eq!(cfg.owner_of(Path::new("mods\\unowned.dll")), None);
+        assert_eq!(cfg.owner_of(Path::new("mods\\multi")), None);
+        let outside = std::env::temp_dir().join("mods\\single.dll");
+        assert_eq!(cfg.owner_of(&outside), None);
+    }
+
+    #[test]
+    fn schema_version_is_saved_and_migrated() {
+        let test_file = Path::new("temp\\test_schema_version.ini");
+        let version = SCHEMA_VERSION.to_string();
+        new_cfg_with_sections(test_file, &INI_SECTIONS).unwrap();
+        save_bool(test_file, INI_SECTIONS[0], INI_KEYS[0], false).unwrap();
+
+        // a config saved before "schema_version" existed is brought up to date
+        let mut cfg = Cfg::read(test_file).unwrap();
+        assert_eq!(cfg.data().get_from(INI_SECTIONS[0], INI_KEYS[11]), None);
+        cfg.migrate().unwrap();
+        assert_eq!(
+            cfg.data().get_from(INI_SECTIONS[0], INI_KEYS[11]),
+            Some(version.as_str())
+        );
+        assert!(!cfg.get_dark_mode());
+
+        let saved = get_cfg(test_file).unwrap();
+        assert_eq!(
+            saved.get_from(INI_SECTIONS[0], INI_KEYS[11]),
+            Some(version.as_str())
+        );
+
+        // a config from a newer version is left as is
+        save_value(test_file, INI_SECTIONS[0], INI_KEYS[11], "999").unwrap();
+        let mut cfg = Cfg::read(test_file).unwrap();
+        cfg.migrate().unwrap();
+        assert_eq!(
+            cfg.data().get_from(INI_SECTIONS[0], INI_KEYS[11]),
+            Some("999")
+        );
 
         remove_file(test_file).unwrap();
+        remove_file(backup_path(test_file)).unwrap();
     }
 }
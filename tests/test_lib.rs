@@ -3,19 +3,41 @@ pub mod common;
 #[cfg(test)]
 mod tests {
     use elden_mod_loader_gui::{
-        does_dir_contain, get_cfg, toggle_files,
-        utils::ini::{
-            parser::{IniProperty, RegMod},
-            writer::{save_path, save_paths},
+        canonical_key, check_app_location, cmp_categories, cmp_mod_names, detect_install_type,
+        does_dir_contain, file_name_from_str, format_key, get_cfg, get_drive, ini_cfg_path,
+        is_restricted_file, labeled_config_contents, loader_cfg_path, omit_off_state,
+        read_game_version, shorten_paths, shorten_paths_ci, toggle_all_mods, toggle_files,
+        toggle_single_file,
+        utils::{
+            display::{DisplayIndices, DisplaySize, DisplayVec},
+            error::EmlError,
+            ini::{
+                common::{Cfg, Config, ModLoaderCfg},
+                mod_loader::{loader_backend, ModLoader},
+                parser::{FileSet, IniProperty, LoadOrder, RegMod},
+                writer::{remove_order_entry, save_path, save_paths},
+            },
+            installer::{
+                check_path_within, clean_orphaned_files, copy_with_progress,
+                copy_with_progress_using, discover_mods, ensure_mods_dir, expand_file_pattern,
+                normalize_path, orphaned_files, remove_mod_files, replaced_path, scan_for_mods,
+                transfer_file, AddDirSummary, DisplayItems, InstallData, ScanMode, Transfer,
+            },
+            lock::UiRwLock,
         },
-        Operation, OperationResult, INI_SECTIONS, OFF_STATE,
+        AppLocationWarning, DriveRoot, FileCategory, InstallType, Operation, OperationResult,
+        OrderMap, DEFAULT_CATEGORY, GAME_PASS_FILES, INI_SECTIONS, LOADER_FILES,
+        MAX_PATTERN_MATCHES, OFF_STATE,
     };
     use std::{
+        collections::HashSet,
         fs::{self, remove_file, File},
+        io::ErrorKind,
         path::{Path, PathBuf},
+        sync::atomic::AtomicBool,
     };
 
-    use crate::common::{file_exists, new_cfg_with_sections, GAME_DIR};
+    use crate::common::{block_on, file_exists, new_cfg_with_sections, MockGameDir, GAME_DIR};
 
     #[test]
     fn do_files_toggle() {
@@ -149,4 +171,1141 @@ mod tests {
             Ok(OperationResult::Bool(false))
         ));
     }
+
+    #[test]
+    fn install_paths_stay_in_game_dir() {
+        let game_dir = Path::new(GAME_DIR);
+
+        assert_eq!(
+            normalize_path(Path::new("C:\\Game\\mods\\.\\a_mod\\..\\b_mod.dll")),
+            Path::new("C:\\Game\\mods\\b_mod.dll")
+        );
+        assert_eq!(
+            check_path_within(game_dir, &game_dir.join("mods\\a_mod\\..\\a_mod.dll")).unwrap(),
+            game_dir.join("mods\\a_mod.dll")
+        );
+
+        let escapes = [
+            game_dir.join("mods\\..\\..\\evil.dll"),
+            game_dir.join("..\\Game_evil\\evil.dll"),
+            PathBuf::from("C:\\Windows\\System32\\evil.dll"),
+        ];
+        for path in escapes {
+            assert_eq!(
+                check_path_within(game_dir, &path).unwrap_err().kind(),
+                ErrorKind::InvalidInput
+            );
+        }
+
+        let source_dir = Path::new("temp\\install_paths");
+        let source_file = source_dir.join("a_mod.dll");
+        fs::create_dir_all(source_dir).unwrap();
+        File::create(&source_file).unwrap();
+
+        let valid = InstallData::new(
+            "valid",
+            vec![source_file.clone(), source_dir.join("config\\..\\a_mod.ini")],
+            game_dir,
+        )
+        .unwrap();
+        assert!(valid.zip_from_to_paths().is_ok());
+
+        let malicious = InstallData::new(
+            "malicious",
+            vec![source_file, source_dir.join("..\\..\\..\\..\\evil.dll")],
+            game_dir,
+        )
+        .unwrap();
+        assert_eq!(
+            malicious.zip_from_to_paths().unwrap_err().kind(),
+            ErrorKind::InvalidInput
+        );
+
+        fs::remove_dir_all(source_dir).unwrap();
+    }
+
+    #[test]
+    fn symlinked_files_are_reported_by_path() {
+        let game_dir = Path::new(GAME_DIR);
+        let source_dir = Path::new("temp\\symlink_source");
+        let linked_dir = Path::new("temp\\symlink_linked");
+        let source_file = source_dir.join("a_mod.dll");
+        let link = linked_dir.join("config.ini");
+        fs::create_dir_all(source_dir).unwrap();
+        fs::create_dir_all(linked_dir).unwrap();
+        File::create(&source_file).unwrap();
+        let target = fs::canonicalize(&source_file).unwrap();
+
+        #[cfg(windows)]
+        let created = std::os::windows::fs::symlink_file(target, &link);
+        #[cfg(unix)]
+        let created = std::os::unix::fs::symlink(target, &link);
+        // creating symlinks can require elevated permissions on windows
+        if created.is_err() {
+            fs::remove_dir_all(source_dir).unwrap();
+            fs::remove_dir_all(linked_dir).unwrap();
+            return;
+        }
+
+        let mut data = InstallData::new("a_mod", vec![source_file], game_dir).unwrap();
+        let err = block_on(data.update_fields_with_new_dir(linked_dir, DisplayItems::All))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().contains(&link.display().to_string()), "{err}");
+        assert!(matches!(
+            err.get_ref().and_then(|err| err.downcast_ref::<EmlError>()),
+            Some(EmlError::SymlinkFound(path)) if *path == link
+        ));
+
+        fs::remove_dir_all(source_dir).unwrap();
+        fs::remove_dir_all(linked_dir).unwrap();
+    }
+
+    #[test]
+    fn install_preview_summary_count() {
+        let game_dir = Path::new(GAME_DIR);
+        let source_dir = Path::new("temp\\install_preview");
+        let config_dir = source_dir.join("config");
+        let source_file = source_dir.join("a_mod.dll");
+
+        for (limit, file_count, expected) in [
+            (3, 3, None),
+            (3, 4, Some("Plus 1 more file")),
+            (3, 6, Some("Plus 3 more files...")),
+            (1, 2, Some("Plus 1 more file")),
+            (100, 100, None),
+        ] {
+            fs::create_dir_all(&config_dir).unwrap();
+            File::create(&source_file).unwrap();
+            for i in 0..file_count {
+                File::create(config_dir.join(format!("config_{i}.ini"))).unwrap();
+            }
+
+            let mut data = InstallData::new("preview", vec![source_file.clone()], game_dir).unwrap();
+            block_on(data.update_fields_with_new_dir(&config_dir, DisplayItems::Limit(limit)))
+                .unwrap();
+
+            // first line is the selected `source_file`
+            let lines = data.display_paths.lines().collect::<Vec<_>>();
+            match expected {
+                Some(summary) => {
+                    assert_eq!(lines.len(), limit + 2);
+                    assert_eq!(lines.last(), Some(&summary));
+                }
+                None => assert_eq!(lines.len(), file_count + 1),
+            }
+
+            fs::remove_dir_all(source_dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn add_dir_summary_reports_branch_taken() {
+        let game_dir = Path::new(GAME_DIR);
+        let source_dir = Path::new("temp\\add_dir_summary");
+        let config_dir = source_dir.join("config");
+        let source_file = source_dir.join("a_mod.dll");
+        fs::create_dir_all(&config_dir).unwrap();
+        File::create(&source_file).unwrap();
+        for i in 0..3 {
+            File::create(config_dir.join(format!("config_{i}.ini"))).unwrap();
+        }
+
+        // selected directory only contains new files
+        let mut data = InstallData::new("a_mod", vec![source_file.clone()], game_dir).unwrap();
+        let summary = block_on(data.update_fields_with_new_dir(&config_dir, DisplayItems::All));
+        let expected = AddDirSummary {
+            files_added: 3,
+            moved_whole_folder: true,
+            ..Default::default()
+        };
+        assert_eq!(summary.unwrap(), expected);
+
+        // selected directory contains the original file, so it is read again with everything else
+        let mut data = InstallData::new("a_mod", vec![source_file.clone()], game_dir).unwrap();
+        let summary = block_on(data.update_fields_with_new_dir(source_dir, DisplayItems::All));
+        let expected = AddDirSummary {
+            files_added: 3,
+            reconstructed: true,
+            ..Default::default()
+        };
+        assert_eq!(summary.unwrap(), expected);
+
+        // a "mods" directory next to other files is rejected
+        let mods_dir = source_dir.join("mods");
+        fs::create_dir_all(&mods_dir).unwrap();
+        File::create(mods_dir.join("other.dll")).unwrap();
+        let mut data =
+            InstallData::new("a_mod", vec![config_dir.join("config_0.ini")], game_dir).unwrap();
+        let err = block_on(data.update_fields_with_new_dir(&mods_dir, DisplayItems::None))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+        fs::remove_dir_all(source_dir).unwrap();
+    }
+
+    #[test]
+    fn file_categories() {
+        let test_files = [
+            ("mods\\a_mod.dll", FileCategory::Dll),
+            ("mods\\a_mod.dll.disabled", FileCategory::Dll),
+            ("mods\\a_mod\\config.ini", FileCategory::Config),
+            ("mods\\a_mod\\load.txt", FileCategory::Text),
+            ("mods\\a_mod\\parts\\am_m_1100.partsbnd.dcx", FileCategory::Asset),
+            ("mods\\a_mod\\menu\\icon.DDS", FileCategory::Asset),
+            ("mods\\a_mod\\regulation.bin", FileCategory::Other),
+        ];
+        for (file, expected) in test_files {
+            assert_eq!(FileCategory::from_path(&file), expected, "{file}");
+        }
+    }
+
+    #[test]
+    fn shorten_paths_ignores_case() {
+        let game_dir = PathBuf::from(GAME_DIR);
+        let selected_files = [
+            PathBuf::from(GAME_DIR.to_lowercase()).join("mods\\A_Mod.dll"),
+            game_dir.join("mods\\A_Mod\\config.ini"),
+        ];
+
+        assert!(shorten_paths(&selected_files, &game_dir).is_err());
+
+        let shortened = shorten_paths_ci(&selected_files, &game_dir).unwrap();
+        assert_eq!(
+            shortened,
+            [Path::new("mods\\A_Mod.dll"), Path::new("mods\\A_Mod\\config.ini")]
+        );
+
+        let not_installed = [PathBuf::from("D:\\Downloads\\A_Mod.dll")];
+        let err = shorten_paths_ci(&not_installed, &game_dir).unwrap_err();
+        assert_eq!(err.err_paths_long, [not_installed[0].as_path()]);
+    }
+
+    #[test]
+    fn forward_slash_paths_resolve() {
+        assert_eq!(file_name_from_str("mods/Sub/x.dll"), "x.dll");
+        assert_eq!(
+            file_name_from_str("mods/Sub/x.dll"),
+            file_name_from_str("mods\\Sub\\x.dll")
+        );
+        assert_eq!(omit_off_state(file_name_from_str("mods/Sub/x.dll.disabled")), "x.dll");
+
+        let order_map = OrderMap::from([(String::from("x.dll"), 3)]);
+        let forward = RegMod::with_load_order(
+            "forward",
+            true,
+            vec![PathBuf::from("mods/Sub/x.dll")],
+            &order_map,
+        );
+        let back = RegMod::with_load_order(
+            "back",
+            true,
+            vec![PathBuf::from("mods\\Sub\\x.dll")],
+            &order_map,
+        );
+        assert_eq!(forward.files.dll, back.files.dll);
+        assert!(forward.order.set && back.order.set);
+        assert_eq!(forward.order.at, back.order.at);
+    }
+
+    #[test]
+    fn restricted_files_are_matched_by_name() {
+        let restricted = [
+            "dinput8.dll",
+            "dinput8.dll.disabled",
+            "DInput8.DLL",
+            "mods\\dinput8.dll",
+            "mods\\Sub\\dinput8.dll.disabled",
+            "C:\\Downloads\\mod\\eldenring.exe",
+            "mods\\mod_loader_config.ini",
+        ];
+        let allowed = [
+            "mods\\my_dinput8.dll",
+            "mods\\dinput8\\config.ini",
+            "mods\\eldenring.exe.txt",
+        ];
+
+        for file in restricted {
+            assert!(is_restricted_file(Path::new(file)), "{file} was not restricted");
+        }
+        for file in allowed {
+            assert!(!is_restricted_file(Path::new(file)), "{file} was restricted");
+        }
+    }
+
+    #[test]
+    fn game_version_requires_exe_with_version_info() {
+        let missing = read_game_version(Path::new("temp\\no_game_here")).unwrap_err();
+        assert_eq!(missing.kind(), ErrorKind::NotFound);
+
+        // mock game files are empty so they never contain version info
+        let mock = MockGameDir::builder("game_version").build().unwrap();
+        let err = read_game_version(mock.game_dir()).unwrap_err();
+        assert!(
+            matches!(err.kind(), ErrorKind::InvalidData | ErrorKind::Unsupported),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn mod_size_on_disk_is_summed_and_displayed() {
+        let mock = MockGameDir::builder("size_on_disk").build().unwrap();
+        let game_dir = mock.game_dir();
+        let empty = RegMod::new("empty", true, Vec::new());
+        assert_eq!(empty.total_size_on_disk(game_dir).unwrap(), 0);
+
+        let files = ["mods\\a_mod.dll", "mods\\a_mod.ini"];
+        fs::create_dir_all(game_dir.join("mods")).unwrap();
+        fs::write(game_dir.join(files[0]), vec![0; 1024]).unwrap();
+        fs::write(game_dir.join(files[1]), vec![0; 512]).unwrap();
+        let a_mod = RegMod::new("a_mod", true, files.iter().map(PathBuf::from).collect());
+        assert_eq!(a_mod.total_size_on_disk(game_dir).unwrap(), 1536);
+
+        // a missing file is counted as 0 bytes
+        fs::remove_file(game_dir.join(files[1])).unwrap();
+        assert_eq!(a_mod.total_size_on_disk(game_dir).unwrap(), 1024);
+
+        assert_eq!(DisplaySize(0).to_string(), "0 B");
+        assert_eq!(DisplaySize(1023).to_string(), "1023 B");
+        assert_eq!(DisplaySize(1024).to_string(), "1.0 KB");
+        assert_eq!(DisplaySize(1536 * 1024).to_string(), "1.5 MB");
+    }
+
+    #[test]
+    fn game_pass_installs_are_detected() {
+        let mock = MockGameDir::builder("install_type").build().unwrap();
+        assert_eq!(detect_install_type(mock.game_dir()), InstallType::Steam);
+
+        let store_dir = Path::new("temp\\game_pass_install");
+        fs::create_dir_all(store_dir).unwrap();
+        assert_eq!(detect_install_type(store_dir), InstallType::Unknown);
+        File::create(store_dir.join(GAME_PASS_FILES[0])).unwrap();
+        assert_eq!(detect_install_type(store_dir), InstallType::GamePass);
+
+        let xbox_dir = Path::new("temp").join("XboxGames").join("ELDEN RING");
+        assert_eq!(detect_install_type(&xbox_dir), InstallType::GamePass);
+
+        let mut ini = Cfg::read(mock.ini_dir()).unwrap();
+        let err = ini.relocate_game_dir(store_dir).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        fs::remove_dir_all(store_dir).unwrap();
+    }
+
+    #[test]
+    fn cfg_paths_are_built_from_explicit_dir() {
+        let mock = MockGameDir::builder("cfg_paths")
+            .add_mod("a_mod", &["mods\\a_mod.dll"])
+            .order(1)
+            .build()
+            .unwrap();
+
+        let loader_dir = loader_cfg_path(mock.game_dir());
+        assert_eq!(loader_dir, mock.loader_dir());
+        let loader_cfg = ModLoaderCfg::read(&loader_dir).unwrap();
+        assert_eq!(loader_cfg.parse_into_map().get("a_mod.dll"), Some(&1));
+
+        let ini_dir = ini_cfg_path(mock.game_dir().parent().unwrap());
+        assert_eq!(ini_dir, mock.ini_dir());
+        assert_eq!(Cfg::read(&ini_dir).unwrap().mods_registered(), 1);
+    }
+
+    #[test]
+    fn scan_reports_empty_mods_folder() {
+        let mock = MockGameDir::builder("scan_empty_mods").build().unwrap();
+        fs::create_dir_all(mock.game_dir().join("mods")).unwrap();
+
+        let scan = discover_mods(mock.game_dir()).unwrap();
+        assert!(scan.folder_is_empty());
+        assert_eq!(scan.dlls_found(), 0);
+        assert_eq!(
+            scan_for_mods(mock.game_dir(), mock.ini_dir(), ScanMode::Reset).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn missing_mods_folder_is_created_on_request() {
+        let mock = MockGameDir::builder("missing_mods_folder").build().unwrap();
+        let mods_dir = mock.game_dir().join("mods");
+        assert!(!mods_dir.exists());
+
+        let err = discover_mods(mock.game_dir()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        assert_eq!(ensure_mods_dir(mock.game_dir()).unwrap(), mods_dir);
+        assert!(mods_dir.is_dir());
+        // calling again with an existing folder is a no-op
+        assert_eq!(ensure_mods_dir(mock.game_dir()).unwrap(), mods_dir);
+        assert!(discover_mods(mock.game_dir()).unwrap().folder_is_empty());
+    }
+
+    #[test]
+    fn install_moves_or_copies_files() {
+        let mock = MockGameDir::builder("install_move_files").build().unwrap();
+        let downloads = mock.game_dir().parent().unwrap().join("downloads");
+        let mods_dir = mock.game_dir().join("mods");
+        let new_sources = || {
+            fs::create_dir_all(&downloads).unwrap();
+            ["a_mod.dll", "config.ini"].map(|file| {
+                let path = downloads.join(file);
+                File::create(&path).unwrap();
+                path
+            })
+        };
+        let zip_with = |sources: &[PathBuf]| {
+            sources
+                .iter()
+                .map(|from| (from.clone(), mods_dir.join(from.file_name().unwrap())))
+                .collect::<Vec<_>>()
+        };
+        let (progress, _receiver) = std::sync::mpsc::channel();
+        let cancel = AtomicBool::new(false);
+
+        let sources = new_sources();
+        let zip = zip_with(&sources);
+        copy_with_progress(&zip, false, &progress, &cancel).unwrap();
+        assert!(zip.iter().all(|(from, to)| from.exists() && to.exists()));
+        fs::remove_dir_all(&mods_dir).unwrap();
+
+        // same volume, files are renamed into place
+        copy_with_progress(&zip, true, &progress, &cancel).unwrap();
+        assert!(zip.iter().all(|(from, to)| !from.exists() && to.exists()));
+        fs::remove_dir_all(&mods_dir).unwrap();
+
+        // a failed move returns files that were already moved
+        let mut sources = new_sources().to_vec();
+        sources.push(downloads.join("missing.dll"));
+        let zip = zip_with(&sources);
+        assert!(copy_with_progress(&zip, true, &progress, &cancel).is_err());
+        assert!(zip[..2].iter().all(|(from, _)| from.exists()));
+        assert!(!mods_dir.exists());
+
+        // moving to another volume falls back to copy and delete
+        let crosses_devices =
+            |_: &Path, _: &Path| Err(std::io::Error::from(ErrorKind::CrossesDevices));
+        let zip = zip_with(&sources[..2]);
+        copy_with_progress_using(&zip, true, &progress, &cancel, crosses_devices).unwrap();
+        assert!(zip.iter().all(|(from, to)| !from.exists() && to.exists()));
+
+        let sources = new_sources();
+        let copy = mods_dir.join("copy.dll");
+        let (transfer, _) = transfer_file(&sources[0], &copy, true, crosses_devices).unwrap();
+        assert_eq!(transfer, Transfer::CopiedForMove);
+        assert!(sources[0].exists() && copy.exists());
+
+        let copy = mods_dir.join("copy_2.dll");
+        let (transfer, _) =
+            transfer_file(&sources[0], &copy, false, |from, to| fs::rename(from, to)).unwrap();
+        assert_eq!(transfer, Transfer::Copied);
+        assert!(sources[0].exists());
+    }
+
+    #[test]
+    fn failed_install_restores_overwritten_files() {
+        let mock = MockGameDir::builder("install_overwrite").build().unwrap();
+        let downloads = mock.game_dir().parent().unwrap().join("downloads");
+        let mods_dir = mock.game_dir().join("mods");
+        fs::create_dir_all(&downloads).unwrap();
+        fs::create_dir_all(&mods_dir).unwrap();
+        fs::write(downloads.join("a_mod.dll"), "new").unwrap();
+        fs::write(mods_dir.join("a_mod.dll"), "old").unwrap();
+        let (progress, _receiver) = std::sync::mpsc::channel();
+        let cancel = AtomicBool::new(false);
+
+        let zip =
+            ["a_mod.dll", "missing.dll"].map(|file| (downloads.join(file), mods_dir.join(file)));
+        assert!(copy_with_progress(&zip, false, &progress, &cancel).is_err());
+        assert_eq!(
+            fs::read_to_string(mods_dir.join("a_mod.dll")).unwrap(),
+            "old"
+        );
+        assert!(!replaced_path(&mods_dir.join("a_mod.dll")).exists());
+        assert!(!mods_dir.join("missing.dll").exists());
+
+        copy_with_progress(&zip[..1], false, &progress, &cancel).unwrap();
+        assert_eq!(
+            fs::read_to_string(mods_dir.join("a_mod.dll")).unwrap(),
+            "new"
+        );
+        assert!(!replaced_path(&mods_dir.join("a_mod.dll")).exists());
+    }
+
+    #[test]
+    fn merge_scan_only_adds_unregistered_mods() {
+        let mock = MockGameDir::builder("merge_scan")
+            .add_mod("a_mod", &["mods\\a_mod.dll", "mods\\a_mod\\config.ini"])
+            .enabled(false)
+            .order(1)
+            .build()
+            .unwrap();
+        let mut ini = Cfg::read(mock.ini_dir()).unwrap();
+        ini.set_mod_note("a_mod", "keep me");
+        ini.write_to_file().unwrap();
+        let mods_dir = mock.game_dir().join("mods");
+        fs::create_dir_all(mods_dir.join("b_mod")).unwrap();
+        File::create(mods_dir.join("b_mod.dll")).unwrap();
+        File::create(mods_dir.join("b_mod\\config.ini")).unwrap();
+        let loader_before = fs::read_to_string(mock.loader_dir()).unwrap();
+
+        // the disabled `a_mod.dll` is still recognized as registered
+        let mut scan = discover_mods(mock.game_dir()).unwrap();
+        assert_eq!(scan.dlls_found(), 2);
+        assert_eq!(scan.retain_unregistered(&ini), 1);
+        assert_eq!(scan.mods[0].name, "b_mod");
+
+        assert_eq!(
+            scan_for_mods(mock.game_dir(), mock.ini_dir(), ScanMode::Merge).unwrap(),
+            1
+        );
+        let mut ini = Cfg::read(mock.ini_dir()).unwrap();
+        let a_mod = ini.get_mod(&"a_mod".into(), mock.game_dir(), None).unwrap();
+        assert!(!a_mod.state);
+        assert_eq!(ini.get_mod_note("a_mod").as_deref(), Some("keep me"));
+        assert!(ini.get_mod(&"b_mod".into(), mock.game_dir(), None).is_ok());
+        assert_eq!(ini.keys().len(), 2);
+        assert_eq!(fs::read_to_string(mock.loader_dir()).unwrap(), loader_before);
+
+        // nothing is left to add
+        assert_eq!(
+            scan_for_mods(mock.game_dir(), mock.ini_dir(), ScanMode::Merge).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn toggle_configs_renames_ini_with_dll() {
+        let mock = MockGameDir::builder("toggle_configs")
+            .add_mod("cfg_mod", &["mods\\cfg_mod.dll", "mods\\cfg_mod\\config.ini"])
+            .build()
+            .unwrap();
+        let (game_dir, ini_dir) = (mock.game_dir(), mock.ini_dir());
+        let config = game_dir.join("mods\\cfg_mod\\config.ini");
+        let config_disabled = game_dir.join("mods\\cfg_mod\\config.ini.disabled");
+        let ini = Cfg::read(ini_dir).unwrap();
+        let mut reg_mod = ini.get_mod(&"cfg_mod".into(), game_dir, None).unwrap();
+        assert!(!reg_mod.toggle_configs);
+
+        // by default only dlls are toggled
+        toggle_files(game_dir, false, &mut reg_mod, Some(ini_dir)).unwrap();
+        assert!(file_exists(&config));
+        toggle_files(game_dir, true, &mut reg_mod, Some(ini_dir)).unwrap();
+
+        reg_mod.set_toggle_configs(true, game_dir, ini_dir).unwrap();
+        toggle_files(game_dir, false, &mut reg_mod, Some(ini_dir)).unwrap();
+        assert!(file_exists(&game_dir.join("mods\\cfg_mod.dll.disabled")));
+        assert!(file_exists(&config_disabled));
+
+        let ini = Cfg::read(ini_dir).unwrap();
+        let reg_mod = ini.get_mod(&"cfg_mod".into(), game_dir, None).unwrap();
+        assert!(reg_mod.toggle_configs);
+        assert!(!reg_mod.state);
+        assert_eq!(
+            reg_mod.files.config,
+            [PathBuf::from("mods\\cfg_mod\\config.ini.disabled")]
+        );
+
+        // a config renamed outside of the app is recovered then put back in the state of the mod
+        fs::rename(&config_disabled, &config).unwrap();
+        let mut reg_mod = ini.collect_mods(game_dir, None, false).mods.pop().unwrap();
+        assert!(file_exists(&config_disabled));
+        assert_eq!(
+            reg_mod.files.config,
+            [PathBuf::from("mods\\cfg_mod\\config.ini.disabled")]
+        );
+
+        // configs are enabled again once they no longer toggle with the mod
+        reg_mod.set_toggle_configs(false, game_dir, ini_dir).unwrap();
+        assert!(file_exists(&config));
+        assert!(!reg_mod.state);
+        assert!(!Cfg::read(ini_dir).unwrap().toggles_configs("cfg_mod"));
+    }
+
+    #[test]
+    fn register_existing_files_without_install() {
+        let mock = MockGameDir::builder("register_existing")
+            .add_mod("a_mod", &["mods\\a_mod.dll"])
+            .build()
+            .unwrap();
+        let (game_dir, loader_dir) = (mock.game_dir(), mock.loader_dir());
+        fs::create_dir_all(game_dir.join("mods\\b_mod")).unwrap();
+        File::create(game_dir.join("mods\\b_mod.dll.disabled")).unwrap();
+        File::create(game_dir.join("mods\\b_mod\\config.ini")).unwrap();
+        let files = vec![
+            PathBuf::from("mods\\b_mod.dll.disabled"),
+            PathBuf::from("mods\\b_mod\\config.ini"),
+        ];
+
+        let mut ini = Cfg::read(mock.ini_dir()).unwrap();
+        let reg_mod = ini.register_existing("b mod", files, game_dir, loader_dir, None).unwrap();
+        assert_eq!(reg_mod.name, "b_mod");
+        assert!(!reg_mod.state);
+        assert_eq!(reg_mod.files.len(), 2);
+        assert!(ini.keys().contains("b_mod"));
+        let saved = Cfg::read(mock.ini_dir()).unwrap();
+        let saved_mod = saved.get_mod(&"b_mod".into(), game_dir, None).unwrap();
+        assert!(!saved_mod.state);
+        assert_eq!(saved_mod.files.len(), 2);
+
+        let mut register = |name: &str, file: &str| {
+            ini.register_existing(name, vec![PathBuf::from(file)], game_dir, loader_dir, None)
+                .unwrap_err()
+                .kind()
+        };
+        assert_eq!(register("B_Mod", "mods\\c_mod.dll"), ErrorKind::AlreadyExists);
+        assert_eq!(register("c_mod", "mods\\a_mod.dll"), ErrorKind::AlreadyExists);
+        assert_eq!(register("c_mod", "mods\\c_mod.dll"), ErrorKind::NotFound);
+        assert_eq!(register("", "mods\\a_mod.dll"), ErrorKind::InvalidInput);
+        assert_eq!(ini.keys().len(), 2);
+
+        fs::create_dir_all(game_dir.join("mods\\d_mod")).unwrap();
+        let files = ["mods\\d_mod.dll", "mods\\d_mod\\a.txt", "mods\\d_mod\\b.txt"]
+            .map(|file| {
+                File::create(game_dir.join(file)).unwrap();
+                PathBuf::from(file)
+            })
+            .to_vec();
+        let track_dir = Some(PathBuf::from("mods\\d_mod"));
+        let reg_mod = ini
+            .register_existing("d_mod", files, game_dir, loader_dir, track_dir)
+            .unwrap();
+        assert_eq!(reg_mod.file_set, FileSet::Dir(PathBuf::from("mods\\d_mod")));
+        assert_eq!(reg_mod.files.len(), 1);
+        let saved = Cfg::read(mock.ini_dir()).unwrap();
+        let saved_mod = saved.get_mod(&"d_mod".into(), game_dir, None).unwrap();
+        assert_eq!(saved_mod.file_set, reg_mod.file_set);
+    }
+
+    #[test]
+    fn duplicated_mod_copies_files_and_keeps_original() {
+        let mock = MockGameDir::builder("duplicate_mod")
+            .add_mod("a_mod", &["mods\\a_mod.dll", "mods\\a_mod\\config.ini"])
+            .enabled(false)
+            .order(1)
+            .build()
+            .unwrap();
+        let game_dir = mock.game_dir();
+        fs::write(game_dir.join("mods\\a_mod\\config.ini"), "key = value").unwrap();
+
+        let mut ini = Cfg::read(mock.ini_dir()).unwrap();
+        let copy = ini.duplicate_mod("a_mod", "a variant", game_dir).unwrap();
+        assert_eq!(copy.name, "a_variant");
+        assert!(!copy.state);
+        assert!(!copy.order.set);
+        assert_eq!(copy.files.dll, [PathBuf::from("mods\\a_variant\\a_variant.dll.disabled")]);
+        assert_eq!(copy.files.config, [PathBuf::from("mods\\a_variant\\a_mod\\config.ini")]);
+        assert!(file_exists(&game_dir.join("mods\\a_mod.dll.disabled")));
+        assert!(file_exists(&game_dir.join("mods\\a_variant\\a_variant.dll.disabled")));
+        assert_eq!(
+            fs::read_to_string(game_dir.join("mods\\a_variant\\a_mod\\config.ini")).unwrap(),
+            "key = value"
+        );
+
+        let mut saved = Cfg::read(mock.ini_dir()).unwrap();
+        assert!(saved.keys().contains("a_mod"));
+        assert!(saved.keys().contains("a_variant"));
+        let saved_copy = saved.get_mod(&"a_variant".into(), game_dir, None).unwrap();
+        assert_eq!(saved_copy.files.len(), 2);
+        let mut order = ModLoaderCfg::read(mock.loader_dir()).unwrap();
+        assert_eq!(order.section().len(), 1);
+        let order_map = order.parse_section(&HashSet::new()).unwrap();
+        let mods = saved.collect_mods(game_dir, Some(&order_map), false).mods;
+        let find = |name: &str| mods.iter().find(|reg_mod| reg_mod.name == name).unwrap();
+        assert!(find("a_mod").order.set);
+        assert!(!find("a_variant").order.set);
+
+        let err = ini.duplicate_mod("a_mod", "A_Variant", game_dir).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        let err = ini.duplicate_mod("missing", "b_mod", game_dir).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn file_patterns_expand_within_game_dir() {
+        let mock = MockGameDir::builder("file_pattern").build().unwrap();
+        let game_dir = mock.game_dir();
+        fs::create_dir_all(game_dir.join("mods\\pat\\sub")).unwrap();
+        fs::create_dir_all(game_dir.join("mods\\many")).unwrap();
+        for file in ["a.dll", "B.DLL", "c.txt", "sub\\d.dll"] {
+            File::create(game_dir.join("mods\\pat").join(file)).unwrap();
+        }
+        for i in 0..=MAX_PATTERN_MATCHES {
+            File::create(game_dir.join(format!("mods\\many\\{i}.dds"))).unwrap();
+        }
+
+        let expand = |pattern: &str| expand_file_pattern(game_dir, pattern);
+        assert_eq!(
+            expand("mods\\pat\\*.dll").unwrap(),
+            vec![
+                PathBuf::from("mods\\pat\\a.dll"),
+                PathBuf::from("mods\\pat\\B.DLL"),
+            ]
+        );
+        assert_eq!(expand("mods\\pat\\**\\*.dll").unwrap().len(), 3);
+        let err_kind = |pattern: &str| expand(pattern).unwrap_err().kind();
+        assert_eq!(err_kind("mods\\pat\\*.zip"), ErrorKind::NotFound);
+        // escapes game_dir, matches restricted files, matches too many files
+        assert_eq!(err_kind("..\\*.dll"), ErrorKind::InvalidInput);
+        assert_eq!(err_kind("*.dll"), ErrorKind::InvalidInput);
+        assert_eq!(err_kind("mods\\many\\*"), ErrorKind::InvalidInput);
+
+        // the expanded files are registered, not the pattern
+        let files = expand("mods\\pat\\*.txt").unwrap();
+        let mut ini = Cfg::read(mock.ini_dir()).unwrap();
+        let reg_mod = ini
+            .register_existing("pat", files, game_dir, mock.loader_dir(), None)
+            .unwrap();
+        assert_eq!(reg_mod.files.len(), 1);
+        assert!(ini.files().contains("mods\\pat\\c.txt"));
+    }
+
+    #[test]
+    fn stale_order_index_is_rejected() {
+        let mut reg_mod = RegMod::new(
+            "stale_order",
+            true,
+            vec![
+                PathBuf::from("mods\\stale_order.dll"),
+                PathBuf::from("mods\\stale_order\\second.dll"),
+            ],
+        );
+        reg_mod.order = LoadOrder::new(true, 1, 3);
+        assert_eq!(reg_mod.validated_order_index(), Some(1));
+
+        // the second dll was removed without updating the saved order
+        reg_mod.files.dll.pop();
+        assert_eq!(reg_mod.validated_order_index(), None);
+        assert_eq!(
+            remove_order_entry(&reg_mod, Path::new("temp\\stale_order.ini"))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn config_contents_are_labeled_with_path() {
+        let mock = MockGameDir::builder("labeled_config").build().unwrap();
+        let contents = labeled_config_contents(mock.ini_dir());
+        assert!(contents.contains(&mock.ini_dir().display().to_string()));
+        assert!(contents.contains("[registered-mods]"));
+
+        let missing = mock.game_dir().join("missing.ini");
+        let contents = labeled_config_contents(&missing);
+        assert!(contents.starts_with("===== missing.ini ====="));
+        assert!(contents.ends_with("File does not exist yet"));
+    }
+
+    #[test]
+    fn tracked_dir_files_are_read_from_disk() {
+        let mock = MockGameDir::builder("tracked_dir")
+            .add_mod(
+                "big_mod",
+                &[
+                    "mods\\big_mod.dll",
+                    "mods\\big_mod\\config.ini",
+                    "mods\\big_mod\\a.dds",
+                    "mods\\big_mod\\b.dds",
+                ],
+            )
+            .build()
+            .unwrap();
+        let ini = Cfg::read(mock.ini_dir()).unwrap();
+        let mut big_mod = ini.get_mod(&"big_mod".into(), mock.game_dir(), None).unwrap();
+        assert_eq!(big_mod.file_set, FileSet::Explicit);
+        let was_array = big_mod.is_array();
+        assert_eq!(big_mod.track_by_dir(PathBuf::from("mods\\big_mod")), 2);
+        big_mod.write_to_file(mock.ini_dir(), was_array).unwrap();
+
+        // files added to the directory after registration are part of the mod
+        File::create(mock.game_dir().join("mods\\big_mod\\c.dds")).unwrap();
+        let ini = Cfg::read(mock.ini_dir()).unwrap();
+        let big_mod = ini.get_mod(&"big_mod".into(), mock.game_dir(), None).unwrap();
+        assert_eq!(big_mod.file_set, FileSet::Dir(PathBuf::from("mods\\big_mod")));
+        assert_eq!(big_mod.files.len(), 2);
+        assert_eq!(big_mod.all_files(mock.game_dir()).unwrap().len(), 5);
+        assert!(ini.unregistered_files(mock.game_dir(), &HashSet::new()).unwrap().is_empty());
+
+        remove_mod_files(mock.game_dir(), mock.loader_dir(), &big_mod).unwrap();
+        assert!(!mock.game_dir().join("mods\\big_mod").exists());
+    }
+
+    #[test]
+    fn scan_reports_files_without_dlls() {
+        let mock = MockGameDir::builder("scan_no_dlls").build().unwrap();
+        let mods_dir = mock.game_dir().join("mods");
+        fs::create_dir_all(mods_dir.join("assets")).unwrap();
+        File::create(mods_dir.join("readme.txt")).unwrap();
+        File::create(mods_dir.join("config.ini")).unwrap();
+
+        let scan = discover_mods(mock.game_dir()).unwrap();
+        assert!(!scan.folder_is_empty());
+        assert_eq!(scan.dlls_found(), 0);
+        assert_eq!(scan.non_dll_files, 2);
+        assert_eq!(scan.dirs_seen, 1);
+        assert_eq!(
+            scan_for_mods(mock.game_dir(), mock.ini_dir(), ScanMode::Reset).unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn app_running_from_game_dir_is_flagged() {
+        let game_dir = Path::new("C:\\Games\\ELDEN RING\\Game");
+        let check = |ini_dir: &str| check_app_location(Path::new(ini_dir), game_dir);
+
+        assert_eq!(
+            check("C:\\Games\\ELDEN RING\\Game\\app.ini"),
+            Some(AppLocationWarning::InGameDir)
+        );
+        assert_eq!(
+            check("c:\\games\\elden ring\\game\\eml\\app.ini"),
+            Some(AppLocationWarning::WithinGameDir)
+        );
+        assert_eq!(check("C:\\Games\\ELDEN RING\\Game\\..\\app.ini"), None);
+        assert_eq!(check("C:\\Games\\ELDEN RING\\app.ini"), None);
+        assert_eq!(check("D:\\EML\\app.ini"), None);
+    }
+
+    #[test]
+    fn mod_names_compare_ignoring_case_and_separators() {
+        use std::cmp::Ordering;
+
+        assert_eq!(cmp_mod_names("Alpha_Mod", "alpha mod"), Ordering::Equal);
+        assert_eq!(cmp_mod_names("alpha", "Bravo"), Ordering::Less);
+        assert_eq!(cmp_mod_names("bravo_2", "bravo"), Ordering::Greater);
+    }
+
+    #[test]
+    fn mod_name_keys_are_canonical() {
+        assert_eq!(format_key("  Über   Mod "), "Über_Mod");
+        assert_eq!(format_key("a_ _b\t c"), "a_b_c");
+        assert_eq!(format_key("U\u{308}ber"), "\u{dc}ber");
+        assert_eq!(canonical_key("ÜBER MOD"), "über_mod");
+        assert_eq!(canonical_key("U\u{308}BER_mod "), canonical_key("über mod"));
+        assert_eq!(canonical_key("İSTANBUL"), canonical_key("İstanbul"));
+        assert_ne!(canonical_key("Uber Mod"), canonical_key("Über Mod"));
+    }
+
+    #[test]
+    fn categories_group_together_with_default_last() {
+        let mut rows = vec![
+            (DEFAULT_CATEGORY, 0),
+            ("weapons", 2),
+            ("Graphics", 1),
+            ("Weapons", 0),
+            (DEFAULT_CATEGORY, 1),
+            ("graphics", 0),
+            ("Weapons", 1),
+        ];
+        rows.sort_by(|a, b| cmp_categories(a.0, b.0).then(a.1.cmp(&b.1)));
+
+        assert_eq!(
+            rows,
+            [
+                ("Graphics", 1),
+                ("graphics", 0),
+                ("Weapons", 0),
+                ("Weapons", 1),
+                ("weapons", 2),
+                (DEFAULT_CATEGORY, 0),
+                (DEFAULT_CATEGORY, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn toggle_all_mods_skips_loader_and_always_active() {
+        let mock = MockGameDir::builder("toggle_all_mods")
+            .add_mod("a_mod", &["mods\\a_mod.dll", "mods\\a_mod\\config.ini"])
+            .add_mod("b_mod", &["mods\\b_mod.dll"])
+            .enabled(false)
+            .add_mod("c_mod", &["mods\\c_mod.dll"])
+            .add_mod("no_dll", &["mods\\no_dll\\texture.dds"])
+            .build()
+            .unwrap();
+        let game_dir = mock.game_dir();
+        let collect = || {
+            Cfg::read(mock.ini_dir())
+                .unwrap()
+                .collect_mods(game_dir, None, false)
+                .mods
+        };
+
+        let mut reg_mods = collect();
+        let toggled = toggle_all_mods(game_dir, false, &mut reg_mods, Some(mock.ini_dir()));
+        assert_eq!(toggled.unwrap(), 2);
+        assert!(collect().iter().all(|m| !m.state || m.is_always_active()));
+        assert!(file_exists(&game_dir.join(format!("mods\\c_mod.dll{OFF_STATE}"))));
+        assert!(file_exists(&game_dir.join(LOADER_FILES[1])));
+
+        let mut reg_mods = collect();
+        let toggled = toggle_all_mods(game_dir, true, &mut reg_mods, Some(mock.ini_dir()));
+        assert_eq!(toggled.unwrap(), 3);
+        assert!(collect().iter().all(|m| m.state));
+    }
+
+    #[test]
+    fn toggle_all_mods_rolls_back_on_failure() {
+        let mock = MockGameDir::builder("toggle_all_mods_rollback")
+            .add_mod("a_mod", &["mods\\a_mod.dll"])
+            .add_mod("b_mod", &["mods\\b_mod.dll"])
+            .build()
+            .unwrap();
+        let game_dir = mock.game_dir();
+        let mut reg_mods = Cfg::read(mock.ini_dir())
+            .unwrap()
+            .collect_mods(game_dir, None, false)
+            .mods;
+        assert_eq!(reg_mods[1].name, "b_mod");
+        fs::remove_file(game_dir.join("mods\\b_mod.dll")).unwrap();
+
+        assert!(toggle_all_mods(game_dir, false, &mut reg_mods, Some(mock.ini_dir())).is_err());
+        assert!(reg_mods[0].state);
+        assert!(file_exists(&game_dir.join("mods\\a_mod.dll")));
+    }
+
+    #[test]
+    fn toggle_single_file_leaves_mod_in_mixed_state() {
+        let mock = MockGameDir::builder("toggle_single_file")
+            .add_mod("multi", &["mods\\multi_a.dll", "mods\\multi_b.dll"])
+            .build()
+            .unwrap();
+        let game_dir = mock.game_dir();
+        let collect = || {
+            Cfg::read(mock.ini_dir())
+                .unwrap()
+                .collect_mods(game_dir, None, false)
+                .mods
+                .remove(0)
+        };
+        let file_a = PathBuf::from("mods\\multi_a.dll");
+        let file_b = PathBuf::from("mods\\multi_b.dll");
+
+        let mut reg_mod = collect();
+        toggle_single_file(game_dir, &file_a, false, &mut reg_mod, Some(mock.ini_dir())).unwrap();
+        assert!(reg_mod.state);
+        assert!(file_exists(&game_dir.join(format!("mods\\multi_a.dll{OFF_STATE}"))));
+        assert!(file_exists(&game_dir.join(&file_b)));
+
+        // a mixed state is kept by `verify_state` which runs within `collect_mods`
+        let mut reg_mod = collect();
+        assert!(reg_mod.state);
+        assert!(file_exists(&game_dir.join(&file_b)));
+
+        toggle_single_file(game_dir, &file_b, false, &mut reg_mod, Some(mock.ini_dir())).unwrap();
+        assert!(!reg_mod.state);
+        assert!(!collect().state);
+
+        // the path can be given in either state
+        toggle_single_file(game_dir, &file_a, true, &mut reg_mod, Some(mock.ini_dir())).unwrap();
+        assert!(reg_mod.state);
+        assert!(file_exists(&game_dir.join(&file_a)));
+
+        let err = toggle_single_file(
+            game_dir,
+            Path::new("mods\\other.dll"),
+            true,
+            &mut reg_mod,
+            Some(mock.ini_dir()),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn get_drive_handles_unc_and_extended_paths() {
+        let disk = |root: &str| DriveRoot::Disk(String::from(root));
+        let share = |root: &str| DriveRoot::Network(String::from(root));
+
+        assert_eq!(get_drive(Path::new("c:\\Games\\ELDEN RING")).unwrap(), disk("C:\\"));
+        assert_eq!(get_drive(Path::new("D:/Games")).unwrap(), disk("D:\\"));
+        assert_eq!(get_drive(Path::new("\\\\?\\E:\\Games")).unwrap(), disk("E:\\"));
+        assert_eq!(
+            get_drive(Path::new("\\\\server\\share\\Games")).unwrap(),
+            share("\\\\server\\share\\")
+        );
+        assert_eq!(
+            get_drive(Path::new("\\\\?\\UNC\\server\\share\\Games")).unwrap(),
+            share("\\\\server\\share\\")
+        );
+        assert_eq!(
+            get_drive(Path::new("\\\\server\\share")).unwrap().to_string(),
+            "\\\\server\\share\\"
+        );
+
+        for invalid in ["\\\\server", "\\\\?\\UNC\\server", "relative\\path", ""] {
+            assert_eq!(
+                get_drive(Path::new(invalid)).unwrap_err().kind(),
+                ErrorKind::InvalidData
+            );
+        }
+    }
+
+    #[test]
+    fn removal_preview_matches_removed_files() {
+        let nested_files = [
+            "mods\\nested_mod.dll",
+            "mods\\nested_mod\\config\\settings.ini",
+            "mods\\nested_mod\\assets\\a.txt",
+        ];
+        let mock = MockGameDir::builder("removal_preview")
+            .add_mod("nested_mod", &nested_files)
+            .add_mod("other_mod", &["mods\\other_mod.dll", "mods\\nested_mod\\assets\\b.txt"])
+            .build()
+            .unwrap();
+        let game_dir = mock.game_dir();
+        let reg_mod = RegMod::new("nested_mod", true, nested_files.map(PathBuf::from).to_vec());
+
+        let mut preview = reg_mod.removal_preview(game_dir);
+        preview.files.sort();
+        let mut expected_files = nested_files.map(|file| game_dir.join(file)).to_vec();
+        expected_files.sort();
+        assert_eq!(preview.files, expected_files);
+        assert_eq!(preview.dirs, [game_dir.join("mods\\nested_mod\\config")]);
+        assert!(preview.files.iter().all(|file| file.exists()));
+
+        remove_mod_files(game_dir, mock.loader_dir(), &reg_mod).unwrap();
+
+        assert!(preview.files.iter().chain(&preview.dirs).all(|path| !path.exists()));
+        assert!(file_exists(&game_dir.join("mods\\nested_mod\\assets\\b.txt")));
+        assert!(reg_mod.removal_preview(game_dir).is_empty());
+    }
+
+    #[test]
+    fn clean_orphaned_files_keeps_registered_files() {
+        let mock = MockGameDir::builder("clean_orphaned")
+            .add_mod("kept", &["mods\\kept.dll", "mods\\kept\\config.ini"])
+            .build()
+            .unwrap();
+        let orphans = [
+            PathBuf::from("mods\\leftover\\leftover.dll"),
+            PathBuf::from("mods\\leftover\\nested\\readme.txt"),
+            PathBuf::from("mods\\stray.txt"),
+        ];
+        for file in orphans.iter() {
+            let path = mock.game_dir().join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            File::create(path).unwrap();
+        }
+        let loader_file = mock.game_dir().join("mods").join(LOADER_FILES[1]);
+        File::create(&loader_file).unwrap();
+        let upper_case_hook = mock.game_dir().join("mods\\DINPUT8.dll");
+        File::create(&upper_case_hook).unwrap();
+        let unknown_dll = mock.game_dir().join("mods\\Unknown.dll");
+        File::create(&unknown_dll).unwrap();
+        let unknown_keys = HashSet::from([String::from("unknown.dll")]);
+
+        let found = orphaned_files(mock.game_dir(), mock.ini_dir(), &unknown_keys).unwrap();
+        assert_eq!(found, orphans);
+        assert!(orphans.iter().all(|file| file_exists(&mock.game_dir().join(file))));
+
+        let removed = clean_orphaned_files(mock.game_dir(), mock.ini_dir(), &unknown_keys).unwrap();
+        assert_eq!(removed, orphans);
+        assert!(!orphans.iter().any(|file| file_exists(&mock.game_dir().join(file))));
+        assert!(!mock.game_dir().join("mods\\leftover").exists());
+        assert!(file_exists(&mock.game_dir().join("mods\\kept.dll")));
+        assert!(file_exists(&mock.game_dir().join("mods\\kept\\config.ini")));
+        assert!(file_exists(&loader_file));
+        assert!(file_exists(&upper_case_hook));
+        assert!(file_exists(&unknown_dll));
+    }
+
+    #[test]
+    fn ui_rw_lock_overlapping_reads() {
+        let lock = UiRwLock::new("test_lock", 1);
+        let first = lock.read();
+        let second = lock.read();
+        assert_eq!(*first + *second, 2);
+        assert!(lock.try_write().is_none());
+        drop((first, second));
+
+        *lock.write() += 1;
+        let write = lock.write();
+        assert!(lock.try_read().is_none());
+        drop(write);
+        assert_eq!(*lock.read(), 2);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "read of: test_lock, requested while a conflicting guard is held")]
+    fn ui_rw_lock_panics_instead_of_deadlock() {
+        let lock = UiRwLock::new("test_lock", Vec::<String>::new());
+        let _write = lock.write();
+        let _read = lock.read();
+    }
+
+    #[test]
+    fn loader_backend_toggles_dll_hook() {
+        let mock = MockGameDir::builder("loader_backend").build().unwrap();
+
+        let mut loader = loader_backend(mock.game_dir()).unwrap();
+        assert!(loader.installed());
+        assert!(!loader.disabled());
+        assert!(loader.renamed_hook().is_none());
+        assert_eq!(loader.config_path(), mock.loader_dir());
+        let mut order = loader.read_order().unwrap();
+        assert!(!order.order_suspended());
+        assert!(order.parse_order(&HashSet::new()).is_ok());
+
+        loader.set_enabled(mock.game_dir(), false).unwrap();
+        assert!(loader.disabled());
+        assert!(file_exists(&mock.game_dir().join(LOADER_FILES[0])));
+        assert!(!file_exists(&mock.game_dir().join(LOADER_FILES[1])));
+
+        let mut loader = loader_backend(mock.game_dir()).unwrap();
+        assert!(loader.disabled());
+        loader.set_enabled(mock.game_dir(), true).unwrap();
+        assert!(!loader.disabled());
+        assert!(file_exists(&mock.game_dir().join(LOADER_FILES[1])));
+    }
+
+    #[test]
+    fn loader_set_enabled_renames_dll_hook() {
+        let game_dir = Path::new("temp\\loader_set_enabled");
+        if game_dir.exists() {
+            fs::remove_dir_all(game_dir).unwrap();
+        }
+        fs::create_dir_all(game_dir).unwrap();
+        let enabled = game_dir.join(LOADER_FILES[1]);
+        let disabled = game_dir.join(LOADER_FILES[0]);
+        File::create(&enabled).unwrap();
+        let loader = ModLoader::default();
+
+        loader.set_enabled(game_dir, false).unwrap();
+        assert!(!file_exists(&enabled));
+        assert!(file_exists(&disabled));
+
+        // already in the requested state
+        loader.set_enabled(game_dir, false).unwrap();
+        assert!(file_exists(&disabled));
+
+        loader.set_enabled(game_dir, true).unwrap();
+        assert!(file_exists(&enabled));
+        assert!(!file_exists(&disabled));
+
+        // the anti-cheat toggle owns the hook while it is saved as `LOADER_FILES[2]`
+        let anti_cheat = game_dir.join(LOADER_FILES[2]);
+        fs::rename(&enabled, &anti_cheat).unwrap();
+        let err = loader.set_enabled(game_dir, false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(file_exists(&anti_cheat));
+        assert!(!file_exists(&disabled));
+
+        fs::remove_file(&anti_cheat).unwrap();
+        let err = loader.set_enabled(game_dir, true).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        fs::remove_dir_all(game_dir).unwrap();
+    }
+
+    #[test]
+    fn display_vec_formats_empty_input() {
+        let empty: [&str; 0] = [];
+        assert_eq!(DisplayVec(&empty).to_string(), "[]");
+        assert_eq!(DisplayIndices(&[], &["a.dll"]).to_string(), "[]");
+        assert_eq!(DisplayVec(&["a.dll"]).to_string(), "a.dll");
+        assert_eq!(DisplayVec(&["a.dll", "b.dll"]).to_string(), "[a.dll, b.dll]");
+    }
 }
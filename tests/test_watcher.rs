@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod tests {
+    use elden_mod_loader_gui::utils::watcher::{
+        debounce_events, is_self_write, mark_self_write, DEBOUNCE_DELAY, SELF_WRITE_WINDOW,
+    };
+    use notify::{
+        event::{AccessKind, ModifyKind},
+        Event, EventKind,
+    };
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            mpsc, Arc, Mutex,
+        },
+        thread,
+        time::Duration,
+    };
+
+    /// `mark_self_write` is global so tests that depend on its state can not run at the same time
+    static SELF_WRITE_STATE: Mutex<()> = Mutex::new(());
+
+    fn wait_for_self_write_window() {
+        while is_self_write() {
+            thread::sleep(Duration::from_millis(50));
+        }
+    }
+
+    fn modify_event() -> notify::Result<Event> {
+        Ok(Event::new(EventKind::Modify(ModifyKind::Any)))
+    }
+
+    #[test]
+    fn bursts_of_events_are_reported_once() {
+        let _lock = SELF_WRITE_STATE.lock().unwrap();
+        wait_for_self_write_window();
+
+        let (sender, receiver) = mpsc::channel();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handle = thread::spawn({
+            let calls = Arc::clone(&calls);
+            move || debounce_events(receiver, || _ = calls.fetch_add(1, Ordering::SeqCst))
+        });
+
+        sender
+            .send(Ok(Event::new(EventKind::Access(AccessKind::Any))))
+            .unwrap();
+        thread::sleep(DEBOUNCE_DELAY * 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        for _ in 0..5 {
+            sender.send(modify_event()).unwrap();
+            thread::sleep(DEBOUNCE_DELAY / 10);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        thread::sleep(DEBOUNCE_DELAY * 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        sender.send(modify_event()).unwrap();
+        thread::sleep(DEBOUNCE_DELAY * 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        drop(sender);
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn self_writes_are_not_reported() {
+        let _lock = SELF_WRITE_STATE.lock().unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let handle = thread::spawn({
+            let calls = Arc::clone(&calls);
+            move || debounce_events(receiver, || _ = calls.fetch_add(1, Ordering::SeqCst))
+        });
+
+        mark_self_write();
+        assert!(is_self_write());
+        sender.send(modify_event()).unwrap();
+        thread::sleep(DEBOUNCE_DELAY * 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        thread::sleep(SELF_WRITE_WINDOW.saturating_sub(DEBOUNCE_DELAY));
+        wait_for_self_write_window();
+        sender.send(modify_event()).unwrap();
+        thread::sleep(DEBOUNCE_DELAY * 2);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        drop(sender);
+        handle.join().unwrap();
+    }
+}